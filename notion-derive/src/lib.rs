@@ -0,0 +1,194 @@
+//! The `NotionRow` derive macro, re-exported by the `notion` crate with its `derive` feature.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, Span};
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr};
+
+/// Implements `FromProperties` and `IntoProperties` for a struct with named fields,
+/// mapping each field to a property of a database row.
+///
+/// Fields are mapped to the property named like the field unless
+/// `#[notion(property = "Name")]` says otherwise. String fields are written as rich text,
+/// `#[notion(kind = "...")]` chooses another property type: `title`, `rich_text`, `select`,
+/// `url`, `email` or `phone_number`. The field types have to implement `PropertyField`.
+///
+/// ```
+/// use notion::models::{FromProperties, IntoProperties};
+/// use notion_derive::NotionRow;
+///
+/// #[derive(NotionRow)]
+/// struct Task {
+///     #[notion(property = "Name", kind = "title")]
+///     name: String,
+///     #[notion(property = "Priority")]
+///     priority: Option<f64>,
+/// }
+///
+/// let properties = Task {
+///     name: "Shop".to_string(),
+///     priority: None,
+/// }
+/// .into_properties();
+/// let task = Task::from_properties(&properties).unwrap();
+/// assert_eq!(task.name, "Shop");
+/// assert_eq!(task.priority, None);
+/// ```
+///
+/// Unknown property types are rejected at compile time:
+///
+/// ```compile_fail
+/// use notion_derive::NotionRow;
+///
+/// #[derive(NotionRow)]
+/// struct Task {
+///     #[notion(kind = "headline")]
+///     name: String,
+/// }
+/// ```
+#[proc_macro_derive(NotionRow, attributes(notion))]
+pub fn derive_notion_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+struct Field {
+    ident: Ident,
+    property: LitStr,
+    kind: Ident,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "NotionRow can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "NotionRow can only be derived for structs",
+            ))
+        }
+    };
+    let fields = fields
+        .iter()
+        .map(|field| parse_field(field.ident.clone().expect("named field"), &field.attrs))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let read = fields.iter().map(
+        |Field {
+             ident, property, ..
+         }| {
+            quote! {
+                #ident: {
+                    let value = properties.properties.get(#property);
+                    match ::notion::models::properties::PropertyField::from_property(value) {
+                        ::std::option::Option::Some(field) => field,
+                        ::std::option::Option::None if value.is_none() => {
+                            return ::std::result::Result::Err(
+                                ::notion::models::FromPropertiesError::missing(#property),
+                            )
+                        }
+                        ::std::option::Option::None => {
+                            return ::std::result::Result::Err(
+                                ::notion::models::FromPropertiesError::wrong_type(#property),
+                            )
+                        }
+                    }
+                }
+            }
+        },
+    );
+    let write = fields.iter().map(
+        |Field {
+             ident,
+             property,
+             kind,
+         }| {
+            quote! {
+                if let ::std::option::Option::Some(value) =
+                    ::notion::models::properties::PropertyField::into_property(
+                        self.#ident,
+                        ::notion::models::properties::FieldKind::#kind,
+                    )
+                {
+                    properties.insert(::std::string::String::from(#property), value);
+                }
+            }
+        },
+    );
+
+    Ok(quote! {
+        impl #impl_generics ::notion::models::FromProperties for #name #type_generics #where_clause {
+            fn from_properties(
+                properties: &::notion::models::Properties,
+            ) -> ::std::result::Result<Self, ::notion::models::FromPropertiesError> {
+                ::std::result::Result::Ok(#name {
+                    #(#read,)*
+                })
+            }
+        }
+
+        impl #impl_generics ::notion::models::IntoProperties for #name #type_generics #where_clause {
+            fn into_properties(self) -> ::notion::models::Properties {
+                let mut properties = ::std::collections::HashMap::new();
+                #(#write)*
+                ::notion::models::Properties { properties }
+            }
+        }
+    })
+}
+
+fn parse_field(
+    ident: Ident,
+    attrs: &[syn::Attribute],
+) -> syn::Result<Field> {
+    let mut property = None;
+    let mut kind = None;
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("notion")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("property") {
+                property = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else if meta.path.is_ident("kind") {
+                let value = meta.value()?.parse::<LitStr>()?;
+                let variant = match value.value().as_str() {
+                    "rich_text" => "Text",
+                    "title" => "Title",
+                    "select" => "Select",
+                    "url" => "Url",
+                    "email" => "Email",
+                    "phone_number" => "PhoneNumber",
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            &value,
+                            "expected one of `title`, `rich_text`, `select`, `url`, `email` or `phone_number`",
+                        ))
+                    }
+                };
+                kind = Some(Ident::new(variant, value.span()));
+                Ok(())
+            } else {
+                Err(meta.error("expected `property` or `kind`"))
+            }
+        })?;
+    }
+
+    Ok(Field {
+        property: property.unwrap_or_else(|| LitStr::new(&ident.unraw().to_string(), ident.span())),
+        kind: kind.unwrap_or_else(|| Ident::new("Text", Span::call_site())),
+        ident,
+    })
+}
@@ -0,0 +1,149 @@
+use notion::models::{FromProperties, FromPropertiesError, IntoProperties, Properties};
+use notion_derive::NotionRow;
+use serde_json::json;
+
+#[derive(NotionRow, Debug, PartialEq)]
+struct Task {
+    #[notion(property = "Name", kind = "title")]
+    name: String,
+    #[notion(property = "Estimate")]
+    estimate: f64,
+    #[notion(property = "Status", kind = "select")]
+    status: Option<String>,
+}
+
+fn properties() -> Properties {
+    serde_json::from_value(json!({
+        "Name": {
+            "id": "title",
+            "type": "title",
+            "title": [{
+                "type": "text",
+                "text": {"content": "Shop", "link": null},
+                "annotations": {},
+                "plain_text": "Shop",
+                "href": null
+            }]
+        },
+        "Estimate": {"id": "%3AUPp", "type": "number", "number": 1.5},
+        "Status": {
+            "id": "h%5Cbz",
+            "type": "select",
+            "select": {"id": "1", "name": "Doing", "color": "blue"}
+        },
+        "Done": {"id": "a%3Bd", "type": "checkbox", "checkbox": false}
+    }))
+    .unwrap()
+}
+
+#[test]
+fn from_properties() {
+    assert_eq!(
+        Task::from_properties(&properties()).unwrap(),
+        Task {
+            name: "Shop".to_string(),
+            estimate: 1.5,
+            status: Some("Doing".to_string()),
+        }
+    );
+}
+
+#[test]
+fn missing_properties() {
+    let mut properties = properties();
+    properties.properties.remove("Status");
+    assert_eq!(Task::from_properties(&properties).unwrap().status, None);
+
+    properties.properties.remove("Estimate");
+    assert_eq!(
+        Task::from_properties(&properties),
+        Err(FromPropertiesError::missing("Estimate"))
+    );
+}
+
+#[test]
+fn mismatched_property_types() {
+    let mut properties = properties();
+    let done = properties.properties.remove("Done").unwrap();
+    properties.properties.insert("Estimate".to_string(), done);
+    assert_eq!(
+        Task::from_properties(&properties),
+        Err(FromPropertiesError::wrong_type("Estimate"))
+    );
+}
+
+#[test]
+fn into_properties() {
+    let properties = Task {
+        name: "Cook".to_string(),
+        estimate: 2.0,
+        status: Some("Todo".to_string()),
+    }
+    .into_properties();
+
+    assert_eq!(
+        serde_json::to_value(&properties).unwrap(),
+        json!({
+            "Estimate": {"type": "number", "number": 2.0},
+            "Name": {
+                "type": "title",
+                "title": [{
+                    "type": "text",
+                    "text": {"content": "Cook"},
+                    "plain_text": "Cook"
+                }]
+            },
+            "Status": {
+                "type": "select",
+                "select": {"name": "Todo", "color": "default"}
+            }
+        })
+    );
+
+    // Values without an id are read back just the same
+    let json = serde_json::to_string(&properties).unwrap();
+    assert_eq!(
+        serde_json::from_str::<Properties>(&json).unwrap(),
+        properties
+    );
+}
+
+#[test]
+fn none_is_left_out() {
+    let properties = Task {
+        name: "Cook".to_string(),
+        estimate: 2.0,
+        status: None,
+    }
+    .into_properties();
+
+    assert!(!properties.properties.contains_key("Status"));
+    assert_eq!(Task::from_properties(&properties).unwrap().status, None);
+}
+
+#[derive(NotionRow, Debug, PartialEq)]
+struct Defaults {
+    r#type: String,
+    count: i64,
+}
+
+#[test]
+fn properties_named_like_fields() {
+    let properties = Defaults {
+        r#type: "Bug".to_string(),
+        count: 3,
+    }
+    .into_properties();
+
+    assert_eq!(
+        serde_json::to_value(&properties).unwrap()["type"]["type"],
+        "rich_text"
+    );
+    assert_eq!(
+        Defaults::from_properties(&properties).unwrap(),
+        Defaults {
+            r#type: "Bug".to_string(),
+            count: 3,
+        }
+    );
+}
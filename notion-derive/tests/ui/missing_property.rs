@@ -0,0 +1,9 @@
+use notion_derive::NotionRow;
+
+#[derive(NotionRow)]
+struct Task {
+    #[notion(property)]
+    name: String,
+}
+
+fn main() {}
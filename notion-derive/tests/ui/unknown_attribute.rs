@@ -0,0 +1,9 @@
+use notion_derive::NotionRow;
+
+#[derive(NotionRow)]
+struct Task {
+    #[notion(rename = "Name")]
+    name: String,
+}
+
+fn main() {}
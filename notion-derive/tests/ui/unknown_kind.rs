@@ -0,0 +1,9 @@
+use notion_derive::NotionRow;
+
+#[derive(NotionRow)]
+struct Task {
+    #[notion(kind = "headline")]
+    name: String,
+}
+
+fn main() {}
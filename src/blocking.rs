@@ -0,0 +1,285 @@
+//! A synchronous client for code that doesn't use async, enabled by the `blocking` feature.
+//!
+//! Every method runs the matching method of the async [`NotionApi`](crate::NotionApi)
+//! on a runtime owned by the client, so it must not be called from within an async context.
+//!
+//! ```no_run
+//! # fn main() -> Result<(), notion::Error> {
+//! use notion::blocking::NotionApi;
+//! use notion::ids::PageId;
+//! use std::str::FromStr;
+//!
+//! let api = NotionApi::new(std::env::var("NOTION_API_TOKEN").unwrap())?;
+//! let page = api.get_page(PageId::from_str("bb85a889-3eb3-4146-9325-80508fb5e23d").unwrap())?;
+//! # Ok(())
+//! # }
+//! ```
+use crate::ids::{AsIdentifier, BlockId, DatabaseId, PageId};
+use crate::models::block::{Block, UpdateBlock};
+use crate::models::comments::{Comment, CreateCommentRequest};
+use crate::models::search::{DatabaseQuery, SearchRequest};
+use crate::models::users::User;
+use crate::models::{
+    Database, DatabaseCreateRequest, DatabaseUpdateRequest, ListResponse, Object, Page,
+    PageCreateRequest, PageUpdateRequest, UpdateBlockChildrenRequest,
+};
+use crate::Error;
+use tokio::runtime::{Builder, Runtime};
+
+/// A blocking wrapper of the async [`NotionApi`](crate::NotionApi).
+pub struct NotionApi {
+    inner: crate::NotionApi,
+    runtime: Runtime,
+}
+
+impl NotionApi {
+    /// Creates an instance of NotionApi.
+    /// May fail if the provided api_token is an improper value.
+    pub fn new(api_token: String) -> Result<Self, Error> {
+        Self::from_async(crate::NotionApi::new(api_token)?)
+    }
+
+    /// Wraps a configured async client, e.g. one with retries enabled.
+    pub fn from_async(inner: crate::NotionApi) -> Result<Self, Error> {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|source| Error::ErrorBuildingRuntime { source })?;
+        Ok(NotionApi { inner, runtime })
+    }
+
+    /// The async client used to send the requests.
+    pub fn inner(&self) -> &crate::NotionApi {
+        &self.inner
+    }
+
+    /// List all the databases shared with the supplied integration token.
+    pub fn list_databases(&self) -> Result<ListResponse<Database>, Error> {
+        self.runtime.block_on(self.inner.list_databases())
+    }
+
+    /// See [`NotionApi::search`](crate::NotionApi::search).
+    pub fn search<T: Into<SearchRequest>>(
+        &self,
+        query: T,
+    ) -> Result<ListResponse<Object>, Error> {
+        self.runtime.block_on(self.inner.search(query))
+    }
+
+    pub fn get_database<T: AsIdentifier<DatabaseId>>(
+        &self,
+        database_id: T,
+    ) -> Result<Database, Error> {
+        self.runtime.block_on(self.inner.get_database(database_id))
+    }
+
+    pub fn create_database(
+        &self,
+        request: DatabaseCreateRequest,
+    ) -> Result<Database, Error> {
+        self.runtime.block_on(self.inner.create_database(request))
+    }
+
+    pub fn update_database<T: AsIdentifier<DatabaseId>>(
+        &self,
+        database_id: T,
+        request: DatabaseUpdateRequest,
+    ) -> Result<Database, Error> {
+        self.runtime
+            .block_on(self.inner.update_database(database_id, request))
+    }
+
+    pub fn archive_database<T: AsIdentifier<DatabaseId>>(
+        &self,
+        database_id: T,
+    ) -> Result<Database, Error> {
+        self.runtime
+            .block_on(self.inner.archive_database(database_id))
+    }
+
+    pub fn restore_database<T: AsIdentifier<DatabaseId>>(
+        &self,
+        database_id: T,
+    ) -> Result<Database, Error> {
+        self.runtime
+            .block_on(self.inner.restore_database(database_id))
+    }
+
+    /// See [`NotionApi::query_database`](crate::NotionApi::query_database).
+    pub fn query_database<D, T>(
+        &self,
+        database: D,
+        query: T,
+    ) -> Result<ListResponse<Page>, Error>
+    where
+        T: Into<DatabaseQuery>,
+        D: AsIdentifier<DatabaseId>,
+    {
+        self.runtime
+            .block_on(self.inner.query_database(database, query))
+    }
+
+    pub fn get_page<T: AsIdentifier<PageId>>(
+        &self,
+        page_id: T,
+    ) -> Result<Page, Error> {
+        self.runtime.block_on(self.inner.get_page(page_id))
+    }
+
+    pub fn create_page<T: Into<PageCreateRequest>>(
+        &self,
+        page: T,
+    ) -> Result<Page, Error> {
+        self.runtime.block_on(self.inner.create_page(page))
+    }
+
+    pub fn update_page<P, T>(
+        &self,
+        page_id: P,
+        page: T,
+    ) -> Result<Page, Error>
+    where
+        P: AsIdentifier<PageId>,
+        T: Into<PageUpdateRequest>,
+    {
+        self.runtime.block_on(self.inner.update_page(page_id, page))
+    }
+
+    pub fn archive_page<P: AsIdentifier<PageId>>(
+        &self,
+        page_id: P,
+    ) -> Result<Page, Error> {
+        self.runtime.block_on(self.inner.archive_page(page_id))
+    }
+
+    pub fn restore_page<P: AsIdentifier<PageId>>(
+        &self,
+        page_id: P,
+    ) -> Result<Page, Error> {
+        self.runtime.block_on(self.inner.restore_page(page_id))
+    }
+
+    pub fn get_block<T: AsIdentifier<BlockId>>(
+        &self,
+        block_id: T,
+    ) -> Result<Block, Error> {
+        self.runtime.block_on(self.inner.get_block(block_id))
+    }
+
+    pub fn get_block_children<T: AsIdentifier<BlockId>>(
+        &self,
+        block_id: T,
+    ) -> Result<ListResponse<Block>, Error> {
+        self.runtime
+            .block_on(self.inner.get_block_children(block_id))
+    }
+
+    /// See [`NotionApi::get_block_children_recursive`](crate::NotionApi::get_block_children_recursive).
+    pub fn get_block_children_recursive<T: AsIdentifier<BlockId>>(
+        &self,
+        block_id: T,
+        max_depth: usize,
+    ) -> Result<Vec<Block>, Error> {
+        self.runtime
+            .block_on(self.inner.get_block_children_recursive(block_id, max_depth))
+    }
+
+    pub fn append_block_children<P, T>(
+        &self,
+        block_id: P,
+        request: T,
+    ) -> Result<ListResponse<Block>, Error>
+    where
+        P: AsIdentifier<BlockId>,
+        T: Into<UpdateBlockChildrenRequest>,
+    {
+        self.runtime
+            .block_on(self.inner.append_block_children(block_id, request))
+    }
+
+    pub fn update_block<P, T>(
+        &self,
+        block_id: P,
+        block: T,
+    ) -> Result<Block, Error>
+    where
+        P: AsIdentifier<BlockId>,
+        T: Into<UpdateBlock>,
+    {
+        self.runtime
+            .block_on(self.inner.update_block(block_id, block))
+    }
+
+    pub fn delete_block<T: AsIdentifier<BlockId>>(
+        &self,
+        block_id: T,
+    ) -> Result<Block, Error> {
+        self.runtime.block_on(self.inner.delete_block(block_id))
+    }
+
+    pub fn get_me(&self) -> Result<User, Error> {
+        self.runtime.block_on(self.inner.get_me())
+    }
+
+    pub fn list_all_users(
+        &self,
+        include_bots: bool,
+    ) -> Result<Vec<User>, Error> {
+        self.runtime
+            .block_on(self.inner.list_all_users(include_bots))
+    }
+
+    pub fn list_comments<T: AsIdentifier<BlockId>>(
+        &self,
+        block_id: T,
+    ) -> Result<ListResponse<Comment>, Error> {
+        self.runtime.block_on(self.inner.list_comments(block_id))
+    }
+
+    pub fn create_comment(
+        &self,
+        request: CreateCommentRequest,
+    ) -> Result<Comment, Error> {
+        self.runtime.block_on(self.inner.create_comment(request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NotionApi;
+    use crate::ids::PageId;
+    use serde_json::json;
+    use std::str::FromStr;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const PAGE_ID: &str = "bb85a889-3eb3-4146-9325-80508fb5e23d";
+
+    #[test]
+    fn get_page_without_async() {
+        // The mock server needs a runtime of its own, the client brings its own.
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let server = runtime.block_on(MockServer::start());
+        let page: serde_json::Value =
+            serde_json::from_str(include_str!("models/tests/page.json")).unwrap();
+        runtime.block_on(
+            Mock::given(method("GET"))
+                .and(path(format!("/v1/pages/{}", PAGE_ID)))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!(page)))
+                .expect(1)
+                .mount(&server),
+        );
+
+        let api = NotionApi::from_async(
+            crate::NotionApi::builder("test-token".to_string())
+                .base_url(server.uri())
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+        let page = api.get_page(PageId::from_str(PAGE_ID).unwrap()).unwrap();
+
+        assert!(page.title().is_some());
+        runtime.block_on(server.verify());
+    }
+}
@@ -0,0 +1,53 @@
+use crate::ids::DatabaseId;
+use crate::models::Database;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Remembers the databases returned by [NotionApi::get_database](crate::NotionApi::get_database)
+/// for `ttl`, shared by all clones of a client.
+pub(crate) struct SchemaCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<DatabaseId, (Instant, Database)>>,
+}
+
+impl SchemaCache {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        SchemaCache {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn get(
+        &self,
+        database_id: &DatabaseId,
+    ) -> Option<Database> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(database_id) {
+            Some((fetched, database)) if fetched.elapsed() < self.ttl => Some(database.clone()),
+            Some(_) => {
+                entries.remove(database_id);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub(crate) fn insert(
+        &self,
+        database: &Database,
+    ) {
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(database.id.clone(), (Instant::now(), database.clone()));
+    }
+
+    pub(crate) fn remove(
+        &self,
+        database_id: &DatabaseId,
+    ) {
+        self.entries.lock().unwrap().remove(database_id);
+    }
+}
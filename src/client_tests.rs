@@ -0,0 +1,2052 @@
+use crate::ids::{AsIdentifier, BlockId, DatabaseId, PageId, PropertyId, UserId};
+use crate::models::block::{Block, BlockCommon, CreateBlock, FileOrEmojiObject};
+use crate::models::paging::Paging;
+use crate::models::properties::{
+    NumberDetails, NumberFormat, PropertyItem, PropertySchema, PropertyValue,
+};
+use crate::models::search::{DatabaseQuery, SearchRequest};
+use crate::models::text::IntoRichText;
+use crate::models::users::{Bot, BotOwner, User};
+use crate::models::{
+    DatabaseCreateRequest, FromProperties, FromPropertiesError, Object, PageCreateRequest, Parent,
+    Properties, PropertyResponse,
+};
+use crate::observer::RequestObserver;
+use crate::retry::{BackoffStrategy, RetryConfig};
+use crate::{Error, NotionApi, RequestContext, WalkControl};
+use futures::{StreamExt, TryStreamExt};
+use reqwest::{Method, StatusCode, Url};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use wiremock::matchers::{body_json, body_partial_json, header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const DATABASE_ID: &str = "5d794de0-2224-49d3-86f9-3540db13d884";
+
+fn test_client(server: &MockServer) -> NotionApi {
+    NotionApi::builder("test-token".to_string())
+        .base_url(server.uri())
+        .build()
+        .unwrap()
+}
+
+fn page_json(id: &str) -> Value {
+    json!({
+        "object": "page",
+        "id": id,
+        "created_time": "2021-05-15T17:16:51.364Z",
+        "last_edited_time": "2021-05-15T17:16:00.000Z",
+        "parent": {
+            "type": "database_id",
+            "database_id": DATABASE_ID
+        },
+        "archived": false,
+        "properties": {}
+    })
+}
+
+fn list_json(
+    results: Vec<Value>,
+    next_cursor: Option<&str>,
+) -> Value {
+    json!({
+        "object": "list",
+        "results": results,
+        "next_cursor": next_cursor,
+        "has_more": next_cursor.is_some()
+    })
+}
+
+fn query_with_page_size(page_size: u8) -> DatabaseQuery {
+    DatabaseQuery {
+        paging: Some(Paging {
+            start_cursor: None,
+            page_size: Some(page_size),
+        }),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn query_database_all_follows_cursor() {
+    let server = MockServer::start().await;
+    let query_path = format!("/v1/databases/{}/query", DATABASE_ID);
+
+    Mock::given(method("POST"))
+        .and(path(query_path.as_str()))
+        .and(body_json(json!({"page_size": 2})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(list_json(
+            vec![
+                page_json("bb85a889-3eb3-4146-9325-80508fb5e23d"),
+                page_json("c81ee776-2752-4e98-aa66-c37bd4ba9b8d"),
+            ],
+            Some("cursor-1"),
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path(query_path.as_str()))
+        .and(body_json(
+            json!({"start_cursor": "cursor-1", "page_size": 2}),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(list_json(
+            vec![page_json("b55c9c91-384d-452b-81db-d1ef79372b75")],
+            None,
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server);
+    let database_id = DatabaseId::from_str(DATABASE_ID).unwrap();
+    let pages: Vec<_> = api
+        .query_database_all(database_id, query_with_page_size(2))
+        .try_collect()
+        .await
+        .unwrap();
+
+    let ids: Vec<_> = pages.into_iter().map(|page| page.id).collect();
+    assert_eq!(
+        ids,
+        vec![
+            PageId::from_str("bb85a889-3eb3-4146-9325-80508fb5e23d").unwrap(),
+            PageId::from_str("c81ee776-2752-4e98-aa66-c37bd4ba9b8d").unwrap(),
+            PageId::from_str("b55c9c91-384d-452b-81db-d1ef79372b75").unwrap(),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn search_all_follows_cursor() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/search"))
+        .and(body_json(json!({"query": "kale"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(list_json(
+            vec![
+                page_json("bb85a889-3eb3-4146-9325-80508fb5e23d"),
+                database_json(),
+            ],
+            Some("cursor-1"),
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/search"))
+        .and(body_json(
+            json!({"query": "kale", "start_cursor": "cursor-1"}),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(list_json(
+            vec![page_json("b55c9c91-384d-452b-81db-d1ef79372b75")],
+            None,
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server);
+    let objects: Vec<_> = api
+        .search_all(SearchRequest::new().query("kale"))
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(objects.len(), 3);
+    assert!(matches!(
+        &objects[0],
+        Object::Page { page } if page.id == PageId::from_str("bb85a889-3eb3-4146-9325-80508fb5e23d").unwrap()
+    ));
+    assert!(matches!(
+        &objects[1],
+        Object::Database { database } if database.id == DatabaseId::from_str(DATABASE_ID).unwrap()
+    ));
+    assert!(matches!(
+        &objects[2],
+        Object::Page { page } if page.id == PageId::from_str("b55c9c91-384d-452b-81db-d1ef79372b75").unwrap()
+    ));
+}
+
+#[derive(Debug, PartialEq)]
+struct Task {
+    name: String,
+    done: bool,
+}
+
+impl FromProperties for Task {
+    fn from_properties(properties: &Properties) -> Result<Self, FromPropertiesError> {
+        Ok(Task {
+            name: properties
+                .title()
+                .ok_or_else(|| FromPropertiesError::missing("Name"))?,
+            done: properties
+                .get_checkbox("Done")
+                .ok_or_else(|| FromPropertiesError::missing("Done"))?,
+        })
+    }
+}
+
+fn task_json(
+    id: &str,
+    name: &str,
+    done: Option<bool>,
+) -> Value {
+    let mut page = page_json(id);
+    page["properties"]["Name"] = json!({
+        "id": "title",
+        "type": "title",
+        "title": [{
+            "type": "text",
+            "text": {"content": name, "link": null},
+            "annotations": {},
+            "plain_text": name,
+            "href": null
+        }]
+    });
+    if let Some(done) = done {
+        page["properties"]["Done"] = json!({"id": "a%3Bd", "type": "checkbox", "checkbox": done});
+    }
+    page
+}
+
+#[tokio::test]
+async fn query_database_into_rows() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(format!("/v1/databases/{}/query", DATABASE_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(list_json(
+            vec![
+                task_json("bb85a889-3eb3-4146-9325-80508fb5e23d", "Shop", Some(true)),
+                task_json("c81ee776-2752-4e98-aa66-c37bd4ba9b8d", "Cook", Some(false)),
+            ],
+            Some("cursor-1"),
+        )))
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server);
+    let tasks = api
+        .query_database_into::<Task>(
+            DatabaseId::from_str(DATABASE_ID).unwrap(),
+            DatabaseQuery::default(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        tasks.results,
+        vec![
+            Task {
+                name: "Shop".to_string(),
+                done: true
+            },
+            Task {
+                name: "Cook".to_string(),
+                done: false
+            },
+        ]
+    );
+    assert!(tasks.has_more);
+    assert_eq!(tasks.next_cursor.unwrap().0, "cursor-1");
+}
+
+#[tokio::test]
+async fn query_database_into_reports_unconvertible_page() {
+    let server = MockServer::start().await;
+    let page_id = "c81ee776-2752-4e98-aa66-c37bd4ba9b8d";
+    Mock::given(method("POST"))
+        .and(path(format!("/v1/databases/{}/query", DATABASE_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(list_json(
+            vec![
+                task_json("bb85a889-3eb3-4146-9325-80508fb5e23d", "Shop", Some(true)),
+                task_json(page_id, "Cook", None),
+            ],
+            None,
+        )))
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server);
+    let result = api
+        .query_database_into::<Task>(
+            DatabaseId::from_str(DATABASE_ID).unwrap(),
+            DatabaseQuery::default(),
+        )
+        .await;
+
+    match result {
+        Err(Error::PropertiesConversion {
+            page_id: id,
+            source,
+        }) => {
+            assert_eq!(id, PageId::from_str(page_id).unwrap());
+            assert_eq!(source, FromPropertiesError::missing("Done"));
+        }
+        other => panic!("Unexpected result {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn query_database_all_surfaces_errors() {
+    let server = MockServer::start().await;
+    let query_path = format!("/v1/databases/{}/query", DATABASE_ID);
+
+    Mock::given(method("POST"))
+        .and(path(query_path.as_str()))
+        .and(body_json(json!({"page_size": 1})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(list_json(
+            vec![page_json("bb85a889-3eb3-4146-9325-80508fb5e23d")],
+            Some("cursor-1"),
+        )))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path(query_path.as_str()))
+        .and(body_json(
+            json!({"start_cursor": "cursor-1", "page_size": 1}),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "object": "user",
+            "id": "6419f912-5293-4ea8-b2c8-9c3ce44f90e3"
+        })))
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server);
+    let database_id = DatabaseId::from_str(DATABASE_ID).unwrap();
+    let results: Vec<_> = api
+        .query_database_all(database_id, query_with_page_size(1))
+        .collect()
+        .await;
+
+    assert_eq!(results.len(), 2);
+    assert!(results[0].is_ok());
+    match &results[1] {
+        Err(Error::UnexpectedResponse { context, .. }) => {
+            assert_eq!(context.method, Method::POST);
+            assert_eq!(context.path, query_path);
+        }
+        other => panic!("Expected an unexpected response, got {:?}", other),
+    }
+}
+
+const PAGE_ID: &str = "bb85a889-3eb3-4146-9325-80508fb5e23d";
+
+fn rate_limited_json() -> Value {
+    json!({
+        "object": "error",
+        "status": 429,
+        "code": "rate_limited",
+        "message": "You have been rate limited. Please try again in a few minutes."
+    })
+}
+
+fn fast_retry(max_retries: u32) -> RetryConfig {
+    RetryConfig {
+        max_retries,
+        backoff: BackoffStrategy::Exponential {
+            base: Duration::from_millis(1),
+            max: Duration::from_secs(30),
+        },
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn retries_after_rate_limit() {
+    let server = MockServer::start().await;
+    let page_path = format!("/v1/pages/{}", PAGE_ID);
+
+    Mock::given(method("GET"))
+        .and(path(page_path.as_str()))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("Retry-After", "0")
+                .set_body_json(rate_limited_json()),
+        )
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(page_path.as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page_json(PAGE_ID)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server).with_retry(fast_retry(2));
+    let page = api
+        .get_page(PageId::from_str(PAGE_ID).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(page.id, PageId::from_str(PAGE_ID).unwrap());
+}
+
+#[tokio::test]
+async fn retries_server_errors_with_backoff() {
+    let server = MockServer::start().await;
+    let page_path = format!("/v1/pages/{}", PAGE_ID);
+
+    Mock::given(method("GET"))
+        .and(path(page_path.as_str()))
+        .respond_with(ResponseTemplate::new(502))
+        .up_to_n_times(2)
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(page_path.as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page_json(PAGE_ID)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server).with_retry(fast_retry(2));
+    assert!(api
+        .get_page(PageId::from_str(PAGE_ID).unwrap())
+        .await
+        .is_ok());
+}
+
+#[tokio::test]
+async fn exhausted_retries_are_rate_limited() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/pages/{}", PAGE_ID).as_str()))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("Retry-After", "0")
+                .set_body_json(rate_limited_json()),
+        )
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server).with_retry(fast_retry(2));
+    let result = api.get_page(PageId::from_str(PAGE_ID).unwrap()).await;
+
+    assert!(matches!(
+        result,
+        Err(Error::RateLimited {
+            retry_after: Some(retry_after)
+        }) if retry_after == Duration::ZERO
+    ));
+}
+
+#[tokio::test]
+async fn rate_limit_without_retry_is_api_error() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/pages/{}", PAGE_ID).as_str()))
+        .respond_with(ResponseTemplate::new(429).set_body_json(rate_limited_json()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server);
+    let result = api.get_page(PageId::from_str(PAGE_ID).unwrap()).await;
+
+    assert!(matches!(result, Err(Error::ApiError { .. })));
+}
+
+#[tokio::test]
+async fn api_errors_carry_request_context() {
+    let server = MockServer::start().await;
+    let page_path = format!("/v1/pages/{}", PAGE_ID);
+
+    Mock::given(method("GET"))
+        .and(path(page_path.as_str()))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "object": "error",
+            "status": 404,
+            "code": "object_not_found",
+            "message": "Could not find page.",
+            "request_id": "b0a1f5c4-2d3e-4f5a-8b6c-7d8e9f0a1b2c"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server);
+    let error = api
+        .get_page(PageId::from_str(PAGE_ID).unwrap())
+        .await
+        .unwrap_err();
+
+    let Error::ApiError { error, context } = &error else {
+        panic!("Expected an API error, got {:?}", error);
+    };
+    assert_eq!(error.status.code(), 404);
+    assert_eq!(context.method, Method::GET);
+    assert_eq!(context.path, page_path);
+    assert_eq!(
+        context.request_id.as_deref(),
+        Some("b0a1f5c4-2d3e-4f5a-8b6c-7d8e9f0a1b2c")
+    );
+}
+
+fn toggle_json(
+    id: &str,
+    has_children: bool,
+) -> Value {
+    json!({
+        "object": "block",
+        "id": id,
+        "type": "toggle",
+        "created_time": "2021-05-15T17:16:00.000Z",
+        "last_edited_time": "2021-05-15T17:16:00.000Z",
+        "created_by": {"object": "user", "id": "e2507360-468c-4e0f-a928-7bbcbbb45353"},
+        "last_edited_by": {"object": "user", "id": "e2507360-468c-4e0f-a928-7bbcbbb45353"},
+        "has_children": has_children,
+        "archived": false,
+        "toggle": {"rich_text": [], "color": "default"}
+    })
+}
+
+#[tokio::test]
+async fn get_block_children_recursive_builds_tree() {
+    let server = MockServer::start().await;
+    let root = "0c7a7c9a-0000-4000-8000-000000000000";
+    let parent = "0c7a7c9a-0000-4000-8000-000000000001";
+    let sibling = "0c7a7c9a-0000-4000-8000-000000000002";
+    let child = "0c7a7c9a-0000-4000-8000-000000000003";
+    let grandchild = "0c7a7c9a-0000-4000-8000-000000000004";
+
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/blocks/{}/children", root).as_str()))
+        .and(query_param("start_cursor", "cursor-1"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(list_json(vec![toggle_json(sibling, false)], None)),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/blocks/{}/children", root).as_str()))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(list_json(vec![toggle_json(parent, true)], Some("cursor-1"))),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/blocks/{}/children", parent).as_str()))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(list_json(vec![toggle_json(child, true)], None)),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/blocks/{}/children", child).as_str()))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(list_json(vec![toggle_json(grandchild, false)], None)),
+        )
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server);
+    let blocks = api
+        .get_block_children_recursive(BlockId::from_str(root).unwrap(), 2)
+        .await
+        .unwrap();
+
+    let ids: Vec<_> = blocks
+        .iter()
+        .map(|block| block.as_id().to_string())
+        .collect();
+    assert_eq!(ids, vec![parent, sibling]);
+
+    let children = blocks[0].children().unwrap();
+    assert_eq!(children.len(), 1);
+    assert_eq!(children[0].as_id(), &BlockId::from_str(child).unwrap());
+    // The depth limit stops before fetching the grandchildren.
+    assert!(children[0].has_children());
+    assert_eq!(children[0].children(), None);
+    assert_eq!(blocks[1].children(), None);
+}
+
+/// Mounts a tree where `root` has the children `a` and `b`, over two pages, and `a` has the
+/// children `c` and `d`. The children of `d`, which is said to have some, are left to the tests.
+/// Returns the ids from `root` to `e`, the child of `d`.
+async fn mount_block_tree(server: &MockServer) -> [&'static str; 6] {
+    let ids = [
+        "0c7a7c9a-0000-4000-8000-000000000000",
+        "0c7a7c9a-0000-4000-8000-00000000000a",
+        "0c7a7c9a-0000-4000-8000-00000000000b",
+        "0c7a7c9a-0000-4000-8000-00000000000c",
+        "0c7a7c9a-0000-4000-8000-00000000000d",
+        "0c7a7c9a-0000-4000-8000-00000000000e",
+    ];
+    let [root, a, b, c, d, _] = ids;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/blocks/{}/children", root)))
+        .and(query_param("start_cursor", "cursor-1"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(list_json(vec![toggle_json(b, false)], None)),
+        )
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/blocks/{}/children", root)))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(list_json(vec![toggle_json(a, true)], Some("cursor-1"))),
+        )
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/blocks/{}/children", a)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(list_json(
+            vec![toggle_json(c, false), toggle_json(d, true)],
+            None,
+        )))
+        .mount(server)
+        .await;
+    ids
+}
+
+#[tokio::test]
+async fn walk_blocks_visits_depth_first() {
+    let server = MockServer::start().await;
+    let [root, a, b, c, d, e] = mount_block_tree(&server).await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/blocks/{}/children", d)))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(list_json(vec![toggle_json(e, false)], None)),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server);
+    let mut visited = vec![];
+    api.walk_blocks(BlockId::from_str(root).unwrap(), |block, depth| {
+        visited.push((block.as_id().to_string(), depth));
+        WalkControl::Continue
+    })
+    .await
+    .unwrap();
+
+    let expected: Vec<_> = [(a, 0), (c, 1), (d, 1), (e, 2), (b, 0)]
+        .iter()
+        .map(|(id, depth)| (id.to_string(), *depth))
+        .collect();
+    assert_eq!(visited, expected);
+}
+
+#[tokio::test]
+async fn walk_blocks_skips_children_and_stops() {
+    let server = MockServer::start().await;
+    let [root, a, _, c, d, _] = mount_block_tree(&server).await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/blocks/{}/children", d)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(list_json(vec![], None)))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server);
+    let d_id = BlockId::from_str(d).unwrap();
+    let mut count = 0;
+    api.walk_blocks(BlockId::from_str(root).unwrap(), |block, _| {
+        count += 1;
+        if block.as_id() == &d_id {
+            WalkControl::SkipChildren
+        } else {
+            WalkControl::Continue
+        }
+    })
+    .await
+    .unwrap();
+    // a, c, d and b, without the child of d.
+    assert_eq!(count, 4);
+
+    let mut visited = vec![];
+    api.walk_blocks(BlockId::from_str(root).unwrap(), |block, _| {
+        visited.push(block.as_id().to_string());
+        if visited.len() == 2 {
+            WalkControl::Stop
+        } else {
+            WalkControl::Continue
+        }
+    })
+    .await
+    .unwrap();
+    assert_eq!(visited, [a, c]);
+}
+
+#[tokio::test]
+async fn walk_blocks_stays_out_of_child_pages() {
+    let server = MockServer::start().await;
+    let root = "0c7a7c9a-0000-4000-8000-000000000000";
+    let subpage = "0c7a7c9a-0000-4000-8000-00000000000a";
+    let mut child_page = toggle_json(subpage, true);
+    child_page["type"] = json!("child_page");
+    child_page["child_page"] = json!({"title": "Subpage"});
+    child_page.as_object_mut().unwrap().remove("toggle");
+
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/blocks/{}/children", root)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(list_json(vec![child_page], None)))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/blocks/{}/children", subpage)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(list_json(vec![], None)))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let mut visited = vec![];
+    test_client(&server)
+        .walk_blocks(BlockId::from_str(root).unwrap(), |block, _| {
+            visited.push(block.as_id().to_string());
+            WalkControl::Continue
+        })
+        .await
+        .unwrap();
+    assert_eq!(visited, [subpage]);
+}
+
+#[tokio::test]
+async fn list_comments_by_block_id() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/comments"))
+        .and(query_param("block_id", PAGE_ID))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(include_str!("models/tests/comment_list.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server);
+    let comments = api
+        .list_comments(BlockId::from(PageId::from_str(PAGE_ID).unwrap()))
+        .await
+        .unwrap();
+
+    assert_eq!(comments.results.len(), 2);
+}
+
+fn relation_item_json(page_id: &str) -> Value {
+    json!({
+        "object": "property_item",
+        "id": "%5Cq%60%5B",
+        "type": "relation",
+        "relation": {"id": page_id}
+    })
+}
+
+#[tokio::test]
+async fn retrieve_page_property_follows_cursor() {
+    let server = MockServer::start().await;
+    let property_path = format!("/v1/pages/{}/properties/%5Cq%60%5B", PAGE_ID);
+    let related = [
+        "0c7a7c9a-0000-4000-8000-000000000001",
+        "0c7a7c9a-0000-4000-8000-000000000002",
+    ];
+
+    Mock::given(method("GET"))
+        .and(path(property_path.as_str()))
+        .and(query_param("start_cursor", "cursor-1"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(list_json(vec![relation_item_json(related[1])], None)),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(property_path.as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(list_json(
+            vec![relation_item_json(related[0])],
+            Some("cursor-1"),
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server);
+    let response = api
+        .retrieve_page_property(
+            PageId::from_str(PAGE_ID).unwrap(),
+            PropertyId::from_str("%5Cq%60%5B").unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let ids: Vec<_> = response
+        .into_values()
+        .into_iter()
+        .flat_map(|value| match value {
+            PropertyValue::Relation {
+                relation: Some(relation),
+                ..
+            } => relation.into_iter().map(|relation| relation.id.to_string()),
+            other => panic!("Expected a relation, got {:?}", other),
+        })
+        .collect();
+    assert_eq!(ids, related);
+}
+
+fn property_list_json(
+    results: Vec<Value>,
+    next_cursor: Option<&str>,
+    property_item: Value,
+) -> Value {
+    let mut list = list_json(results, next_cursor);
+    list["type"] = json!("property_item");
+    list["property_item"] = property_item;
+    list
+}
+
+#[tokio::test]
+async fn get_page_property_all_combines_relation() {
+    let server = MockServer::start().await;
+    let property_path = format!("/v1/pages/{}/properties/%5Cq%60%5B", PAGE_ID);
+    let relation =
+        json!({"id": "%5Cq%60%5B", "next_url": null, "type": "relation", "relation": {}});
+
+    Mock::given(method("GET"))
+        .and(path(property_path.as_str()))
+        .and(query_param("start_cursor", "cursor-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(property_list_json(
+            vec![relation_item_json("0c7a7c9a-0000-4000-8000-000000000002")],
+            None,
+            relation.clone(),
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(property_path.as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(property_list_json(
+            vec![relation_item_json("0c7a7c9a-0000-4000-8000-000000000001")],
+            Some("cursor-1"),
+            relation,
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server);
+    let value = api
+        .get_page_property_all(
+            PageId::from_str(PAGE_ID).unwrap(),
+            PropertyId::from_str("%5Cq%60%5B").unwrap(),
+        )
+        .await
+        .unwrap();
+
+    match value {
+        PropertyValue::Relation {
+            id,
+            relation: Some(relation),
+        } => {
+            assert_eq!(id, PropertyId::from_str("%5Cq%60%5B").unwrap());
+            let ids: Vec<_> = relation.iter().map(|page| page.id.to_string()).collect();
+            assert_eq!(
+                ids,
+                [
+                    "0c7a7c9a-0000-4000-8000-000000000001",
+                    "0c7a7c9a-0000-4000-8000-000000000002"
+                ]
+            );
+        }
+        other => panic!("Expected a relation, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn get_page_property_all_empty_and_rollup() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/pages/{}/properties/title", PAGE_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(property_list_json(
+            vec![],
+            None,
+            json!({"id": "title", "next_url": null, "type": "title", "title": {}}),
+        )))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/pages/{}/properties/rollup", PAGE_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(property_list_json(
+            vec![json!({
+                "object": "property_item",
+                "id": "rollup",
+                "type": "number",
+                "number": 3
+            })],
+            None,
+            json!({
+                "id": "rollup",
+                "next_url": null,
+                "type": "rollup",
+                "rollup": {"type": "array", "array": [], "function": "show_original"}
+            }),
+        )))
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server);
+    let page_id = PageId::from_str(PAGE_ID).unwrap();
+    let title = api
+        .get_page_property_all(&page_id, PropertyId::from_str("title").unwrap())
+        .await
+        .unwrap();
+    assert_eq!(
+        title,
+        PropertyValue::Title {
+            id: PropertyId::from_str("title").unwrap(),
+            title: vec![]
+        }
+    );
+
+    let rollup = api
+        .get_page_property_all(&page_id, PropertyId::from_str("rollup").unwrap())
+        .await
+        .unwrap();
+    match rollup {
+        PropertyValue::Rollup {
+            rollup: Some(rollup),
+            ..
+        } => assert_eq!(rollup.flatten_plain(), ["3"]),
+        other => panic!("Expected a rollup, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn retrieve_page_property_single_item() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(
+            format!("/v1/pages/{}/properties/abc", PAGE_ID).as_str(),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "object": "property_item",
+            "id": "abc",
+            "type": "number",
+            "number": 7
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server);
+    let response = api
+        .retrieve_page_property(
+            PageId::from_str(PAGE_ID).unwrap(),
+            PropertyId::from_str("abc").unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        response,
+        PropertyResponse::PropertyItem(PropertyItem::Number {
+            id: PropertyId::from_str("abc").unwrap(),
+            number: Some(7.into()),
+        })
+    );
+}
+
+#[tokio::test]
+async fn archive_and_restore_page() {
+    let server = MockServer::start().await;
+    let page_path = format!("/v1/pages/{}", PAGE_ID);
+    let mut archived_page = page_json(PAGE_ID);
+    archived_page["archived"] = json!(true);
+
+    Mock::given(method("PATCH"))
+        .and(path(page_path.as_str()))
+        .and(body_json(json!({"archived": true, "properties": {}})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(archived_page))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path(page_path.as_str()))
+        .and(body_json(json!({"archived": false, "properties": {}})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page_json(PAGE_ID)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server);
+    let page_id = PageId::from_str(PAGE_ID).unwrap();
+
+    let page = api.archive_page(page_id.clone()).await.unwrap();
+    assert!(page.archived);
+
+    let page = api.restore_page(page_id).await.unwrap();
+    assert!(!page.archived);
+}
+
+#[tokio::test]
+async fn append_block_children_to_page() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PATCH"))
+        .and(path(format!("/v1/blocks/{}/children", PAGE_ID).as_str()))
+        .and(body_json(json!({
+            "children": [{
+                "type": "paragraph",
+                "paragraph": {
+                    "rich_text": [{
+                        "type": "text",
+                        "plain_text": "Hello",
+                        "text": {"content": "Hello"}
+                    }],
+                    "color": "default"
+                }
+            }]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(list_json(
+            vec![toggle_json("f2ae7a0e-e8d2-4d47-a1aa-2b7ac1f4e6c2", false)],
+            None,
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server);
+    let created = api
+        .append_block_children(
+            BlockId::from(PageId::from_str(PAGE_ID).unwrap()),
+            vec![CreateBlock::paragraph("Hello")],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(created.results().len(), 1);
+    assert_eq!(
+        created.results()[0].as_id(),
+        &BlockId::from_str("f2ae7a0e-e8d2-4d47-a1aa-2b7ac1f4e6c2").unwrap()
+    );
+}
+
+#[tokio::test]
+async fn delete_block_returns_archived_block() {
+    let server = MockServer::start().await;
+    let block_id = "f2ae7a0e-e8d2-4d47-a1aa-2b7ac1f4e6c2";
+    let mut archived_block = toggle_json(block_id, false);
+    archived_block["archived"] = json!(true);
+
+    Mock::given(method("DELETE"))
+        .and(path(format!("/v1/blocks/{}", block_id).as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(archived_block))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server);
+    let block = api
+        .delete_block(BlockId::from_str(block_id).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(block.as_id(), &BlockId::from_str(block_id).unwrap());
+    assert!(matches!(
+        block,
+        Block::Toggle {
+            common: BlockCommon { archived: true, .. },
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn get_me_returns_bot_user() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/users/me"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(include_str!("models/tests/bot_user.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server);
+    let user = api.get_me().await.unwrap();
+
+    assert_eq!(
+        user.id(),
+        &UserId::from_str("16d84278-ab0e-484c-9bdd-b35da3bd8905").unwrap()
+    );
+    assert!(matches!(
+        user,
+        User::Bot {
+            bot: Bot {
+                owner: Some(BotOwner::Workspace { workspace: true }),
+                ..
+            },
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn create_database_with_title_and_number() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/databases"))
+        .and(body_json(json!({
+            "parent": {"type": "page_id", "page_id": PAGE_ID},
+            "title": [{"type": "text", "plain_text": "Groceries", "text": {"content": "Groceries"}}],
+            "properties": {
+                "Name": {"title": {}},
+                "Price": {"number": {"format": "dollar"}}
+            }
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "object": "database",
+            "id": DATABASE_ID,
+            "created_time": "2021-05-15T17:16:00.000Z",
+            "last_edited_time": "2021-05-15T17:16:00.000Z",
+            "title": [],
+            "properties": {
+                "Name": {"id": "title", "type": "title", "title": {}},
+                "Price": {"id": "evWq", "type": "number", "number": {"format": "dollar"}}
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server);
+    let database = api
+        .create_database(
+            DatabaseCreateRequest::new(PageId::from_str(PAGE_ID).unwrap(), "Groceries", "Name")
+                .property(
+                    "Price",
+                    PropertySchema::Number(NumberDetails {
+                        format: NumberFormat::Dollar,
+                    }),
+                ),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(database.id, DatabaseId::from_str(DATABASE_ID).unwrap());
+    assert_eq!(database.properties.len(), 2);
+}
+
+fn database_json() -> Value {
+    json!({
+        "object": "database",
+        "id": DATABASE_ID,
+        "created_time": "2021-05-15T17:16:00.000Z",
+        "last_edited_time": "2021-05-15T17:16:00.000Z",
+        "title": [],
+        "properties": {
+            "Name": {"id": "title", "type": "title", "title": {}}
+        }
+    })
+}
+
+#[tokio::test]
+async fn archive_and_restore_database() {
+    let server = MockServer::start().await;
+    let database_path = format!("/v1/databases/{}", DATABASE_ID);
+    let mut archived_database = database_json();
+    archived_database["archived"] = json!(true);
+
+    Mock::given(method("PATCH"))
+        .and(path(database_path.as_str()))
+        .and(body_json(json!({"archived": true})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(archived_database))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let mut restored_database = database_json();
+    restored_database["archived"] = json!(false);
+    Mock::given(method("PATCH"))
+        .and(path(database_path.as_str()))
+        .and(body_json(json!({"archived": false})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(restored_database))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server);
+    let database_id = DatabaseId::from_str(DATABASE_ID).unwrap();
+
+    let database = api.archive_database(&database_id).await.unwrap();
+    assert_eq!(database.archived, Some(true));
+
+    let database = api.restore_database(database_id).await.unwrap();
+    assert_eq!(database.archived, Some(false));
+}
+
+#[tokio::test]
+async fn schema_cache_reuses_databases() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/databases/{}", DATABASE_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(database_json()))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server).with_schema_cache(Duration::from_secs(60));
+    let database_id = DatabaseId::from_str(DATABASE_ID).unwrap();
+    let first = api.get_database(database_id.clone()).await.unwrap();
+    let second = api.get_database(database_id.clone()).await.unwrap();
+    assert_eq!(first, second);
+
+    api.invalidate_database(database_id.clone());
+    api.get_database(database_id).await.unwrap();
+}
+
+#[tokio::test]
+async fn schema_cache_expires() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/databases/{}", DATABASE_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(database_json()))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server).with_schema_cache(Duration::ZERO);
+    let database_id = DatabaseId::from_str(DATABASE_ID).unwrap();
+    api.get_database(database_id.clone()).await.unwrap();
+    api.get_database(database_id).await.unwrap();
+}
+
+#[tokio::test]
+async fn create_database_requires_one_title() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/databases"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server);
+    let page_id = PageId::from_str(PAGE_ID).unwrap();
+
+    let two_titles = DatabaseCreateRequest::new(page_id.clone(), "Groceries", "Name")
+        .property("Other name", PropertySchema::Title {});
+    assert!(matches!(
+        api.create_database(two_titles).await,
+        Err(Error::InvalidRequest { .. })
+    ));
+
+    let mut no_title = DatabaseCreateRequest::new(page_id, "Groceries", "Name");
+    no_title.properties.clear();
+    assert!(matches!(
+        api.create_database(no_title).await,
+        Err(Error::InvalidRequest { .. })
+    ));
+}
+
+#[tokio::test]
+async fn get_block_parses_paragraph() {
+    let server = MockServer::start().await;
+    let block_id = "4c9e6b1e-8b11-4a0b-9fd3-6c6d1f2d7c1a";
+
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/blocks/{}", block_id).as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_string(include_str!(
+            "models/block/tests/paragraph_with_equation.json"
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server);
+    let block = api
+        .get_block(BlockId::from_str(block_id).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(block.as_id(), &BlockId::from_str(block_id).unwrap());
+    match block {
+        Block::Paragraph { paragraph, .. } => assert_eq!(paragraph.rich_text.len(), 2),
+        other => panic!("Expected a paragraph, got {:?}", other),
+    }
+}
+
+#[derive(Default)]
+struct RecordingObserver {
+    requests: Mutex<Vec<(Method, String, Option<String>)>>,
+    responses: Mutex<Vec<(StatusCode, String)>>,
+}
+
+impl RequestObserver for RecordingObserver {
+    fn on_request(
+        &self,
+        method: &Method,
+        url: &Url,
+        body: Option<&str>,
+    ) {
+        self.requests.lock().unwrap().push((
+            method.clone(),
+            url.path().to_string(),
+            body.map(str::to_string),
+        ));
+    }
+
+    fn on_response(
+        &self,
+        status: StatusCode,
+        body: &str,
+    ) {
+        self.responses
+            .lock()
+            .unwrap()
+            .push((status, body.to_string()));
+    }
+}
+
+#[tokio::test]
+async fn observer_sees_request_and_response() {
+    let server = MockServer::start().await;
+    let page_path = format!("/v1/pages/{}", PAGE_ID);
+
+    Mock::given(method("PATCH"))
+        .and(path(page_path.as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page_json(PAGE_ID)))
+        .mount(&server)
+        .await;
+
+    let observer = Arc::new(RecordingObserver::default());
+    let api = test_client(&server).with_observer(observer.clone());
+    api.archive_page(PageId::from_str(PAGE_ID).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        *observer.requests.lock().unwrap(),
+        vec![(
+            Method::PATCH,
+            page_path,
+            Some(r#"{"properties":{},"archived":true}"#.to_string())
+        )]
+    );
+    let responses = observer.responses.lock().unwrap();
+    assert_eq!(responses.len(), 1);
+    assert_eq!(responses[0].0, StatusCode::OK);
+    assert_eq!(
+        serde_json::from_str::<Value>(&responses[0].1).unwrap(),
+        page_json(PAGE_ID)
+    );
+}
+
+#[tokio::test]
+async fn duplicate_block_into_copies_subtree() {
+    let server = MockServer::start().await;
+    let source = "a1d8b2b7-5c8f-4d77-9c58-1a4b8b5e8f10";
+    let child = "b2e9c3c8-6d90-4e88-8d69-2b5c9c6f9a21";
+    let target = "c3fad4d9-7ea1-4f99-9e7a-3c6dad7a0b32";
+    let copy = "d40be5ea-8fb2-4aaa-8f8b-4d7ebe8b1c43";
+    let toggle_body = json!({"type": "toggle", "toggle": {"rich_text": [], "color": "default"}});
+
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/blocks/{}", source).as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(toggle_json(source, true)))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/blocks/{}/children", source).as_str()))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(list_json(vec![toggle_json(child, false)], None)),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path(format!("/v1/blocks/{}/children", target).as_str()))
+        .and(body_json(json!({"children": [toggle_body]})))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(list_json(vec![toggle_json(copy, false)], None)),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path(format!("/v1/blocks/{}/children", copy).as_str()))
+        .and(body_json(json!({"children": [toggle_body]})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(list_json(
+            vec![toggle_json("e51cf6fb-90c3-4bbb-9a9c-5e8fcf9c2d54", false)],
+            None,
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server);
+    let created = api
+        .duplicate_block_into(
+            BlockId::from_str(source).unwrap(),
+            BlockId::from_str(target).unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(created.len(), 1);
+    assert_eq!(created[0].as_id(), &BlockId::from_str(copy).unwrap());
+}
+
+/// A `column_list` or `column` block with children.
+fn layout_json(
+    id: &str,
+    block_type: &str,
+) -> Value {
+    let mut block = toggle_json(id, true);
+    block["type"] = json!(block_type);
+    block[block_type] = json!({});
+    block.as_object_mut().unwrap().remove("toggle");
+    block
+}
+
+#[tokio::test]
+async fn duplicate_block_into_creates_columns_with_their_list() {
+    let server = MockServer::start().await;
+    let source = "a1d8b2b7-5c8f-4d77-9c58-1a4b8b5e8f10";
+    let column = "b2e9c3c8-6d90-4e88-8d69-2b5c9c6f9a21";
+    let content = "0c7a7c9a-0000-4000-8000-000000000001";
+    let target = "c3fad4d9-7ea1-4f99-9e7a-3c6dad7a0b32";
+    let copy = "d40be5ea-8fb2-4aaa-8f8b-4d7ebe8b1c43";
+    let column_copy = "e51cf6fb-90c3-4bbb-9a9c-5e8fcf9c2d54";
+    let content_copy = "0c7a7c9a-0000-4000-8000-000000000002";
+    let toggle_body = json!({"type": "toggle", "toggle": {"rich_text": [], "color": "default"}});
+
+    let children = [
+        (source, vec![layout_json(column, "column")]),
+        (column, vec![toggle_json(content, true)]),
+        (
+            content,
+            vec![toggle_json("0c7a7c9a-0000-4000-8000-000000000003", false)],
+        ),
+        (copy, vec![layout_json(column_copy, "column")]),
+        (column_copy, vec![toggle_json(content_copy, false)]),
+    ];
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/blocks/{}", source).as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(layout_json(source, "column_list")))
+        .mount(&server)
+        .await;
+    for (parent, blocks) in children {
+        Mock::given(method("GET"))
+            .and(path(format!("/v1/blocks/{}/children", parent).as_str()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(list_json(blocks, None)))
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("PATCH"))
+        .and(path(format!("/v1/blocks/{}/children", target).as_str()))
+        .and(body_json(json!({"children": [{
+            "type": "column_list",
+            "column_list": {"children": [{
+                "type": "column",
+                "column": {"children": [toggle_body]}
+            }]}
+        }]})))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(list_json(vec![layout_json(copy, "column_list")], None)),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path(
+            format!("/v1/blocks/{}/children", content_copy).as_str(),
+        ))
+        .and(body_json(json!({"children": [toggle_body]})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(list_json(
+            vec![toggle_json("0c7a7c9a-0000-4000-8000-000000000004", false)],
+            None,
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let created = test_client(&server)
+        .duplicate_block_into(
+            BlockId::from_str(source).unwrap(),
+            BlockId::from_str(target).unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(created.len(), 1);
+    assert_eq!(created[0].as_id(), &BlockId::from_str(copy).unwrap());
+}
+
+fn synced_reference_json(
+    id: &str,
+    original: &str,
+) -> Value {
+    json!({
+        "object": "block",
+        "id": id,
+        "type": "synced_block",
+        "created_time": "2021-05-15T17:16:00.000Z",
+        "last_edited_time": "2021-05-15T17:16:00.000Z",
+        "created_by": {"object": "user", "id": "e2507360-468c-4e0f-a928-7bbcbbb45353"},
+        "last_edited_by": {"object": "user", "id": "e2507360-468c-4e0f-a928-7bbcbbb45353"},
+        "has_children": true,
+        "archived": false,
+        "synced_block": {"synced_from": {"type": "block_id", "block_id": original}}
+    })
+}
+
+#[tokio::test]
+async fn duplicate_block_into_leaves_out_synced_block_content() {
+    let server = MockServer::start().await;
+    let source = "a1d8b2b7-5c8f-4d77-9c58-1a4b8b5e8f10";
+    let reference = "b2e9c3c8-6d90-4e88-8d69-2b5c9c6f9a21";
+    let original = "7bdb2c53-3b2c-4d2a-9a31-5e6168a3a7a7";
+    let target = "c3fad4d9-7ea1-4f99-9e7a-3c6dad7a0b32";
+    let copy = "d40be5ea-8fb2-4aaa-8f8b-4d7ebe8b1c43";
+    let reference_copy = "e51cf6fb-90c3-4bbb-9a9c-5e8fcf9c2d54";
+    let reference_body = json!({
+        "type": "synced_block",
+        "synced_block": {"synced_from": {"type": "block_id", "block_id": original}}
+    });
+
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/blocks/{}", source).as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(toggle_json(source, true)))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/blocks/{}/children", source).as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(list_json(
+            vec![synced_reference_json(reference, original)],
+            None,
+        )))
+        .mount(&server)
+        .await;
+    // The content of the original, as mirrored by the duplicate
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/blocks/{}/children", reference).as_str()))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(list_json(vec![toggle_json(original, false)], None)),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path(format!("/v1/blocks/{}/children", target).as_str()))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(list_json(vec![toggle_json(copy, true)], None)),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path(format!("/v1/blocks/{}/children", copy).as_str()))
+        .and(body_json(json!({"children": [reference_body]})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(list_json(
+            vec![synced_reference_json(reference_copy, original)],
+            None,
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path(
+            format!("/v1/blocks/{}/children", reference_copy).as_str(),
+        ))
+        .respond_with(ResponseTemplate::new(400))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server);
+    api.duplicate_block_into(
+        BlockId::from_str(source).unwrap(),
+        BlockId::from_str(target).unwrap(),
+    )
+    .await
+    .unwrap();
+    server.verify().await;
+
+    // Duplicating the reference itself doesn't fetch the mirrored content either.
+    server.reset().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/blocks/{}", reference).as_str()))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(synced_reference_json(reference, original)),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/blocks/{}/children", reference).as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(list_json(vec![], None)))
+        .expect(0)
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path(format!("/v1/blocks/{}/children", target).as_str()))
+        .and(body_json(json!({"children": [reference_body]})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(list_json(
+            vec![synced_reference_json(reference_copy, original)],
+            None,
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    api.duplicate_block_into(
+        BlockId::from_str(reference).unwrap(),
+        BlockId::from_str(target).unwrap(),
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn get_pages_keeps_order_and_errors() {
+    let server = MockServer::start().await;
+    let ids = [
+        "11111111-1111-1111-1111-111111111111",
+        "22222222-2222-2222-2222-222222222222",
+        "33333333-3333-3333-3333-333333333333",
+    ];
+    for id in [ids[0], ids[2]] {
+        Mock::given(method("GET"))
+            .and(path(format!("/v1/pages/{}", id)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(page_json(id)))
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/pages/{}", ids[1])))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "object": "error",
+            "status": 404,
+            "code": "object_not_found",
+            "message": "Could not find page."
+        })))
+        .mount(&server)
+        .await;
+
+    let page_ids: Vec<PageId> = ids.iter().map(|id| PageId::from_str(id).unwrap()).collect();
+    let results = test_client(&server).get_pages(&page_ids).await;
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().unwrap().id, page_ids[0]);
+    assert!(matches!(results[1], Err(Error::ApiError { .. })));
+    assert_eq!(results[2].as_ref().unwrap().id, page_ids[2]);
+}
+
+#[tokio::test]
+async fn sends_notion_version_header() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/users/me"))
+        .and(header("Notion-Version", "2022-06-28"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(include_str!("models/tests/bot_user.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/users/me"))
+        .and(header("Notion-Version", "2022-02-22"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(include_str!("models/tests/bot_user.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    test_client(&server).get_me().await.unwrap();
+    NotionApi::builder("test-token".to_string())
+        .base_url(server.uri())
+        .notion_version("2022-02-22")
+        .build()
+        .unwrap()
+        .get_me()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn slow_responses_time_out() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/users/me"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(include_str!("models/tests/bot_user.json"))
+                .set_delay(Duration::from_millis(500)),
+        )
+        .mount(&server)
+        .await;
+
+    let result = NotionApi::builder("test-token".to_string())
+        .base_url(server.uri())
+        .timeout(Duration::from_millis(50))
+        .build()
+        .unwrap()
+        .get_me()
+        .await;
+
+    assert!(matches!(result, Err(Error::Timeout { .. })), "{:?}", result);
+    assert!(result.unwrap_err().is_retryable());
+}
+
+#[test]
+fn retryable_errors() {
+    let api_error = |status: u16, code: &str| Error::ApiError {
+        error: serde_json::from_value(json!({
+            "status": status,
+            "code": code,
+            "message": "message"
+        }))
+        .unwrap(),
+        context: RequestContext {
+            method: Method::GET,
+            path: format!("/v1/pages/{}", PAGE_ID),
+            request_id: None,
+        },
+    };
+
+    assert!(Error::RateLimited { retry_after: None }.is_retryable());
+    assert!(api_error(500, "internal_server_error").is_retryable());
+    assert!(api_error(504, "gateway_timeout").is_retryable());
+
+    assert!(!api_error(400, "validation_error").is_retryable());
+    assert!(!api_error(401, "unauthorized").is_retryable());
+    assert!(!api_error(404, "object_not_found").is_retryable());
+    assert!(!Error::InvalidRequest {
+        message: "message".to_string()
+    }
+    .is_retryable());
+}
+
+#[tokio::test]
+async fn move_page_rejects_block_parent() {
+    let server = MockServer::start().await;
+    Mock::given(method("PATCH"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page_json(PAGE_ID)))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let parent = Parent::Block {
+        block_id: BlockId::from_str(PAGE_ID).unwrap(),
+    };
+    let result = test_client(&server)
+        .move_page(PageId::from_str(PAGE_ID).unwrap(), parent)
+        .await;
+
+    assert!(matches!(result, Err(Error::InvalidRequest { .. })));
+}
+
+#[tokio::test]
+async fn set_and_clear_page_icon() {
+    let icon = FileOrEmojiObject::emoji("📄");
+    assert_eq!(
+        serde_json::to_value(&icon).unwrap(),
+        json!({"type": "emoji", "emoji": "📄"})
+    );
+
+    let server = MockServer::start().await;
+    let page_path = format!("/v1/pages/{}", PAGE_ID);
+    Mock::given(method("PATCH"))
+        .and(path(page_path.as_str()))
+        .and(body_json(json!({"icon": null})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page_json(PAGE_ID)))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path(page_path.as_str()))
+        .and(body_partial_json(
+            json!({"icon": {"type": "emoji", "emoji": "📄"}}),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page_json(PAGE_ID)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server);
+    let page_id = PageId::from_str(PAGE_ID).unwrap();
+    api.set_page_icon(page_id.clone(), icon).await.unwrap();
+    api.clear_page_icon(page_id).await.unwrap();
+}
+
+/// Tracks how many requests are waiting for a response at the same time.
+#[derive(Default)]
+struct ConcurrencyObserver {
+    in_flight: AtomicUsize,
+    max_in_flight: AtomicUsize,
+}
+
+impl RequestObserver for ConcurrencyObserver {
+    fn on_request(
+        &self,
+        _method: &Method,
+        _url: &Url,
+        _body: Option<&str>,
+    ) {
+        let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+    }
+
+    fn on_response(
+        &self,
+        _status: StatusCode,
+        _body: &str,
+    ) {
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+fn page_create_request(name: &str) -> PageCreateRequest {
+    PageCreateRequest {
+        parent: Parent::Database {
+            database_id: DatabaseId::from_str(DATABASE_ID).unwrap(),
+        },
+        properties: Properties {
+            properties: HashMap::from([(
+                "Name".to_string(),
+                PropertyValue::Title {
+                    id: PropertyId::from_str("title").unwrap(),
+                    title: name.into_rich_text(),
+                },
+            )]),
+        },
+        children: None,
+        icon: None,
+        cover: None,
+    }
+}
+
+#[tokio::test]
+async fn create_pages_caps_concurrency_and_reports_errors() {
+    let server = MockServer::start().await;
+    let titled = |name: &str| {
+        body_partial_json(json!({"properties": {"Name": {"title": [{"plain_text": name}]}}}))
+    };
+
+    Mock::given(method("POST"))
+        .and(path("/v1/pages"))
+        .and(titled("Invalid"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+            "object": "error",
+            "status": 400,
+            "code": "validation_error",
+            "message": "Name is not a property that exists."
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/pages"))
+        .and(titled("Rate limited"))
+        .respond_with(ResponseTemplate::new(429).set_body_json(rate_limited_json()))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/pages"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(page_json(PAGE_ID))
+                .set_delay(Duration::from_millis(50)),
+        )
+        .mount(&server)
+        .await;
+
+    let observer = Arc::new(ConcurrencyObserver::default());
+    let api = test_client(&server)
+        .with_retry(fast_retry(2))
+        .with_observer(observer.clone());
+    let names = ["One", "Invalid", "Rate limited", "Four", "Five", "Six"];
+    let results = api
+        .create_pages(
+            names.iter().map(|name| page_create_request(name)).collect(),
+            2,
+        )
+        .await;
+
+    assert_eq!(results.len(), names.len());
+    for (name, result) in names.iter().zip(&results) {
+        match *name {
+            "Invalid" => assert!(matches!(result, Err(Error::ApiError { .. }))),
+            _ => assert!(result.is_ok(), "{} failed: {:?}", name, result),
+        }
+    }
+    assert_eq!(observer.max_in_flight.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn create_pages_does_not_retry_server_errors() {
+    let server = MockServer::start().await;
+
+    // Neither with the client's retries nor with the default ones
+    for api in [
+        test_client(&server).with_retry(fast_retry(2)),
+        test_client(&server),
+    ] {
+        server.reset().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/pages"))
+            .respond_with(ResponseTemplate::new(502).set_body_json(json!({
+                "object": "error",
+                "status": 502,
+                "code": "internal_server_error",
+                "message": "Bad gateway"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let results = api
+            .create_pages(vec![page_create_request("Maybe created")], 1)
+            .await;
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+        server.verify().await;
+    }
+}
+
+fn person_json(
+    id: &str,
+    name: &str,
+) -> Value {
+    json!({
+        "object": "user",
+        "id": id,
+        "name": name,
+        "avatar_url": null,
+        "type": "person",
+        "person": {"email": format!("{}@example.com", name.to_lowercase())}
+    })
+}
+
+#[tokio::test]
+async fn list_all_users_follows_cursor() {
+    let server = MockServer::start().await;
+    let ada = "2f5e8c1a-7d4b-4e39-9a6f-3c2b1d0e9f8a";
+    let grace = "5b1c9d3e-2a4f-4b6c-8d7e-9f0a1b2c3d4e";
+    let bot: Value = serde_json::from_str(include_str!("models/tests/bot_user.json")).unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/v1/users"))
+        .and(query_param("start_cursor", "cursor-1"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(list_json(vec![person_json(grace, "Grace")], None)),
+        )
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/users"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(list_json(
+            vec![person_json(ada, "Ada"), bot],
+            Some("cursor-1"),
+        )))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let api = test_client(&server);
+    let ids = |users: Vec<User>| -> Vec<UserId> { users.iter().map(|u| u.id().clone()).collect() };
+
+    let people = api.list_all_users(false).await.unwrap();
+    assert_eq!(
+        ids(people),
+        vec![
+            UserId::from_str(ada).unwrap(),
+            UserId::from_str(grace).unwrap()
+        ]
+    );
+
+    let everyone = api.list_all_users(true).await.unwrap();
+    assert_eq!(everyone.len(), 3);
+    assert!(matches!(everyone[1], User::Bot { .. }));
+}
+
+#[tokio::test]
+async fn get_page_with_blocks_populates_blocks() {
+    let server = MockServer::start().await;
+    let first = "0c7a7c9a-0000-4000-8000-000000000011";
+    let second = "0c7a7c9a-0000-4000-8000-000000000012";
+
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/pages/{}", PAGE_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page_json(PAGE_ID)))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/blocks/{}/children", PAGE_ID)))
+        .and(query_param("start_cursor", "cursor-1"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(list_json(vec![toggle_json(second, false)], None)),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/blocks/{}/children", PAGE_ID)))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(list_json(vec![toggle_json(first, false)], Some("cursor-1"))),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let page = test_client(&server)
+        .get_page_with_blocks(PageId::from_str(PAGE_ID).unwrap(), 1)
+        .await
+        .unwrap();
+
+    let ids: Vec<&BlockId> = page
+        .blocks
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(Block::as_id)
+        .collect();
+    assert_eq!(
+        ids,
+        vec![
+            &BlockId::from_str(first).unwrap(),
+            &BlockId::from_str(second).unwrap()
+        ]
+    );
+}
@@ -1,5 +1,5 @@
+use std::convert::Infallible;
 use std::fmt::Display;
-use std::fmt::Error;
 
 pub trait Identifier: Display {
     fn value(&self) -> &str;
@@ -28,8 +28,32 @@ where
     }
 }
 
+/// The error returned when parsing a string that isn't a Notion id.
+#[derive(Debug, Eq, PartialEq, Clone, thiserror::Error)]
+#[error("Invalid Notion id: {input:?}")]
+pub struct ParseIdError {
+    input: String,
+}
+
 macro_rules! identifer {
     ($name:ident) => {
+        identifer!(@base $name);
+
+        /// Accepts the id with or without dashes, and in either case.
+        /// It's normalized to the dashed, lowercase form the API responds with,
+        /// so parsed ids compare equal to the ones in responses.
+        impl std::str::FromStr for $name {
+            type Err = ParseIdError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                normalize(s).map($name).ok_or_else(|| ParseIdError {
+                    input: s.to_string(),
+                })
+            }
+        }
+    };
+    // Without a FromStr implementation, for ids that aren't parsed as UUIDs.
+    (@base $name:ident) => {
         #[derive(serde::Serialize, serde::Deserialize, Debug, Eq, PartialEq, Hash, Clone)]
         #[serde(transparent)]
         pub struct $name(String);
@@ -48,14 +72,6 @@ macro_rules! identifer {
                 self.0.fmt(f)
             }
         }
-
-        impl std::str::FromStr for $name {
-            type Err = Error;
-
-            fn from_str(s: &str) -> Result<Self, Self::Err> {
-                Ok($name(s.to_string()))
-            }
-        }
     };
 }
 
@@ -63,10 +79,208 @@ identifer!(DatabaseId);
 identifer!(PageId);
 identifer!(BlockId);
 identifer!(UserId);
-identifer!(PropertyId);
+identifer!(@base PropertyId);
+identifer!(CommentId);
+identifer!(DiscussionId);
+
+/// An empty id, for property values built before the id of their property is known.
+/// It is left out when serializing the value.
+impl Default for PropertyId {
+    fn default() -> Self {
+        PropertyId(String::new())
+    }
+}
+
+impl PropertyId {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Property ids are short, url encoded strings rather than UUIDs, so any string is accepted.
+impl std::str::FromStr for PropertyId {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(PropertyId(s.to_string()))
+    }
+}
+
+impl PageId {
+    /// Extracts the id from the url of a page, e.g.
+    /// `https://www.notion.so/workspace/Page-Title-0123456789abcdef0123456789abcdef`.
+    ///
+    /// Pages opened from a database view carry their id in the `p` query parameter,
+    /// which is preferred over the database id in the path.
+    pub fn from_url(url: &str) -> Result<Self, ParseIdError> {
+        id_from_url(url, Some("p")).map(PageId)
+    }
+}
+
+impl DatabaseId {
+    /// Extracts the id from the url of a database, e.g.
+    /// `https://www.notion.so/workspace/0123456789abcdef0123456789abcdef?v=...`.
+    pub fn from_url(url: &str) -> Result<Self, ParseIdError> {
+        id_from_url(url, None).map(DatabaseId)
+    }
+}
 
 impl From<PageId> for BlockId {
     fn from(page_id: PageId) -> Self {
         BlockId(page_id.0)
     }
 }
+
+impl BlockId {
+    /// Child page and child database blocks share their id with the page or database.
+    pub(crate) fn to_page_id(&self) -> PageId {
+        PageId(self.0.clone())
+    }
+
+    pub(crate) fn to_database_id(&self) -> DatabaseId {
+        DatabaseId(self.0.clone())
+    }
+}
+
+/// Formats a 32 digit hex id, optionally dashed like a UUID, as a dashed, lowercase UUID.
+fn normalize(id: &str) -> Option<String> {
+    let id = id.trim();
+    let hex = match id.len() {
+        32 => id.to_string(),
+        36 if [8, 13, 18, 23]
+            .iter()
+            .all(|index| id.as_bytes()[*index] == b'-') =>
+        {
+            id.replace('-', "")
+        }
+        _ => return None,
+    };
+    if hex.len() != 32 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let hex = hex.to_ascii_lowercase();
+    Some(format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    ))
+}
+
+/// Finds the id at the end of the last path segment of a Notion url,
+/// or in the `query_parameter` if that is present.
+fn id_from_url(
+    url: &str,
+    query_parameter: Option<&str>,
+) -> Result<String, ParseIdError> {
+    let error = || ParseIdError {
+        input: url.to_string(),
+    };
+    let parsed = reqwest::Url::parse(url)
+        .or_else(|_| reqwest::Url::parse(&format!("https://{}", url)))
+        .map_err(|_| error())?;
+
+    if let Some(parameter) = query_parameter {
+        let id = parsed
+            .query_pairs()
+            .find(|(key, _)| key == parameter)
+            .and_then(|(_, value)| normalize(&value));
+        if let Some(id) = id {
+            return Ok(id);
+        }
+    }
+
+    // The id follows the title of the page in the slug, separated by a dash.
+    let segment = parsed
+        .path_segments()
+        .and_then(|mut segments| segments.rfind(|segment| !segment.is_empty()))
+        .ok_or_else(error)?;
+    normalize(segment)
+        .or_else(|| {
+            segment
+                .get(segment.len().saturating_sub(32)..)
+                .and_then(normalize)
+        })
+        .ok_or_else(error)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ids::{BlockId, DatabaseId, PageId, ParseIdError, PropertyId};
+    use std::str::FromStr;
+
+    const ID: &str = "5c6a2821-6bb1-4a7e-b6e1-c50111515c3d";
+
+    #[test]
+    fn parse_dashed_ids() {
+        assert_eq!(PageId::from_str(ID).unwrap().to_string(), ID);
+        assert_eq!(
+            BlockId::from_str("5C6A2821-6BB1-4A7E-B6E1-C50111515C3D")
+                .unwrap()
+                .to_string(),
+            ID
+        );
+    }
+
+    #[test]
+    fn parse_undashed_ids() {
+        assert_eq!(
+            DatabaseId::from_str("5c6a28216bb14a7eb6e1c50111515c3d").unwrap(),
+            DatabaseId::from_str(ID).unwrap()
+        );
+    }
+
+    #[test]
+    fn reject_invalid_ids() {
+        for invalid in [
+            "",
+            "not an id",
+            "5c6a28216bb14a7eb6e1c50111515c3",
+            "5c6a2821-6bb1-4a7e-b6e1-c50111515c3g",
+            "5c6a28216-bb1-4a7e-b6e1-c50111515c3d",
+        ] {
+            assert_eq!(
+                PageId::from_str(invalid),
+                Err(ParseIdError {
+                    input: invalid.to_string()
+                })
+            );
+        }
+        assert_eq!(
+            PropertyId::from_str("%3AUPp").unwrap().to_string(),
+            "%3AUPp"
+        );
+    }
+
+    #[test]
+    fn page_id_from_url() {
+        let page_id = PageId::from_str(ID).unwrap();
+        for url in [
+            "https://www.notion.so/workspace/Page-Title-5c6a28216bb14a7eb6e1c50111515c3d",
+            "https://www.notion.so/5c6a28216bb14a7eb6e1c50111515c3d?pvs=4",
+            "https://workspace.notion.site/Page-5c6a28216bb14a7eb6e1c50111515c3d#f2ae7a0e",
+            "https://www.notion.so/workspace/5c6a2821-6bb1-4a7e-b6e1-c50111515c3d/",
+            "notion.so/Page-Title-5c6a28216bb14a7eb6e1c50111515c3d",
+            "https://www.notion.so/workspace/0123456789abcdef0123456789abcdef?v=fedcba9876543210fedcba9876543210&p=5c6a28216bb14a7eb6e1c50111515c3d",
+        ] {
+            assert_eq!(PageId::from_url(url).unwrap(), page_id, "{}", url);
+        }
+
+        assert!(PageId::from_url("https://www.notion.so/workspace/Page-Title").is_err());
+        assert!(PageId::from_url("https://www.notion.so/").is_err());
+    }
+
+    #[test]
+    fn database_id_from_url() {
+        assert_eq!(
+            DatabaseId::from_url(
+                "https://www.notion.so/workspace/5c6a28216bb14a7eb6e1c50111515c3d?v=fedcba9876543210fedcba9876543210&p=0123456789abcdef0123456789abcdef"
+            )
+            .unwrap(),
+            DatabaseId::from_str(ID).unwrap()
+        );
+    }
+}
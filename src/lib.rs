@@ -1,30 +1,79 @@
+#![allow(clippy::result_large_err)]
+
+use crate::cache::SchemaCache;
 use crate::ids::{BlockId, DatabaseId, PropertyId};
 use crate::models::error::ErrorResponse;
-use crate::models::properties::PropertyItem;
+use crate::models::properties::{PropertyItem, PropertyValue, RollupPropertyValue, RollupValue};
 use crate::models::search::{DatabaseQuery, SearchRequest};
-use crate::models::{Database, ListResponse, Object, Page};
-use ids::{AsIdentifier, PageId};
-use models::block::{Block, CreateBlock};
-use models::paging::PagingCursor;
+use crate::models::{
+    Database, DatabaseCreateRequest, DatabaseUpdateRequest, FromProperties, ListResponse, Object,
+    Page, Parent, PropertyResponse,
+};
+use futures::future::BoxFuture;
+use futures::{stream, FutureExt, Stream, StreamExt, TryStreamExt};
+use ids::{AsIdentifier, Identifier, PageId};
+use models::block::{Block, FileOrEmojiObject, UpdateBlock};
+use models::comments::{Comment, CreateCommentRequest};
+use models::paging::{Pageable, PagingCursor};
+use models::users::User;
 use models::{PageCreateRequest, PageUpdateRequest, UpdateBlockChildrenRequest};
+use observer::RequestObserver;
 use reqwest::header::{HeaderMap, HeaderValue};
-use reqwest::{header, Client, ClientBuilder, RequestBuilder};
+use reqwest::{header, Client, ClientBuilder, Method, RequestBuilder, StatusCode};
+use retry::{BackoffStrategy, RetryConfig};
+use serde::Deserialize;
+use std::fmt::{Display, Formatter};
+use std::future::Future;
+use std::ops::ControlFlow;
+use std::sync::Arc;
+use std::time::Duration;
 use tracing::Instrument;
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod cache;
 pub mod ids;
 pub mod models;
+pub mod oauth;
+pub mod observer;
+pub mod retry;
+#[cfg(feature = "testing")]
+pub mod testing;
+
+/// Derives [FromProperties](models::FromProperties) and [IntoProperties](models::IntoProperties)
+/// for a struct whose fields map to the properties of a database row.
+#[cfg(feature = "derive")]
+pub use notion_derive::NotionRow;
+
+#[cfg(test)]
+mod client_tests;
 
+const NOTION_API_BASE_URL: &str = "https://api.notion.com";
 const NOTION_API_VERSION: &str = "2022-06-28";
+/// The number of children the API accepts in a single append request.
+const MAX_BLOCKS_PER_APPEND: usize = 100;
+/// How many requests [NotionApi::get_pages] keeps in flight.
+const MAX_CONCURRENT_REQUESTS: usize = 3;
+/// How long a request may take, including reading the response, unless configured otherwise.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// An wrapper Error type for all errors produced by the [`NotionApi`](NotionApi) client.
 #[derive(Debug, thiserror::Error)]
+#[allow(clippy::large_enum_variant)]
 pub enum Error {
     #[error("Invalid Notion API Token: {}", source)]
     InvalidApiToken { source: header::InvalidHeaderValue },
 
+    #[error("Invalid Notion-Version header: {}", source)]
+    InvalidNotionVersion { source: header::InvalidHeaderValue },
+
     #[error("Unable to build reqwest HTTP client: {}", source)]
     ErrorBuildingClient { source: reqwest::Error },
 
+    #[cfg(feature = "blocking")]
+    #[error("Unable to build the runtime of the blocking client: {}", source)]
+    ErrorBuildingRuntime { source: std::io::Error },
+
     #[error("Error sending HTTP request: {}", source)]
     RequestFailed {
         #[from]
@@ -34,14 +83,182 @@ pub enum Error {
     #[error("Error reading response: {}", source)]
     ResponseIoError { source: reqwest::Error },
 
+    #[error("Request timed out: {}", source)]
+    Timeout { source: reqwest::Error },
+
     #[error("Error parsing json response: {}", source)]
     JsonParseError { source: serde_json::Error },
 
-    #[error("Unexpected API Response")]
-    UnexpectedResponse { response: Object },
+    #[error("Unexpected API Response to {}", context)]
+    UnexpectedResponse {
+        response: Object,
+        context: RequestContext,
+    },
+
+    #[error("API Error {}({}) on {}: {}", .error.code, .error.status, .context, .error.message)]
+    ApiError {
+        error: ErrorResponse,
+        context: RequestContext,
+    },
+
+    #[error("Unable to convert the properties of page {}: {}", page_id, source)]
+    PropertiesConversion {
+        page_id: PageId,
+        source: models::FromPropertiesError,
+    },
+
+    #[error("Invalid request: {}", message)]
+    InvalidRequest { message: String },
+
+    #[error("Rate limited by the Notion API, retry after {:?}", retry_after)]
+    RateLimited { retry_after: Option<Duration> },
+
+    #[error("OAuth Error {}: {}", .error.error, .error.error_description.as_deref().unwrap_or_default())]
+    OAuthError { error: oauth::OAuthErrorResponse },
+}
+
+impl Error {
+    /// Whether the failure is likely transient, so the request may succeed when sent again:
+    /// rate limits, server errors, timeouts and connection failures.
+    ///
+    /// Invalid requests, missing permissions or objects and unexpected responses aren't.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::RateLimited { .. } | Error::Timeout { .. } | Error::ResponseIoError { .. } => {
+                true
+            }
+            Error::RequestFailed { source } => source.is_timeout() || source.is_connect(),
+            Error::ApiError { error, .. } => error.is_retryable(),
+            _ => false,
+        }
+    }
+}
+
+/// The request a [Error::ApiError] or [Error::UnexpectedResponse] was returned for.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct RequestContext {
+    pub method: Method,
+    /// The path of the url, e.g. `/v1/pages/bb85a889-3eb3-4146-9325-80508fb5e23d`.
+    pub path: String,
+    /// Set if Notion included the id it assigned to the request in the response.
+    pub request_id: Option<String>,
+}
+
+impl Display for RequestContext {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> std::fmt::Result {
+        write!(f, "{} {}", self.method, self.path)?;
+        match &self.request_id {
+            Some(request_id) => write!(f, " (request id {})", request_id),
+            None => Ok(()),
+        }
+    }
+}
+
+/// What [NotionApi::walk_blocks] does after visiting a block.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum WalkControl {
+    /// Descend into the children of the block, then go on with its siblings.
+    Continue,
+    /// Go on with the next sibling without fetching the children of the block.
+    SkipChildren,
+    /// End the walk.
+    Stop,
+}
+
+/// A successfully parsed response, along with the request it answers.
+struct JsonResponse {
+    object: Object,
+    context: RequestContext,
+    json: String,
+}
+
+impl JsonResponse {
+    /// Converts the response with `expect`, which hands back objects of an unexpected type.
+    fn expect<T>(
+        self,
+        expect: impl FnOnce(Object) -> Result<T, Object>,
+    ) -> Result<T, Error> {
+        let JsonResponse {
+            object,
+            mut context,
+            json,
+        } = self;
+        expect(object).map_err(|response| {
+            #[derive(Deserialize)]
+            struct RequestId {
+                request_id: Option<String>,
+            }
+            context.request_id = serde_json::from_str::<RequestId>(&json)
+                .ok()
+                .and_then(|id| id.request_id);
+            Error::UnexpectedResponse { response, context }
+        })
+    }
+}
+
+/// The `property_item` of a paginated property response, which describes the listed property.
+#[derive(Deserialize)]
+struct PropertyList {
+    property_item: Option<ListedProperty>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ListedProperty {
+    Title {
+        id: PropertyId,
+    },
+    RichText {
+        id: PropertyId,
+    },
+    Relation {
+        id: PropertyId,
+    },
+    People {
+        id: PropertyId,
+    },
+    /// The items of a rollup are the rolled up values, its result is only given here.
+    Rollup {
+        id: PropertyId,
+        rollup: RollupValue,
+    },
+    #[serde(other)]
+    Other,
+}
+
+impl ListedProperty {
+    /// The value of the property when the list holds no items.
+    fn empty_value(self) -> Option<PropertyValue> {
+        let value = match self {
+            ListedProperty::Title { id } => PropertyValue::Title { id, title: vec![] },
+            ListedProperty::RichText { id } => PropertyValue::Text {
+                id,
+                rich_text: vec![],
+            },
+            ListedProperty::Relation { id } => PropertyValue::Relation {
+                id,
+                relation: Some(vec![]),
+            },
+            ListedProperty::People { id } => PropertyValue::People { id, people: vec![] },
+            ListedProperty::Rollup { id, rollup } => PropertyValue::Rollup {
+                id,
+                rollup: Some(rollup),
+            },
+            ListedProperty::Other => return None,
+        };
+        Some(value)
+    }
+}
 
-    #[error("API Error {}({}): {}", .error.code, .error.status, .error.message)]
-    ApiError { error: ErrorResponse },
+/// A rolled up item as an element of a rollup array.
+/// Both share the tagged representation of property values, minus the id.
+fn rollup_item(value: PropertyValue) -> Option<RollupPropertyValue> {
+    serde_json::to_value(value)
+        .and_then(serde_json::from_value)
+        .ok()
 }
 
 /// An API client for Notion.
@@ -49,37 +266,169 @@ pub enum Error {
 #[derive(Clone)]
 pub struct NotionApi {
     client: Client,
+    base_url: String,
+    retry: Option<RetryConfig>,
+    observer: Option<Arc<dyn RequestObserver>>,
+    schema_cache: Option<Arc<SchemaCache>>,
 }
 
-impl NotionApi {
-    /// Creates an instance of NotionApi.
-    /// May fail if the provided api_token is an improper value.
-    pub fn new(api_token: String) -> Result<Self, Error> {
+/// Configures a [NotionApi], see [NotionApi::builder].
+pub struct NotionApiBuilder {
+    api_token: String,
+    base_url: String,
+    notion_version: String,
+    timeout: Duration,
+}
+
+impl NotionApiBuilder {
+    /// The server requests are sent to, `https://api.notion.com` by default.
+    pub fn base_url<T: Into<String>>(
+        mut self,
+        base_url: T,
+    ) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// The `Notion-Version` header sent with every request.
+    /// Defaults to the version the models of this crate are written against.
+    pub fn notion_version<T: Into<String>>(
+        mut self,
+        notion_version: T,
+    ) -> Self {
+        self.notion_version = notion_version.into();
+        self
+    }
+
+    /// How long a request may take before it fails with [Error::Timeout], 30 seconds by default.
+    /// Every retry of a request gets the full timeout.
+    pub fn timeout(
+        mut self,
+        timeout: Duration,
+    ) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// May fail if the api token or the version aren't valid header values.
+    pub fn build(self) -> Result<NotionApi, Error> {
         let mut headers = HeaderMap::new();
         headers.insert(
             "Notion-Version",
-            HeaderValue::from_static(NOTION_API_VERSION),
+            HeaderValue::from_str(&self.notion_version)
+                .map_err(|source| Error::InvalidNotionVersion { source })?,
         );
 
-        let mut auth_value = HeaderValue::from_str(&format!("Bearer {}", api_token))
+        let mut auth_value = HeaderValue::from_str(&format!("Bearer {}", self.api_token))
             .map_err(|source| Error::InvalidApiToken { source })?;
         auth_value.set_sensitive(true);
         headers.insert(header::AUTHORIZATION, auth_value);
 
         let client = ClientBuilder::new()
             .default_headers(headers)
+            .timeout(self.timeout)
             .build()
             .map_err(|source| Error::ErrorBuildingClient { source })?;
 
-        Ok(Self { client })
+        Ok(NotionApi {
+            client,
+            base_url: self.base_url.trim_end_matches('/').to_string(),
+            retry: None,
+            observer: None,
+            schema_cache: None,
+        })
+    }
+}
+
+impl NotionApi {
+    /// Creates an instance of NotionApi.
+    /// May fail if the provided api_token is an improper value.
+    pub fn new(api_token: String) -> Result<Self, Error> {
+        Self::builder(api_token).build()
+    }
+
+    /// Starts configuring a client, to talk to another server or pin another API version.
+    pub fn builder(api_token: String) -> NotionApiBuilder {
+        NotionApiBuilder {
+            api_token,
+            base_url: NOTION_API_BASE_URL.to_string(),
+            notion_version: NOTION_API_VERSION.to_string(),
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Replays requests that were rate limited or failed with a server error,
+    /// according to the provided [`RetryConfig`].
+    pub fn with_retry(
+        self,
+        retry: RetryConfig,
+    ) -> Self {
+        Self {
+            retry: Some(retry),
+            ..self
+        }
+    }
+
+    /// Waits according to `backoff` between retries.
+    /// Enables retrying with the [default](RetryConfig::default) configuration if it isn't yet.
+    pub fn with_backoff(
+        self,
+        backoff: BackoffStrategy,
+    ) -> Self {
+        let retry = RetryConfig {
+            backoff,
+            ..self.retry.unwrap_or_default()
+        };
+        self.with_retry(retry)
+    }
+
+    /// Reports every request and its response to `observer`.
+    pub fn with_observer(
+        self,
+        observer: Arc<dyn RequestObserver>,
+    ) -> Self {
+        Self {
+            observer: Some(observer),
+            ..self
+        }
+    }
+
+    /// Remembers databases returned by [get_database](Self::get_database) for `ttl`,
+    /// to avoid fetching the schema before every query.
+    /// Databases updated through this client are refreshed, other changes show up
+    /// once the TTL has passed or after [invalidate_database](Self::invalidate_database).
+    pub fn with_schema_cache(
+        self,
+        ttl: Duration,
+    ) -> Self {
+        Self {
+            schema_cache: Some(Arc::new(SchemaCache::new(ttl))),
+            ..self
+        }
+    }
+
+    /// Makes the next [get_database](Self::get_database) fetch the database from Notion,
+    /// even if it is cached.
+    pub fn invalidate_database<T: AsIdentifier<DatabaseId>>(
+        &self,
+        database_id: T,
+    ) {
+        if let Some(cache) = &self.schema_cache {
+            cache.remove(database_id.as_id());
+        }
     }
 
     async fn make_json_request(
         &self,
         request: RequestBuilder,
-    ) -> Result<Object, Error> {
-        let request = request.build()?;
+    ) -> Result<JsonResponse, Error> {
+        let mut request = request.build()?;
         let url = request.url();
+        let mut context = RequestContext {
+            method: request.method().clone(),
+            path: url.path().to_string(),
+            request_id: None,
+        };
         let mut prefix = "";
         let mut bodystr = "";
 
@@ -98,19 +447,69 @@ impl NotionApi {
             prefix,
             bodystr
         );
+        if let Some(observer) = &self.observer {
+            observer.on_request(
+                request.method(),
+                url,
+                (!prefix.is_empty()).then_some(bodystr),
+            );
+        }
 
-        let json = self
-            .client
-            .execute(request)
-            .instrument(tracing::trace_span!("Sending request"))
-            .await
-            .map_err(|source| Error::RequestFailed { source })?
+        let mut attempt = 0;
+        let response = loop {
+            let replay = self
+                .retry
+                .filter(|retry| attempt < retry.max_retries)
+                .and_then(|_| request.try_clone());
+
+            let response = self
+                .client
+                .execute(request)
+                .instrument(tracing::trace_span!("Sending request"))
+                .await
+                .map_err(|source| match source.is_timeout() {
+                    true => Error::Timeout { source },
+                    false => Error::RequestFailed { source },
+                })?;
+
+            let status = response.status();
+            match (self.retry, replay) {
+                (Some(retry), Some(replay)) if retry.should_retry(status) => {
+                    let delay =
+                        retry.delay(attempt, status, retry::retry_after(response.headers()));
+                    tracing::debug!(
+                        status = status.as_u16(),
+                        attempt,
+                        "Retrying request in {:?}",
+                        delay
+                    );
+                    tokio::time::sleep(delay).await;
+                    request = replay;
+                    attempt += 1;
+                }
+                (Some(_), _) if status == StatusCode::TOO_MANY_REQUESTS => {
+                    return Err(Error::RateLimited {
+                        retry_after: retry::retry_after(response.headers()),
+                    });
+                }
+                _ => break response,
+            }
+        };
+
+        let status = response.status();
+        let json = response
             .text()
             .instrument(tracing::trace_span!("Reading response"))
             .await
-            .map_err(|source| Error::ResponseIoError { source })?;
+            .map_err(|source| match source.is_timeout() {
+                true => Error::Timeout { source },
+                false => Error::ResponseIoError { source },
+            })?;
 
         tracing::debug!("JSON Response: {}", json);
+        if let Some(observer) = &self.observer {
+            observer.on_response(status, &json);
+        }
         #[cfg(test)]
         {
             dbg!(serde_json::from_str::<serde_json::Value>(&json)
@@ -120,8 +519,15 @@ impl NotionApi {
             serde_json::from_str(&json).map_err(|source| Error::JsonParseError { source })?;
 
         match result {
-            Object::Error { error } => Err(Error::ApiError { error }),
-            response => Ok(response),
+            Object::Error { error } => {
+                context.request_id = error.request_id.clone();
+                Err(Error::ApiError { error, context })
+            }
+            object => Ok(JsonResponse {
+                object,
+                context,
+                json,
+            }),
         }
     }
 
@@ -129,12 +535,14 @@ impl NotionApi {
     /// > This method is apparently deprecated/"not recommended" and
     /// > [search()](Self::search()) should be used instead.
     pub async fn list_databases(&self) -> Result<ListResponse<Database>, Error> {
-        let builder = self.client.get("https://api.notion.com/v1/databases");
+        let builder = self.client.get(format!("{}/v1/databases", self.base_url));
 
-        match self.make_json_request(builder).await? {
-            Object::List { list } => Ok(list.expect_databases()?),
-            response => Err(Error::UnexpectedResponse { response }),
-        }
+        self.make_json_request(builder)
+            .await?
+            .expect(|object| match object {
+                Object::List { list } => list.expect_databases(),
+                response => Err(response),
+            })
     }
 
     /// Search all pages in notion.
@@ -147,15 +555,27 @@ impl NotionApi {
         let result = self
             .make_json_request(
                 self.client
-                    .post("https://api.notion.com/v1/search")
+                    .post(format!("{}/v1/search", self.base_url))
                     .json(&query.into()),
             )
             .await?;
 
-        match result {
+        result.expect(|object| match object {
             Object::List { list } => Ok(list),
-            response => Err(Error::UnexpectedResponse { response }),
-        }
+            response => Err(response),
+        })
+    }
+
+    /// Search and stream every page and database found,
+    /// transparently following `next_cursor` until all results have been returned.
+    ///
+    /// The query, sort, filter, and page size are kept for every request.
+    /// If a request fails the error is yielded and the stream ends.
+    pub fn search_all<T: Into<SearchRequest>>(
+        &self,
+        query: T,
+    ) -> impl Stream<Item = Result<Object, Error>> + '_ {
+        paginate(query.into(), move |query| self.search(query))
     }
 
     /// Get a database by [DatabaseId].
@@ -163,17 +583,100 @@ impl NotionApi {
         &self,
         database_id: T,
     ) -> Result<Database, Error> {
+        if let Some(database) = self
+            .schema_cache
+            .as_ref()
+            .and_then(|cache| cache.get(database_id.as_id()))
+        {
+            return Ok(database);
+        }
+
         let result = self
             .make_json_request(self.client.get(format!(
-                "https://api.notion.com/v1/databases/{}",
+                "{}/v1/databases/{}",
+                self.base_url,
                 database_id.as_id()
             )))
             .await?;
 
-        match result {
+        let database = result.expect(|object| match object {
             Object::Database { database } => Ok(database),
-            response => Err(Error::UnexpectedResponse { response }),
+            response => Err(response),
+        })?;
+        if let Some(cache) = &self.schema_cache {
+            cache.insert(&database);
         }
+        Ok(database)
+    }
+
+    /// Creates a new database and return the created database.
+    ///
+    /// Fails with [Error::InvalidRequest] without sending the request if the parent isn't a page
+    /// or the schema doesn't have exactly one title property.
+    pub async fn create_database(
+        &self,
+        request: DatabaseCreateRequest,
+    ) -> Result<Database, Error> {
+        request.validate()?;
+
+        let result = self
+            .make_json_request(
+                self.client
+                    .post(format!("{}/v1/databases", self.base_url))
+                    .json(&request),
+            )
+            .await?;
+
+        result.expect(|object| match object {
+            Object::Database { database } => Ok(database),
+            response => Err(response),
+        })
+    }
+
+    /// Update the title, description or properties of a database by [DatabaseId].
+    pub async fn update_database<T: AsIdentifier<DatabaseId>>(
+        &self,
+        database_id: T,
+        request: DatabaseUpdateRequest,
+    ) -> Result<Database, Error> {
+        let result = self
+            .make_json_request(
+                self.client
+                    .patch(format!(
+                        "{}/v1/databases/{}",
+                        self.base_url,
+                        database_id.as_id()
+                    ))
+                    .json(&request),
+            )
+            .await?;
+
+        let database = result.expect(|object| match object {
+            Object::Database { database } => Ok(database),
+            response => Err(response),
+        })?;
+        if let Some(cache) = &self.schema_cache {
+            cache.insert(&database);
+        }
+        Ok(database)
+    }
+
+    /// Archives (moves to trash) a database and return the archived database
+    pub async fn archive_database<T: AsIdentifier<DatabaseId>>(
+        &self,
+        database_id: T,
+    ) -> Result<Database, Error> {
+        self.update_database(database_id, DatabaseUpdateRequest::archived(true))
+            .await
+    }
+
+    /// Restores an archived database and return the restored database
+    pub async fn restore_database<T: AsIdentifier<DatabaseId>>(
+        &self,
+        database_id: T,
+    ) -> Result<Database, Error> {
+        self.update_database(database_id, DatabaseUpdateRequest::archived(false))
+            .await
     }
 
     /// Get a page by [PageId].
@@ -183,15 +686,48 @@ impl NotionApi {
     ) -> Result<Page, Error> {
         let result = self
             .make_json_request(self.client.get(format!(
-                "https://api.notion.com/v1/pages/{}",
+                "{}/v1/pages/{}",
+                self.base_url,
                 page_id.as_id()
             )))
             .await?;
 
-        match result {
+        result.expect(|object| match object {
             Object::Page { page } => Ok(page),
-            response => Err(Error::UnexpectedResponse { response }),
-        }
+            response => Err(response),
+        })
+    }
+
+    /// Get a page by [PageId] together with its content.
+    ///
+    /// [Page::blocks] is populated with [get_block_children_recursive()](Self::get_block_children_recursive()),
+    /// `max_depth` set to `1` only fetches the top level blocks.
+    pub async fn get_page_with_blocks<T: AsIdentifier<PageId>>(
+        &self,
+        page_id: T,
+        max_depth: usize,
+    ) -> Result<Page, Error> {
+        let mut page = self.get_page(page_id.as_id()).await?;
+        let blocks = self
+            .get_block_children_recursive(BlockId::from(page.id.clone()), max_depth)
+            .await?;
+        page.blocks = Some(blocks);
+        Ok(page)
+    }
+
+    /// Get several pages by [PageId], fetching at most a few at a time.
+    ///
+    /// The results are in the same order as `page_ids`, a page that fails to load
+    /// doesn't stop the others from being fetched.
+    pub async fn get_pages(
+        &self,
+        page_ids: &[PageId],
+    ) -> Vec<Result<Page, Error>> {
+        stream::iter(page_ids)
+            .map(|page_id| self.get_page(page_id))
+            .buffered(MAX_CONCURRENT_REQUESTS)
+            .collect()
+            .await
     }
 
     /// Creates a new page and return the created page
@@ -202,15 +738,37 @@ impl NotionApi {
         let result = self
             .make_json_request(
                 self.client
-                    .post("https://api.notion.com/v1/pages")
+                    .post(format!("{}/v1/pages", self.base_url))
                     .json(&page.into()),
             )
             .await?;
 
-        match result {
+        result.expect(|object| match object {
             Object::Page { page } => Ok(page),
-            response => Err(Error::UnexpectedResponse { response }),
-        }
+            response => Err(response),
+        })
+    }
+
+    /// Creates many pages, with at most `concurrency` requests in flight.
+    ///
+    /// The results are in the same order as `pages`, a page that fails to be created
+    /// doesn't stop the others. Large batches are likely to be rate limited, so rate limited
+    /// requests are retried, with the client's [`RetryConfig`] or else the default one.
+    /// Server errors are never retried, as the page may have been created regardless.
+    pub async fn create_pages(
+        &self,
+        pages: Vec<PageCreateRequest>,
+        concurrency: usize,
+    ) -> Vec<Result<Page, Error>> {
+        let api = self.clone().with_retry(RetryConfig {
+            retry_server_errors: false,
+            ..self.retry.unwrap_or_default()
+        });
+        stream::iter(pages)
+            .map(|page| api.create_page(page))
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
     }
 
     /// Updates a page and return the updated page
@@ -223,21 +781,90 @@ impl NotionApi {
         P: AsIdentifier<PageId>,
         T: Into<PageUpdateRequest>,
     {
+        let page = page.into();
+        page.validate()?;
         let result = self
             .make_json_request(
                 self.client
-                    .patch(&format!(
-                        "https://api.notion.com/v1/pages/{page_id}",
+                    .patch(format!(
+                        "{base_url}/v1/pages/{page_id}",
+                        base_url = self.base_url,
                         page_id = page_id.as_id()
                     ))
-                    .json(&page.into()),
+                    .json(&page),
             )
             .await?;
 
-        match result {
+        result.expect(|object| match object {
             Object::Page { page } => Ok(page),
-            response => Err(Error::UnexpectedResponse { response }),
-        }
+            response => Err(response),
+        })
+    }
+
+    /// Archives (moves to trash) a page and return the archived page
+    pub async fn archive_page<P: AsIdentifier<PageId>>(
+        &self,
+        page_id: P,
+    ) -> Result<Page, Error> {
+        self.update_page(page_id, PageUpdateRequest::archived(true))
+            .await
+    }
+
+    /// Restores an archived page and return the restored page
+    pub async fn restore_page<P: AsIdentifier<PageId>>(
+        &self,
+        page_id: P,
+    ) -> Result<Page, Error> {
+        self.update_page(page_id, PageUpdateRequest::archived(false))
+            .await
+    }
+
+    /// Replaces the icon of a page with an emoji or an external image
+    /// and return the updated page.
+    pub async fn set_page_icon<P: AsIdentifier<PageId>>(
+        &self,
+        page_id: P,
+        icon: FileOrEmojiObject,
+    ) -> Result<Page, Error> {
+        self.update_page(page_id, PageUpdateRequest::icon(icon))
+            .await
+    }
+
+    /// Removes the icon of a page and return the updated page.
+    pub async fn clear_page_icon<P: AsIdentifier<PageId>>(
+        &self,
+        page_id: P,
+    ) -> Result<Page, Error> {
+        // PageUpdateRequest leaves out unset fields, removing the icon requires an explicit null.
+        let result = self
+            .make_json_request(
+                self.client
+                    .patch(format!(
+                        "{base_url}/v1/pages/{page_id}",
+                        base_url = self.base_url,
+                        page_id = page_id.as_id()
+                    ))
+                    .json(&serde_json::json!({ "icon": null })),
+            )
+            .await?;
+
+        result.expect(|object| match object {
+            Object::Page { page } => Ok(page),
+            response => Err(response),
+        })
+    }
+
+    /// Moves a page into another page or database and return the moved page.
+    ///
+    /// Fails with [Error::InvalidRequest] without sending the request if `new_parent`
+    /// is neither a page nor a database.
+    pub async fn move_page<P: AsIdentifier<PageId>>(
+        &self,
+        page_id: P,
+        new_parent: Parent,
+    ) -> Result<Page, Error> {
+        self.update_page(page_id, PageUpdateRequest::moved_to(new_parent))
+            .await
     }
 
     /// Get a page property by [PageId] and [PropertyId]
@@ -248,16 +875,17 @@ impl NotionApi {
     ) -> Result<PropertyItem, Error> {
         let result = self
             .make_json_request(self.client.get(format!(
-                "https://api.notion.com/v1/pages/{}/properties/{}",
+                "{}/v1/pages/{}/properties/{}",
+                self.base_url,
                 page_id.as_id(),
                 property_id.as_id()
             )))
             .await?;
 
-        match result {
+        result.expect(|object| match object {
             Object::PropertyItem { property_item } => Ok(property_item),
-            response => Err(Error::UnexpectedResponse { response }),
-        }
+            response => Err(response),
+        })
     }
 
     /// Get a paginated page property by [PageId] and [PropertyId]
@@ -268,15 +896,128 @@ impl NotionApi {
     ) -> Result<ListResponse<Object>, Error> {
         let result = self
             .make_json_request(self.client.get(format!(
-                "https://api.notion.com/v1/pages/{}/properties/{}",
+                "{}/v1/pages/{}/properties/{}",
+                self.base_url,
                 page_id.as_id(),
                 property_id.as_id()
             )))
             .await?;
 
-        match result {
+        result.expect(|object| match object {
             Object::List { list } => Ok(list),
-            response => Err(Error::UnexpectedResponse { response }),
+            response => Err(response),
+        })
+    }
+
+    /// Retrieve a single page property by [PageId] and [PropertyId].
+    ///
+    /// Paginated properties (title, rich text, relation, people) are fetched in full
+    /// by following the response cursor.
+    pub async fn retrieve_page_property<T: AsIdentifier<PageId>, U: AsIdentifier<PropertyId>>(
+        &self,
+        page_id: T,
+        property_id: U,
+    ) -> Result<PropertyResponse, Error> {
+        let (response, _) = self
+            .page_property_items(page_id.as_id(), property_id.as_id())
+            .await?;
+        Ok(response)
+    }
+
+    /// Retrieve a page property by [PageId] and [PropertyId] as a single, complete value.
+    ///
+    /// Unlike [NotionApi::retrieve_page_property], the items of paginated properties are
+    /// combined, e.g. into one relation holding every related page. Rollups are returned
+    /// with their computed value, or with all rolled up items if they show the original values.
+    pub async fn get_page_property_all<T: AsIdentifier<PageId>, U: AsIdentifier<PropertyId>>(
+        &self,
+        page_id: T,
+        property_id: U,
+    ) -> Result<PropertyValue, Error> {
+        let (response, listed) = self
+            .page_property_items(page_id.as_id(), property_id.as_id())
+            .await?;
+
+        let value = match listed {
+            None => response.into_value(),
+            Some(ListedProperty::Rollup { id, rollup }) => {
+                let rollup = match rollup {
+                    RollupValue::Array { function, .. } => RollupValue::Array {
+                        array: response
+                            .into_values()
+                            .into_iter()
+                            .filter_map(rollup_item)
+                            .collect(),
+                        function,
+                    },
+                    computed => computed,
+                };
+                Some(PropertyValue::Rollup {
+                    id,
+                    rollup: Some(rollup),
+                })
+            }
+            Some(listed) => response.into_value().or_else(|| listed.empty_value()),
+        };
+
+        value.ok_or_else(|| Error::InvalidRequest {
+            message: format!(
+                "Property {} of page {} has no items",
+                property_id.as_id(),
+                page_id.as_id()
+            ),
+        })
+    }
+
+    /// Fetches every item of a page property, along with the description of the property
+    /// that paginated responses include.
+    async fn page_property_items(
+        &self,
+        page_id: &PageId,
+        property_id: &PropertyId,
+    ) -> Result<(PropertyResponse, Option<ListedProperty>), Error> {
+        let url = format!(
+            "{}/v1/pages/{}/properties/{}",
+            self.base_url, page_id, property_id
+        );
+        let mut items = vec![];
+        let mut listed = None;
+        let mut cursor: Option<PagingCursor> = None;
+
+        loop {
+            let mut request = self.client.get(url.as_str());
+            if let Some(cursor) = cursor.take() {
+                request = request.query(&[("start_cursor", cursor.0)]);
+            }
+
+            // Only the first response may be a single, unpaginated property item.
+            let first = items.is_empty();
+            let response = self.make_json_request(request).await?;
+            if listed.is_none() {
+                listed = serde_json::from_str::<PropertyList>(&response.json)
+                    .ok()
+                    .and_then(|list| list.property_item);
+            }
+            let page = response.expect(|object| match object {
+                Object::PropertyItem { property_item } if first => {
+                    Ok(ControlFlow::Break(property_item))
+                }
+                Object::List { list } => list.expect_property_items().map(ControlFlow::Continue),
+                response => Err(response),
+            })?;
+
+            match page {
+                ControlFlow::Break(property_item) => {
+                    return Ok((PropertyResponse::PropertyItem(property_item), None));
+                }
+                ControlFlow::Continue(mut list) => {
+                    items.append(&mut list.results);
+                    cursor = list.next_cursor.filter(|_| list.has_more);
+                    if cursor.is_none() {
+                        return Ok((PropertyResponse::List(items), listed));
+                    }
+                }
+            }
         }
     }
 
@@ -293,35 +1034,100 @@ impl NotionApi {
         let result = self
             .make_json_request(
                 self.client
-                    .post(&format!(
-                        "https://api.notion.com/v1/databases/{database_id}/query",
+                    .post(format!(
+                        "{base_url}/v1/databases/{database_id}/query",
+                        base_url = self.base_url,
                         database_id = database.as_id()
                     ))
                     .json(&query.into()),
             )
             .await?;
-        match result {
-            Object::List { list } => Ok(list.expect_pages()?),
-            response => Err(Error::UnexpectedResponse { response }),
-        }
+        result.expect(|object| match object {
+            Object::List { list } => list.expect_pages(),
+            response => Err(response),
+        })
+    }
+
+    /// Query a database and stream every matching page,
+    /// transparently following `next_cursor` until all results have been returned.
+    ///
+    /// The sorts, filter, and page size of `query` are kept for every request.
+    /// If a request fails the error is yielded and the stream ends.
+    pub fn query_database_all<D, T>(
+        &self,
+        database: D,
+        query: T,
+    ) -> impl Stream<Item = Result<Page, Error>> + '_
+    where
+        T: Into<DatabaseQuery>,
+        D: AsIdentifier<DatabaseId>,
+    {
+        let database_id = database.as_id().clone();
+        paginate(query.into(), move |query| {
+            let database_id = database_id.clone();
+            async move { self.query_database(&database_id, query).await }
+        })
+    }
+
+    /// Query a database and convert the properties of each page into a `T`.
+    ///
+    /// Fails with [Error::PropertiesConversion] for the first page that can't be converted.
+    /// The row type can be given on its own, as in `query_database_into::<Task>(database, query)`.
+    pub async fn query_database_into<R: FromProperties>(
+        &self,
+        database: impl AsIdentifier<DatabaseId>,
+        query: impl Into<DatabaseQuery>,
+    ) -> Result<ListResponse<R>, Error> {
+        let pages = self.query_database(database, query).await?;
+        let results = pages
+            .results
+            .iter()
+            .map(|page| {
+                R::from_properties(&page.properties).map_err(|source| Error::PropertiesConversion {
+                    page_id: page.id.clone(),
+                    source,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(ListResponse {
+            results,
+            next_cursor: pages.next_cursor,
+            has_more: pages.has_more,
+        })
     }
 
     /// Get a block by [BlockId].
     pub async fn get_block<T: AsIdentifier<BlockId>>(
         &self,
-        page_id: T,
+        block_id: T,
     ) -> Result<Block, Error> {
         let result = self
             .make_json_request(self.client.get(format!(
-                "https://api.notion.com/v1/blocks/{}",
-                page_id.as_id()
+                "{}/v1/blocks/{}",
+                self.base_url,
+                block_id.as_id()
             )))
             .await?;
 
-        match result {
+        result.expect(|object| match object {
             Object::Block { block } => Ok(block),
-            response => Err(Error::UnexpectedResponse { response }),
-        }
+            response => Err(response),
+        })
+    }
+
+    /// Re-fetches a media block to get a fresh url of its file.
+    ///
+    /// Urls of files hosted by Notion expire after an hour, see [FileObject::is_expired].
+    /// Returns `None` if the block isn't an image, video, file or pdf.
+    ///
+    /// [FileObject::is_expired]: models::block::FileObject::is_expired
+    pub async fn refresh_file_url<T: AsIdentifier<BlockId>>(
+        &self,
+        block_id: T,
+    ) -> Result<Option<String>, Error> {
+        let block = self.get_block(block_id).await?;
+        Ok(block.media().map(|media| media.file.url().to_string()))
     }
 
     /// Get block children a block by [BlockId].
@@ -330,16 +1136,17 @@ impl NotionApi {
         block_id: T,
     ) -> Result<ListResponse<Block>, Error> {
         let result = self
-            .make_json_request(self.client.get(&format!(
-                "https://api.notion.com/v1/blocks/{block_id}/children",
+            .make_json_request(self.client.get(format!(
+                "{base_url}/v1/blocks/{block_id}/children",
+                base_url = self.base_url,
                 block_id = block_id.as_id()
             )))
             .await?;
 
-        match result {
-            Object::List { list } => Ok(list.expect_blocks()?),
-            response => Err(Error::UnexpectedResponse { response }),
-        }
+        result.expect(|object| match object {
+            Object::List { list } => list.expect_blocks(),
+            response => Err(response),
+        })
     }
 
     /// Get block children a block by [BlockId].
@@ -351,21 +1158,256 @@ impl NotionApi {
         let result = self
             .make_json_request(
                 self.client
-                    .get(&format!(
-                        "https://api.notion.com/v1/blocks/{block_id}/children",
+                    .get(format!(
+                        "{base_url}/v1/blocks/{block_id}/children",
+                        base_url = self.base_url,
                         block_id = block_id.as_id()
                     ))
                     .query(&[("start_cursor", cursor.0)]),
             )
             .await?;
 
-        match result {
-            Object::List { list } => Ok(list.expect_blocks()?),
-            response => Err(Error::UnexpectedResponse { response }),
+        result.expect(|object| match object {
+            Object::List { list } => list.expect_blocks(),
+            response => Err(response),
+        })
+    }
+
+    /// Get all children of a block by [BlockId], recursively populating the
+    /// children of every nested block that [has children](Block::has_children).
+    ///
+    /// Every level is paginated internally. `max_depth` is the number of levels
+    /// fetched, so `1` returns the same blocks as [get_block_children()](Self::get_block_children()).
+    /// Child pages and databases are not descended into.
+    pub async fn get_block_children_recursive<T: AsIdentifier<BlockId>>(
+        &self,
+        block_id: T,
+        max_depth: usize,
+    ) -> Result<Vec<Block>, Error> {
+        self.get_block_tree(block_id.as_id().clone(), max_depth)
+            .await
+    }
+
+    fn get_block_tree(
+        &self,
+        block_id: BlockId,
+        depth: usize,
+    ) -> BoxFuture<'_, Result<Vec<Block>, Error>> {
+        async move {
+            if depth == 0 {
+                return Ok(vec![]);
+            }
+
+            let mut list = self.get_block_children(&block_id).await?;
+            let mut blocks = std::mem::take(&mut list.results);
+            while let (true, Some(cursor)) = (list.has_more, list.next_cursor.take()) {
+                list = self
+                    .get_block_children_with_cursor(&block_id, cursor)
+                    .await?;
+                blocks.append(&mut list.results);
+            }
+
+            for block in blocks.iter_mut() {
+                if depth == 1 || !block.has_children() {
+                    continue;
+                }
+                let child_id = block.as_id().clone();
+                if let Some(children) = block.children_mut() {
+                    *children = Some(self.get_block_tree(child_id, depth - 1).await?);
+                }
+            }
+
+            Ok(blocks)
         }
+        .boxed()
     }
 
-    /// Append block children under a block by [BlockId].
+    /// Visits every descendant of `root` depth first, fetching children as they're needed
+    /// instead of collecting the whole tree.
+    ///
+    /// `visitor` receives each block along with its depth, 0 for the children of `root`,
+    /// and decides whether to descend into the block's children.
+    /// Like [get_block_children_recursive()](Self::get_block_children_recursive()),
+    /// child pages and databases are visited but not descended into.
+    pub async fn walk_blocks<T: AsIdentifier<BlockId>>(
+        &self,
+        root: T,
+        mut visitor: impl FnMut(&Block, usize) -> WalkControl + Send,
+    ) -> Result<(), Error> {
+        // Whether the visitor stopped the walk early makes no difference to the caller.
+        let _ = self
+            .walk_children(root.as_id().clone(), 0, &mut visitor)
+            .await?;
+        Ok(())
+    }
+
+    /// Returns [ControlFlow::Break] once the visitor stopped the walk.
+    fn walk_children<'a>(
+        &'a self,
+        block_id: BlockId,
+        depth: usize,
+        visitor: &'a mut (dyn FnMut(&Block, usize) -> WalkControl + Send),
+    ) -> BoxFuture<'a, Result<ControlFlow<()>, Error>> {
+        async move {
+            let mut list = self.get_block_children(&block_id).await?;
+            loop {
+                for block in &list.results {
+                    match visitor(block, depth) {
+                        WalkControl::Stop => return Ok(ControlFlow::Break(())),
+                        WalkControl::SkipChildren => {}
+                        WalkControl::Continue
+                            if block.has_children()
+                                && block.as_page_id().is_none()
+                                && block.as_database_id().is_none() =>
+                        {
+                            let child_id = block.as_id().clone();
+                            if self
+                                .walk_children(child_id, depth + 1, &mut *visitor)
+                                .await?
+                                .is_break()
+                            {
+                                return Ok(ControlFlow::Break(()));
+                            }
+                        }
+                        WalkControl::Continue => {}
+                    }
+                }
+
+                match (list.has_more, list.next_cursor.take()) {
+                    (true, Some(cursor)) => {
+                        list = self
+                            .get_block_children_with_cursor(&block_id, cursor)
+                            .await?;
+                    }
+                    _ => return Ok(ControlFlow::Continue(())),
+                }
+            }
+        }
+        .boxed()
+    }
+
+    /// Copies the block `source` and all of its descendants to the end of `target_parent`,
+    /// returning the created copy of `source`.
+    ///
+    /// Blocks without a [Block::to_create_block] equivalent are skipped along with their children.
+    /// Duplicates of synced blocks are copied as another duplicate of the same original.
+    pub async fn duplicate_block_into<S, T>(
+        &self,
+        source: S,
+        target_parent: T,
+    ) -> Result<Vec<Block>, Error>
+    where
+        S: AsIdentifier<BlockId>,
+        T: AsIdentifier<BlockId>,
+    {
+        let mut block = self.get_block(source).await?;
+        let is_synced_duplicate = matches!(
+            &block,
+            Block::SyncedBlock { synced_block, .. } if !synced_block.is_original()
+        );
+        if block.has_children() && !is_synced_duplicate {
+            let children = self
+                .get_block_children_recursive(block.as_id().clone(), usize::MAX)
+                .await?;
+            if let Some(slot) = block.children_mut() {
+                *slot = Some(children);
+            }
+        }
+
+        self.create_block_tree(target_parent.as_id().clone(), vec![block])
+            .await
+    }
+
+    /// Creates `blocks` under `parent` one level at a time,
+    /// as the API only accepts a limited depth of nested children per request.
+    fn create_block_tree(
+        &self,
+        parent: BlockId,
+        blocks: Vec<Block>,
+    ) -> BoxFuture<'_, Result<Vec<Block>, Error>> {
+        async move {
+            let (requests, originals): (Vec<_>, Vec<_>) = blocks
+                .into_iter()
+                .filter_map(|block| Some((block.to_create_block()?, block)))
+                .unzip();
+
+            let mut created = vec![];
+            for chunk in requests.chunks(MAX_BLOCKS_PER_APPEND) {
+                let mut list = self.append_block_children(&parent, chunk.to_vec()).await?;
+                created.append(&mut list.results);
+            }
+
+            for (block, original) in created.iter().zip(originals) {
+                self.create_descendants(block, original).await?;
+            }
+
+            Ok(created)
+        }
+        .boxed()
+    }
+
+    /// Creates the children of `original` under its copy `created`,
+    /// unless they were already part of the request creating it.
+    fn create_descendants<'a>(
+        &'a self,
+        created: &'a Block,
+        original: Block,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        async move {
+            match original {
+                // table rows are already part of the request and the children of
+                // a synced block duplicate mirror its original
+                Block::Table { .. } => {}
+                Block::SyncedBlock { synced_block, .. } if !synced_block.is_original() => {}
+                // so are the columns and their content, which leaves the children of the content
+                Block::ColumnList { column_list, .. } => {
+                    let columns = column_list.children.unwrap_or_default();
+                    let has_descendants = columns
+                        .iter()
+                        .flat_map(|column| column.children().unwrap_or_default())
+                        .any(|content| content.children().is_some_and(|c| !c.is_empty()));
+                    if !has_descendants {
+                        return Ok(());
+                    }
+
+                    let created_columns = self.get_block_tree(created.as_id().clone(), 2).await?;
+                    for (created_column, mut column) in created_columns.iter().zip(columns) {
+                        let content = column
+                            .children_mut()
+                            .and_then(Option::take)
+                            .unwrap_or_default()
+                            .into_iter()
+                            .filter(|content| content.to_create_block().is_some());
+                        for (created_content, content) in created_column
+                            .children()
+                            .unwrap_or_default()
+                            .iter()
+                            .zip(content)
+                        {
+                            self.create_descendants(created_content, content).await?;
+                        }
+                    }
+                }
+                mut block => {
+                    if let Some(children) = block
+                        .children_mut()
+                        .and_then(Option::take)
+                        .filter(|c| !c.is_empty())
+                    {
+                        self.create_block_tree(created.as_id().clone(), children)
+                            .await?;
+                    }
+                }
+            }
+            Ok(())
+        }
+        .boxed()
+    }
+
+    /// Append block children under a block by [BlockId] and return the created blocks.
+    ///
+    /// Pages are blocks too, so a [PageId] can be used as parent by converting it
+    /// with [`BlockId::from`].
     pub async fn append_block_children<P, T>(
         &self,
         block_id: P,
@@ -378,36 +1420,41 @@ impl NotionApi {
         let result = self
             .make_json_request(
                 self.client
-                    .patch(&format!(
-                        "https://api.notion.com/v1/blocks/{block_id}/children",
+                    .patch(format!(
+                        "{base_url}/v1/blocks/{block_id}/children",
+                        base_url = self.base_url,
                         block_id = block_id.as_id()
                     ))
                     .json(&request.into()),
             )
             .await?;
 
-        match result {
-            Object::List { list } => Ok(list.expect_blocks()?),
-            response => Err(Error::UnexpectedResponse { response }),
-        }
+        result.expect(|object| match object {
+            Object::List { list } => list.expect_blocks(),
+            response => Err(response),
+        })
     }
 
     /// Delete a block by [BlockId].
+    ///
+    /// Notion doesn't remove the block, it moves it to the trash and returns it
+    /// with `archived: true`.
     pub async fn delete_block<T: AsIdentifier<BlockId>>(
         &self,
         block_id: T,
     ) -> Result<Block, Error> {
         let result = self
-            .make_json_request(self.client.delete(&format!(
-                "https://api.notion.com/v1/blocks/{block_id}",
+            .make_json_request(self.client.delete(format!(
+                "{base_url}/v1/blocks/{block_id}",
+                base_url = self.base_url,
                 block_id = block_id.as_id()
             )))
             .await?;
 
-        match result {
+        result.expect(|object| match object {
             Object::Block { block } => Ok(block),
-            response => Err(Error::UnexpectedResponse { response }),
-        }
+            response => Err(response),
+        })
     }
 
     /// Update a block by [BlockId].
@@ -418,26 +1465,145 @@ impl NotionApi {
     ) -> Result<Block, Error>
     where
         P: AsIdentifier<BlockId>,
-        T: Into<CreateBlock>,
+        T: Into<UpdateBlock>,
     {
-        // using CreateBlock is not perfect
-        // technically speaking you can update text on a todo block and not touch checked by not settings it
-        // but I don't want to create a new type for this
-        // or make checked optional in CreateBlock
         let result = self
             .make_json_request(
                 self.client
-                    .patch(&format!(
-                        "https://api.notion.com/v1/blocks/{block_id}",
+                    .patch(format!(
+                        "{base_url}/v1/blocks/{block_id}",
+                        base_url = self.base_url,
                         block_id = block_id.as_id()
                     ))
                     .json(&block.into()),
             )
             .await?;
 
-        match result {
+        result.expect(|object| match object {
             Object::Block { block } => Ok(block),
-            response => Err(Error::UnexpectedResponse { response }),
+            response => Err(response),
+        })
+    }
+
+    /// Retrieves the bot user of the api token, including who owns it.
+    /// Useful to check the token is valid on startup.
+    pub async fn get_me(&self) -> Result<User, Error> {
+        let result = self
+            .make_json_request(self.client.get(format!("{}/v1/users/me", self.base_url)))
+            .await?;
+
+        result.expect(|object| match object {
+            Object::User { user } => Ok(user),
+            response => Err(response),
+        })
+    }
+
+    /// List the users of the workspace, starting from `cursor` if set.
+    pub async fn list_users(
+        &self,
+        cursor: Option<PagingCursor>,
+    ) -> Result<ListResponse<User>, Error> {
+        let mut request = self.client.get(format!("{}/v1/users", self.base_url));
+        if let Some(cursor) = cursor {
+            request = request.query(&[("start_cursor", cursor.0)]);
         }
+        let result = self.make_json_request(request).await?;
+
+        result.expect(|object| match object {
+            Object::List { list } => list.expect_users(),
+            response => Err(response),
+        })
+    }
+
+    /// List all users of the workspace, following the cursor until the last page.
+    /// Bots, including the integration itself, are left out unless `include_bots` is set.
+    pub async fn list_all_users(
+        &self,
+        include_bots: bool,
+    ) -> Result<Vec<User>, Error> {
+        let mut users = vec![];
+        let mut cursor = None;
+        loop {
+            let response = self.list_users(cursor).await?;
+            users.extend(
+                response
+                    .results
+                    .into_iter()
+                    .filter(|user| include_bots || !matches!(user, User::Bot { .. })),
+            );
+            match response.next_cursor {
+                Some(next) if response.has_more => cursor = Some(next),
+                _ => return Ok(users),
+            }
+        }
+    }
+
+    /// List the unresolved comments on a page or block by [BlockId].
+    /// Pages can be passed by converting their id with `BlockId::from(page_id)`.
+    pub async fn list_comments<T: AsIdentifier<BlockId>>(
+        &self,
+        block_id: T,
+    ) -> Result<ListResponse<Comment>, Error> {
+        let result = self
+            .make_json_request(
+                self.client
+                    .get(format!("{}/v1/comments", self.base_url))
+                    .query(&[("block_id", block_id.as_id().value())]),
+            )
+            .await?;
+
+        result.expect(|object| match object {
+            Object::List { list } => list.expect_comments(),
+            response => Err(response),
+        })
+    }
+
+    /// Add a comment to a page, or reply to an existing discussion.
+    pub async fn create_comment(
+        &self,
+        request: CreateCommentRequest,
+    ) -> Result<Comment, Error> {
+        let result = self
+            .make_json_request(
+                self.client
+                    .post(format!("{}/v1/comments", self.base_url))
+                    .json(&request),
+            )
+            .await?;
+
+        result.expect(|object| match object {
+            Object::Comment { comment } => Ok(comment),
+            response => Err(response),
+        })
     }
 }
+
+/// Repeatedly calls `fetch`, starting each request from the previous response's cursor,
+/// and flattens the results into a single stream.
+fn paginate<'a, Q, T, F, Fut>(
+    query: Q,
+    fetch: F,
+) -> impl Stream<Item = Result<T, Error>> + 'a
+where
+    Q: Pageable + Clone + 'a,
+    T: 'a,
+    F: FnMut(Q) -> Fut + 'a,
+    Fut: Future<Output = Result<ListResponse<T>, Error>> + 'a,
+{
+    stream::try_unfold((Some(query), fetch), |(query, mut fetch)| async move {
+        let query = match query {
+            Some(query) => query,
+            None => return Ok::<_, Error>(None),
+        };
+
+        let response = fetch(query.clone()).await?;
+        let next_query = match response.next_cursor {
+            Some(cursor) if response.has_more => Some(query.start_from(Some(cursor))),
+            _ => None,
+        };
+        let results = stream::iter(response.results.into_iter().map(Ok));
+
+        Ok(Some((results, (next_query, fetch))))
+    })
+    .try_flatten()
+}
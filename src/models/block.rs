@@ -2,10 +2,11 @@ use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
 use crate::ids::{AsIdentifier, BlockId, DatabaseId, PageId};
-use crate::models::text::{RichText, TextColor};
+use crate::models::text::{IntoRichText, RichText, TextColor};
 use crate::models::users::UserCommon;
 use std::hash::{Hash, Hasher};
 
+#[cfg(test)]
 mod tests;
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
@@ -18,6 +19,12 @@ pub struct BlockCommon {
     pub has_children: bool,
     pub created_by: UserCommon,
     pub last_edited_by: UserCommon,
+    /// Whether the block has been archived, older API versions use this for trashed blocks too.
+    #[serde(default)]
+    pub archived: bool,
+    /// Whether the block has been moved to the trash, only sent by newer API versions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_trash: Option<bool>,
 }
 
 impl Hash for BlockCommon {
@@ -29,17 +36,79 @@ impl Hash for BlockCommon {
     }
 }
 
+/// The content of paragraphs, quotes, list items and toggles.
+///
+/// `B` is the type of the children, [Block] when reading a block and [CreateBlock]
+/// when creating one together with its children.
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
-pub struct TextAndChildren {
+pub struct TextAndChildren<B = Block> {
     pub rich_text: Vec<RichText>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub children: Option<Vec<Block>>,
+    pub children: Option<Vec<B>>,
     pub color: TextColor,
 }
 
+impl From<TextAndChildren> for TextAndChildren<CreateBlock> {
+    fn from(fields: TextAndChildren) -> Self {
+        TextAndChildren {
+            rich_text: fields.rich_text,
+            children: create_children(fields.children),
+            color: fields.color,
+        }
+    }
+}
+
+/// Converts read children into requests to create them again, see [CreateBlock::from].
+fn create_children(children: Option<Vec<Block>>) -> Option<Vec<CreateBlock>> {
+    children.map(|blocks| blocks.into_iter().map(CreateBlock::from).collect())
+}
+
+/// The content of a heading, only toggleable headings can have children.
+///
+/// `B` is the type of the children, [Block] when reading a heading and [CreateBlock]
+/// when creating a toggleable heading together with its content.
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
-pub struct Text {
+pub struct HeadingFields<B = Block> {
     pub rich_text: Vec<RichText>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<TextColor>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_toggleable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<B>>,
+}
+
+impl<B> HeadingFields<B> {
+    fn new<T: IntoRichText>(text: T) -> Self {
+        HeadingFields {
+            rich_text: text.into_rich_text(),
+            color: None,
+            is_toggleable: false,
+            children: None,
+        }
+    }
+
+    fn toggleable<T: IntoRichText>(
+        text: T,
+        children: Vec<B>,
+    ) -> Self {
+        HeadingFields {
+            is_toggleable: true,
+            children: Some(children),
+            ..HeadingFields::new(text)
+        }
+    }
+}
+
+impl From<HeadingFields> for HeadingFields<CreateBlock> {
+    fn from(heading: HeadingFields) -> Self {
+        HeadingFields {
+            rich_text: heading.rich_text,
+            color: heading.color,
+            is_toggleable: heading.is_toggleable,
+            children: create_children(heading.children),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
@@ -59,34 +128,129 @@ pub struct ExternalFileObject {
 #[serde(rename_all = "snake_case")]
 pub enum FileOrEmojiObject {
     Emoji { emoji: String },
-    File,
-    External,
+    File { file: InternalFileObject },
+    External { external: ExternalFileObject },
+}
+
+impl FileOrEmojiObject {
+    pub fn emoji<T: Into<String>>(emoji: T) -> Self {
+        FileOrEmojiObject::Emoji {
+            emoji: emoji.into(),
+        }
+    }
+
+    /// An image hosted outside of Notion, the only kind of file icon that can be set
+    /// through the API.
+    pub fn external<T: Into<String>>(url: T) -> Self {
+        FileOrEmojiObject::External {
+            external: ExternalFileObject { url: url.into() },
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
 #[serde(tag = "type")]
 #[serde(rename_all = "snake_case")]
 pub enum FileObject {
-    File,
-    External,
+    /// A file uploaded to Notion, the url is only valid until `expiry_time`.
+    File {
+        file: InternalFileObject,
+    },
+    External {
+        external: ExternalFileObject,
+    },
 }
 
+impl FileObject {
+    /// A file hosted outside of Notion, the only kind that can be set through the API.
+    pub fn external<T: Into<String>>(url: T) -> Self {
+        FileObject::External {
+            external: ExternalFileObject { url: url.into() },
+        }
+    }
+
+    /// Whether the url of a file hosted by Notion stopped working at `now`.
+    /// External files never expire.
+    pub fn is_expired(
+        &self,
+        now: OffsetDateTime,
+    ) -> bool {
+        match self {
+            FileObject::File { file } => now >= file.expiry_time,
+            FileObject::External { .. } => false,
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        match self {
+            FileObject::File { file } => &file.url,
+            FileObject::External { external } => &external.url,
+        }
+    }
+}
+
+/// The content of image, video, file and pdf blocks.
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
-pub struct Callout {
+pub struct MediaBlock {
+    #[serde(flatten)]
+    pub file: FileObject,
+    #[serde(default)]
+    pub caption: Vec<RichText>,
+    /// The name of the file, only used by file blocks.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+impl MediaBlock {
+    pub fn external<T: Into<String>>(url: T) -> Self {
+        MediaBlock {
+            file: FileObject::external(url),
+            caption: vec![],
+            name: None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct Callout<B = Block> {
     pub rich_text: Vec<RichText>,
     pub icon: FileOrEmojiObject,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<B>>,
     pub color: TextColor,
 }
 
+impl From<Callout> for Callout<CreateBlock> {
+    fn from(callout: Callout) -> Self {
+        Callout {
+            rich_text: callout.rich_text,
+            icon: callout.icon,
+            children: create_children(callout.children),
+            color: callout.color,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
-pub struct ToDoFields {
+pub struct ToDoFields<B = Block> {
     pub rich_text: Vec<RichText>,
     pub checked: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub children: Option<Vec<Block>>,
+    pub children: Option<Vec<B>>,
     pub color: TextColor,
 }
 
+impl From<ToDoFields> for ToDoFields<CreateBlock> {
+    fn from(to_do: ToDoFields) -> Self {
+        ToDoFields {
+            rich_text: to_do.rich_text,
+            checked: to_do.checked,
+            children: create_children(to_do.children),
+            color: to_do.color,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
 pub struct ChildPageFields {
     pub title: String,
@@ -210,14 +374,17 @@ pub struct TableOfContents {
     pub color: TextColor,
 }
 
+/// Like [TableFields], `B` is [CreateBlock] when creating a column list together with its columns.
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
-pub struct ColumnListFields {
-    pub children: Option<Vec<Block>>,
+pub struct ColumnListFields<B = Block> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<B>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
-pub struct ColumnFields {
-    pub children: Option<Vec<Block>>,
+pub struct ColumnFields<B = Block> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<B>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
@@ -239,28 +406,79 @@ pub enum LinkToPageFields {
     DatabaseId { database_id: DatabaseId },
 }
 
+/// The `{}` body of blocks without any configuration, like dividers.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Default, Clone)]
+pub struct EmptyObject {}
+
+/// The original block a duplicate synced block mirrors.
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
-pub struct SyncedFromObject {
-    pub block_id: BlockId,
+#[serde(tag = "type")]
+#[serde(rename_all = "snake_case")]
+pub enum SyncedFrom {
+    BlockId { block_id: BlockId },
 }
 
+/// See <https://developers.notion.com/reference/block#synced-block>
+///
+/// `B` is the type of the children, [CreateBlock] when creating an original synced block
+/// together with its content.
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
-pub struct SyncedBlockFields {
-    pub synced_from: Option<SyncedFromObject>,
-    pub children: Option<Vec<Block>>,
+pub struct SyncedBlockFields<B = Block> {
+    /// `None` for the original synced block, which holds the synced content as its children.
+    /// Duplicates reference the original here and their children mirror its children.
+    pub synced_from: Option<SyncedFrom>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<B>>,
 }
 
+impl From<SyncedBlockFields> for SyncedBlockFields<CreateBlock> {
+    /// The children of a duplicate are left out, they mirror the original
+    /// and can't be appended to the duplicate.
+    fn from(synced_block: SyncedBlockFields) -> Self {
+        let children = if synced_block.is_original() {
+            create_children(synced_block.children)
+        } else {
+            None
+        };
+        SyncedBlockFields {
+            synced_from: synced_block.synced_from,
+            children,
+        }
+    }
+}
+
+impl<B> SyncedBlockFields<B> {
+    pub fn is_original(&self) -> bool {
+        self.synced_from.is_none()
+    }
+
+    /// The id of the block holding the synced content, if this is a duplicate.
+    pub fn original_block_id(&self) -> Option<&BlockId> {
+        match &self.synced_from {
+            Some(SyncedFrom::BlockId { block_id }) => Some(block_id),
+            None => None,
+        }
+    }
+}
+
+/// A table, its rows are [table_row](Block::TableRow) children.
+///
+/// `B` is the type of the rows, [Block] when reading a table and [CreateBlock]
+/// when creating one, as tables have to be created together with their rows.
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
-pub struct TableFields {
+pub struct TableFields<B = Block> {
+    /// The number of columns, can't be changed after the table has been created.
     pub table_width: u64,
     pub has_column_header: bool,
     pub has_row_header: bool,
-    pub children: Option<Vec<Block>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<B>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
 pub struct TableRowFields {
-    pub cells: Vec<RichText>,
+    /// The rich text of each cell in the row.
+    pub cells: Vec<Vec<RichText>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
@@ -276,19 +494,19 @@ pub enum Block {
     Heading1 {
         #[serde(flatten)]
         common: BlockCommon,
-        heading_1: Text,
+        heading_1: HeadingFields,
     },
     #[serde(rename = "heading_2")]
     Heading2 {
         #[serde(flatten)]
         common: BlockCommon,
-        heading_2: Text,
+        heading_2: HeadingFields,
     },
     #[serde(rename = "heading_3")]
     Heading3 {
         #[serde(flatten)]
         common: BlockCommon,
-        heading_3: Text,
+        heading_3: HeadingFields,
     },
     Callout {
         #[serde(flatten)]
@@ -343,23 +561,22 @@ pub enum Block {
     Image {
         #[serde(flatten)]
         common: BlockCommon,
-        image: FileObject,
+        image: MediaBlock,
     },
     Video {
         #[serde(flatten)]
         common: BlockCommon,
-        video: FileObject,
+        video: MediaBlock,
     },
     File {
         #[serde(flatten)]
         common: BlockCommon,
-        file: FileObject,
-        caption: Text,
+        file: MediaBlock,
     },
     Pdf {
         #[serde(flatten)]
         common: BlockCommon,
-        pdf: FileObject,
+        pdf: MediaBlock,
     },
     Bookmark {
         #[serde(flatten)]
@@ -443,6 +660,19 @@ impl Hash for Block {
 
 impl AsIdentifier<BlockId> for Block {
     fn as_id(&self) -> &BlockId {
+        match self.common() {
+            Some(common) => &common.id,
+            None => panic!("Trying to reference identifier for unknown block!"),
+        }
+    }
+}
+
+impl Block {
+    pub fn has_children(&self) -> bool {
+        self.common().is_some_and(|common| common.has_children)
+    }
+
+    fn common(&self) -> Option<&BlockCommon> {
         use Block::*;
         match self {
             Paragraph { common, .. }
@@ -476,9 +706,283 @@ impl AsIdentifier<BlockId> for Block {
             | SyncedBlock { common, .. }
             | Table { common, .. }
             | TableRow { common, .. }
-            | Unsupported { common, .. } => &common.id,
-            Unknown => {
-                panic!("Trying to reference identifier for unknown block!")
+            | Unsupported { common, .. } => Some(common),
+            Unknown => None,
+        }
+    }
+
+    /// The text of blocks that contain text, like paragraphs, headings and list items.
+    pub fn rich_text(&self) -> Option<&[RichText]> {
+        use Block::*;
+        let rich_text = match self {
+            Paragraph {
+                paragraph: fields, ..
+            }
+            | Quote { quote: fields, .. }
+            | BulletedListItem {
+                bulleted_list_item: fields,
+                ..
+            }
+            | NumberedListItem {
+                numbered_list_item: fields,
+                ..
+            }
+            | Toggle { toggle: fields, .. } => &fields.rich_text,
+            Heading1 {
+                heading_1: text, ..
+            }
+            | Heading2 {
+                heading_2: text, ..
+            }
+            | Heading3 {
+                heading_3: text, ..
+            } => &text.rich_text,
+            Callout { callout, .. } => &callout.rich_text,
+            ToDo { to_do, .. } => &to_do.rich_text,
+            Code { code, .. } => &code.rich_text,
+            Template { template, .. } => &template.rich_text,
+            _ => return None,
+        };
+        Some(rich_text)
+    }
+
+    /// The color of blocks that can be colored, like paragraphs, headings, callouts and list items.
+    /// Headings without a color are shown in the default color.
+    pub fn color(&self) -> Option<TextColor> {
+        use Block::*;
+        match self {
+            Paragraph {
+                paragraph: fields, ..
+            }
+            | Quote { quote: fields, .. }
+            | BulletedListItem {
+                bulleted_list_item: fields,
+                ..
+            }
+            | NumberedListItem {
+                numbered_list_item: fields,
+                ..
+            }
+            | Toggle { toggle: fields, .. } => Some(fields.color),
+            Heading1 {
+                heading_1: heading, ..
+            }
+            | Heading2 {
+                heading_2: heading, ..
+            }
+            | Heading3 {
+                heading_3: heading, ..
+            } => Some(heading.color.unwrap_or_default()),
+            Callout { callout, .. } => Some(callout.color),
+            ToDo { to_do, .. } => Some(to_do.color),
+            TableOfContents {
+                table_of_contents, ..
+            } => Some(table_of_contents.color),
+            _ => None,
+        }
+    }
+
+    /// Changes the color of blocks that can be colored, see [Block::color].
+    /// Returns `false` and leaves the block untouched if it can't be colored.
+    pub fn set_color(
+        &mut self,
+        color: TextColor,
+    ) -> bool {
+        use Block::*;
+        let slot = match self {
+            Paragraph {
+                paragraph: fields, ..
+            }
+            | Quote { quote: fields, .. }
+            | BulletedListItem {
+                bulleted_list_item: fields,
+                ..
+            }
+            | NumberedListItem {
+                numbered_list_item: fields,
+                ..
+            }
+            | Toggle { toggle: fields, .. } => &mut fields.color,
+            Heading1 {
+                heading_1: heading, ..
+            }
+            | Heading2 {
+                heading_2: heading, ..
+            }
+            | Heading3 {
+                heading_3: heading, ..
+            } => {
+                heading.color = Some(color);
+                return true;
+            }
+            Callout { callout, .. } => &mut callout.color,
+            ToDo { to_do, .. } => &mut to_do.color,
+            TableOfContents {
+                table_of_contents, ..
+            } => &mut table_of_contents.color,
+            _ => return false,
+        };
+        *slot = color;
+        true
+    }
+
+    /// The file of image, video, file and pdf blocks.
+    pub fn media(&self) -> Option<&MediaBlock> {
+        match self {
+            Block::Image { image: media, .. }
+            | Block::Video { video: media, .. }
+            | Block::File { file: media, .. }
+            | Block::Pdf { pdf: media, .. } => Some(media),
+            _ => None,
+        }
+    }
+
+    /// The id of the subpage a `child_page` block stands for.
+    pub fn as_page_id(&self) -> Option<PageId> {
+        match self {
+            Block::ChildPage { common, .. } => Some(common.id.to_page_id()),
+            _ => None,
+        }
+    }
+
+    /// The id of the inline or full page database a `child_database` block stands for.
+    pub fn as_database_id(&self) -> Option<DatabaseId> {
+        match self {
+            Block::ChildDatabase { common, .. } => Some(common.id.to_database_id()),
+            _ => None,
+        }
+    }
+
+    /// The nested children of this block, if they have been populated.
+    ///
+    /// Notion never includes children when listing blocks, see
+    /// [`NotionApi::get_block_children_recursive`](crate::NotionApi::get_block_children_recursive).
+    pub fn children(&self) -> Option<&[Block]> {
+        use Block::*;
+        let children = match self {
+            Paragraph {
+                paragraph: fields, ..
+            }
+            | Quote { quote: fields, .. }
+            | BulletedListItem {
+                bulleted_list_item: fields,
+                ..
+            }
+            | NumberedListItem {
+                numbered_list_item: fields,
+                ..
+            }
+            | Toggle { toggle: fields, .. } => &fields.children,
+            Heading1 {
+                heading_1: heading, ..
+            }
+            | Heading2 {
+                heading_2: heading, ..
+            }
+            | Heading3 {
+                heading_3: heading, ..
+            } => &heading.children,
+            Callout { callout, .. } => &callout.children,
+            ToDo { to_do, .. } => &to_do.children,
+            ColumnList { column_list, .. } => &column_list.children,
+            Column { column, .. } => &column.children,
+            Template { template, .. } => &template.children,
+            SyncedBlock { synced_block, .. } => &synced_block.children,
+            Table { table, .. } => &table.children,
+            _ => return None,
+        };
+        children.as_deref()
+    }
+
+    /// The slot holding this block's children, or `None` if this kind of block can't contain any.
+    pub fn children_mut(&mut self) -> Option<&mut Option<Vec<Block>>> {
+        use Block::*;
+        match self {
+            Paragraph {
+                paragraph: fields, ..
+            }
+            | Quote { quote: fields, .. }
+            | BulletedListItem {
+                bulleted_list_item: fields,
+                ..
+            }
+            | NumberedListItem {
+                numbered_list_item: fields,
+                ..
+            }
+            | Toggle { toggle: fields, .. } => Some(&mut fields.children),
+            Heading1 {
+                heading_1: heading, ..
+            }
+            | Heading2 {
+                heading_2: heading, ..
+            }
+            | Heading3 {
+                heading_3: heading, ..
+            } if heading.is_toggleable => Some(&mut heading.children),
+            Callout { callout, .. } => Some(&mut callout.children),
+            ToDo { to_do, .. } => Some(&mut to_do.children),
+            ColumnList { column_list, .. } => Some(&mut column_list.children),
+            Column { column, .. } => Some(&mut column.children),
+            Template { template, .. } => Some(&mut template.children),
+            SyncedBlock { synced_block, .. } => Some(&mut synced_block.children),
+            Table { table, .. } => Some(&mut table.children),
+            _ => None,
+        }
+    }
+}
+
+impl Block {
+    /// The request to create a copy of this block, without its children.
+    ///
+    /// Tables and column lists are the exception, as they have to be created together
+    /// with their rows and with their columns and the content of those respectively.
+    /// They are only returned if these have been fetched, the content of the columns
+    /// doesn't include its own children though.
+    ///
+    /// Returns `None` for blocks that can't be created through the API, such as child pages
+    /// and databases, link previews, templates and unsupported blocks.
+    /// Single columns are skipped as well, as they can only be created as part of their list.
+    pub fn to_create_block(&self) -> Option<CreateBlock> {
+        match self {
+            Block::Table { table, .. } if table.children.is_none() => None,
+            Block::Table { .. } => Some(self.clone().into()),
+            Block::ColumnList { column_list, .. } => {
+                let columns = column_list
+                    .children
+                    .as_ref()?
+                    .iter()
+                    .map(|column| match column {
+                        Block::Column { column, .. } => Some(CreateBlock::column(
+                            column
+                                .children
+                                .as_ref()?
+                                .iter()
+                                .filter_map(Block::to_create_block)
+                                .collect(),
+                        )),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                Some(CreateBlock::ColumnList {
+                    column_list: ColumnListFields {
+                        children: Some(columns),
+                    },
+                })
+            }
+            Block::ChildPage { .. }
+            | Block::ChildDatabase { .. }
+            | Block::LinkPreview { .. }
+            | Block::Template { .. }
+            | Block::Column { .. }
+            | Block::Unsupported { .. }
+            | Block::Unknown => None,
+            block => {
+                let mut block = block.clone();
+                if let Some(children) = block.children_mut() {
+                    *children = None;
+                }
+                Some(block.into())
             }
         }
     }
@@ -487,20 +991,40 @@ impl AsIdentifier<BlockId> for Block {
 impl From<Block> for CreateBlock {
     fn from(val: Block) -> Self {
         match val {
-            Block::Paragraph { paragraph, .. } => CreateBlock::Paragraph { paragraph },
-            Block::Heading1 { heading_1, .. } => CreateBlock::Heading1 { heading_1 },
-            Block::Heading2 { heading_2, .. } => CreateBlock::Heading2 { heading_2 },
-            Block::Heading3 { heading_3, .. } => CreateBlock::Heading3 { heading_3 },
-            Block::Callout { callout, .. } => CreateBlock::Callout { callout },
-            Block::Quote { quote, .. } => CreateBlock::Quote { quote },
+            Block::Paragraph { paragraph, .. } => CreateBlock::Paragraph {
+                paragraph: paragraph.into(),
+            },
+            Block::Heading1 { heading_1, .. } => CreateBlock::Heading1 {
+                heading_1: heading_1.into(),
+            },
+            Block::Heading2 { heading_2, .. } => CreateBlock::Heading2 {
+                heading_2: heading_2.into(),
+            },
+            Block::Heading3 { heading_3, .. } => CreateBlock::Heading3 {
+                heading_3: heading_3.into(),
+            },
+            Block::Callout { callout, .. } => CreateBlock::Callout {
+                callout: callout.into(),
+            },
+            Block::Quote { quote, .. } => CreateBlock::Quote {
+                quote: quote.into(),
+            },
             Block::BulletedListItem {
                 bulleted_list_item, ..
-            } => CreateBlock::BulletedListItem { bulleted_list_item },
+            } => CreateBlock::BulletedListItem {
+                bulleted_list_item: bulleted_list_item.into(),
+            },
             Block::NumberedListItem {
                 numbered_list_item, ..
-            } => CreateBlock::NumberedListItem { numbered_list_item },
-            Block::ToDo { to_do, .. } => CreateBlock::ToDo { to_do },
-            Block::Toggle { toggle, .. } => CreateBlock::Toggle { toggle },
+            } => CreateBlock::NumberedListItem {
+                numbered_list_item: numbered_list_item.into(),
+            },
+            Block::ToDo { to_do, .. } => CreateBlock::ToDo {
+                to_do: to_do.into(),
+            },
+            Block::Toggle { toggle, .. } => CreateBlock::Toggle {
+                toggle: toggle.into(),
+            },
             Block::Code { code, .. } => CreateBlock::Code { code },
             Block::ChildPage { child_page, .. } => CreateBlock::ChildPage { child_page },
             Block::ChildDatabase { child_database, .. } => {
@@ -509,23 +1033,40 @@ impl From<Block> for CreateBlock {
             Block::Embed { embed, .. } => CreateBlock::Embed { embed },
             Block::Image { image, .. } => CreateBlock::Image { image },
             Block::Video { video, .. } => CreateBlock::Video { video },
-            Block::File { file, caption, .. } => CreateBlock::File { file, caption },
+            Block::File { file, .. } => CreateBlock::File { file },
             Block::Pdf { pdf, .. } => CreateBlock::Pdf { pdf },
             Block::Bookmark { bookmark, .. } => CreateBlock::Bookmark { bookmark },
             Block::Equation { equation, .. } => CreateBlock::Equation { equation },
-            Block::Divider { .. } => CreateBlock::Divider {},
+            Block::Divider { .. } => CreateBlock::divider(),
             Block::TableOfContents {
                 table_of_contents, ..
             } => CreateBlock::TableOfContents { table_of_contents },
-            Block::Breadcrumb { .. } => CreateBlock::Breadcrumb {},
-            Block::ColumnList { column_list, .. } => CreateBlock::ColumnList { column_list },
-            Block::Column { column, .. } => CreateBlock::Column { column },
+            Block::Breadcrumb { .. } => CreateBlock::breadcrumb(),
+            Block::ColumnList { column_list, .. } => CreateBlock::ColumnList {
+                column_list: ColumnListFields {
+                    children: create_children(column_list.children),
+                },
+            },
+            Block::Column { column, .. } => CreateBlock::Column {
+                column: ColumnFields {
+                    children: create_children(column.children),
+                },
+            },
 
             Block::LinkPreview { link_preview, .. } => CreateBlock::LinkPreview { link_preview },
             Block::Template { template, .. } => CreateBlock::Template { template },
             Block::LinkToPage { link_to_page, .. } => CreateBlock::LinkToPage { link_to_page },
-            Block::Table { table, .. } => CreateBlock::Table { table },
-            Block::SyncedBlock { synced_block, .. } => CreateBlock::SyncedBlock { synced_block },
+            Block::Table { table, .. } => CreateBlock::Table {
+                table: TableFields {
+                    table_width: table.table_width,
+                    has_column_header: table.has_column_header,
+                    has_row_header: table.has_row_header,
+                    children: create_children(table.children),
+                },
+            },
+            Block::SyncedBlock { synced_block, .. } => CreateBlock::SyncedBlock {
+                synced_block: synced_block.into(),
+            },
             Block::TableRow { table_row, .. } => CreateBlock::TableRow { table_row },
             Block::Unsupported { .. } => CreateBlock::Unsupported,
             Block::Unknown => CreateBlock::Unknown,
@@ -538,37 +1079,37 @@ impl From<Block> for CreateBlock {
 #[serde(rename_all = "snake_case")]
 pub enum CreateBlock {
     Paragraph {
-        paragraph: TextAndChildren,
+        paragraph: TextAndChildren<CreateBlock>,
     },
     #[serde(rename = "heading_1")]
     Heading1 {
-        heading_1: Text,
+        heading_1: HeadingFields<CreateBlock>,
     },
     #[serde(rename = "heading_2")]
     Heading2 {
-        heading_2: Text,
+        heading_2: HeadingFields<CreateBlock>,
     },
     #[serde(rename = "heading_3")]
     Heading3 {
-        heading_3: Text,
+        heading_3: HeadingFields<CreateBlock>,
     },
     Callout {
-        callout: Callout,
+        callout: Callout<CreateBlock>,
     },
     Quote {
-        quote: TextAndChildren,
+        quote: TextAndChildren<CreateBlock>,
     },
     BulletedListItem {
-        bulleted_list_item: TextAndChildren,
+        bulleted_list_item: TextAndChildren<CreateBlock>,
     },
     NumberedListItem {
-        numbered_list_item: TextAndChildren,
+        numbered_list_item: TextAndChildren<CreateBlock>,
     },
     ToDo {
-        to_do: ToDoFields,
+        to_do: ToDoFields<CreateBlock>,
     },
     Toggle {
-        toggle: TextAndChildren,
+        toggle: TextAndChildren<CreateBlock>,
     },
     Code {
         code: CodeFields,
@@ -583,17 +1124,16 @@ pub enum CreateBlock {
         embed: EmbedFields,
     },
     Image {
-        image: FileObject,
+        image: MediaBlock,
     },
     Video {
-        video: FileObject,
+        video: MediaBlock,
     },
     File {
-        file: FileObject,
-        caption: Text,
+        file: MediaBlock,
     },
     Pdf {
-        pdf: FileObject,
+        pdf: MediaBlock,
     },
     Bookmark {
         bookmark: BookmarkFields,
@@ -601,16 +1141,22 @@ pub enum CreateBlock {
     Equation {
         equation: Equation,
     },
-    Divider,
+    Divider {
+        #[serde(default)]
+        divider: EmptyObject,
+    },
     TableOfContents {
         table_of_contents: TableOfContents,
     },
-    Breadcrumb,
+    Breadcrumb {
+        #[serde(default)]
+        breadcrumb: EmptyObject,
+    },
     ColumnList {
-        column_list: ColumnListFields,
+        column_list: ColumnListFields<CreateBlock>,
     },
     Column {
-        column: ColumnFields,
+        column: ColumnFields<CreateBlock>,
     },
     LinkPreview {
         link_preview: LinkPreviewFields,
@@ -622,10 +1168,10 @@ pub enum CreateBlock {
         link_to_page: LinkToPageFields,
     },
     Table {
-        table: TableFields,
+        table: TableFields<CreateBlock>,
     },
     SyncedBlock {
-        synced_block: SyncedBlockFields,
+        synced_block: SyncedBlockFields<CreateBlock>,
     },
     TableRow {
         table_row: TableRowFields,
@@ -634,3 +1180,465 @@ pub enum CreateBlock {
     #[serde(other)]
     Unknown,
 }
+
+impl<B> TextAndChildren<B> {
+    fn new<T: IntoRichText>(text: T) -> Self {
+        TextAndChildren {
+            rich_text: text.into_rich_text(),
+            children: None,
+            color: TextColor::Default,
+        }
+    }
+}
+
+impl CreateBlock {
+    pub fn paragraph<T: IntoRichText>(text: T) -> Self {
+        CreateBlock::Paragraph {
+            paragraph: TextAndChildren::new(text),
+        }
+    }
+
+    pub fn heading_1<T: IntoRichText>(text: T) -> Self {
+        CreateBlock::Heading1 {
+            heading_1: HeadingFields::new(text),
+        }
+    }
+
+    pub fn heading_2<T: IntoRichText>(text: T) -> Self {
+        CreateBlock::Heading2 {
+            heading_2: HeadingFields::new(text),
+        }
+    }
+
+    pub fn heading_3<T: IntoRichText>(text: T) -> Self {
+        CreateBlock::Heading3 {
+            heading_3: HeadingFields::new(text),
+        }
+    }
+
+    /// A heading that can be collapsed like a toggle, hiding `children`.
+    pub fn toggleable_heading_1<T: IntoRichText>(
+        text: T,
+        children: Vec<CreateBlock>,
+    ) -> Self {
+        CreateBlock::Heading1 {
+            heading_1: HeadingFields::toggleable(text, children),
+        }
+    }
+
+    /// A heading that can be collapsed like a toggle, hiding `children`.
+    pub fn toggleable_heading_2<T: IntoRichText>(
+        text: T,
+        children: Vec<CreateBlock>,
+    ) -> Self {
+        CreateBlock::Heading2 {
+            heading_2: HeadingFields::toggleable(text, children),
+        }
+    }
+
+    /// A heading that can be collapsed like a toggle, hiding `children`.
+    pub fn toggleable_heading_3<T: IntoRichText>(
+        text: T,
+        children: Vec<CreateBlock>,
+    ) -> Self {
+        CreateBlock::Heading3 {
+            heading_3: HeadingFields::toggleable(text, children),
+        }
+    }
+
+    pub fn bulleted_list_item<T: IntoRichText>(text: T) -> Self {
+        CreateBlock::BulletedListItem {
+            bulleted_list_item: TextAndChildren::new(text),
+        }
+    }
+
+    pub fn numbered_list_item<T: IntoRichText>(text: T) -> Self {
+        CreateBlock::NumberedListItem {
+            numbered_list_item: TextAndChildren::new(text),
+        }
+    }
+
+    pub fn to_do<T: IntoRichText>(
+        text: T,
+        checked: bool,
+    ) -> Self {
+        CreateBlock::ToDo {
+            to_do: ToDoFields {
+                rich_text: text.into_rich_text(),
+                checked,
+                children: None,
+                color: TextColor::Default,
+            },
+        }
+    }
+
+    pub fn toggle<T: IntoRichText>(text: T) -> Self {
+        CreateBlock::Toggle {
+            toggle: TextAndChildren::new(text),
+        }
+    }
+
+    pub fn quote<T: IntoRichText>(text: T) -> Self {
+        CreateBlock::Quote {
+            quote: TextAndChildren::new(text),
+        }
+    }
+
+    /// A callout with an emoji or an external image as its icon,
+    /// see [FileOrEmojiObject::emoji] and [FileOrEmojiObject::external].
+    pub fn callout<T: IntoRichText>(
+        text: T,
+        icon: FileOrEmojiObject,
+        color: TextColor,
+    ) -> Self {
+        CreateBlock::Callout {
+            callout: Callout {
+                rich_text: text.into_rich_text(),
+                icon,
+                children: None,
+                color,
+            },
+        }
+    }
+
+    pub fn code<T: IntoRichText>(
+        text: T,
+        language: CodeLanguage,
+    ) -> Self {
+        CreateBlock::Code {
+            code: CodeFields {
+                rich_text: text.into_rich_text(),
+                caption: vec![],
+                language,
+            },
+        }
+    }
+
+    /// An image hosted at `url`.
+    pub fn image<S: Into<String>>(url: S) -> Self {
+        CreateBlock::Image {
+            image: MediaBlock::external(url),
+        }
+    }
+
+    /// A video hosted at `url`.
+    pub fn video<S: Into<String>>(url: S) -> Self {
+        CreateBlock::Video {
+            video: MediaBlock::external(url),
+        }
+    }
+
+    /// A file hosted at `url`.
+    pub fn file<S: Into<String>>(url: S) -> Self {
+        CreateBlock::File {
+            file: MediaBlock::external(url),
+        }
+    }
+
+    /// A pdf hosted at `url`.
+    pub fn pdf<S: Into<String>>(url: S) -> Self {
+        CreateBlock::Pdf {
+            pdf: MediaBlock::external(url),
+        }
+    }
+
+    pub fn equation<S: Into<String>>(expression: S) -> Self {
+        CreateBlock::Equation {
+            equation: Equation {
+                expression: expression.into(),
+            },
+        }
+    }
+
+    pub fn bookmark<S: Into<String>>(url: S) -> Self {
+        CreateBlock::Bookmark {
+            bookmark: BookmarkFields {
+                url: url.into(),
+                caption: vec![],
+            },
+        }
+    }
+
+    pub fn embed<S: Into<String>>(url: S) -> Self {
+        CreateBlock::Embed {
+            embed: EmbedFields { url: url.into() },
+        }
+    }
+
+    pub fn divider() -> Self {
+        CreateBlock::Divider {
+            divider: EmptyObject {},
+        }
+    }
+
+    pub fn breadcrumb() -> Self {
+        CreateBlock::Breadcrumb {
+            breadcrumb: EmptyObject {},
+        }
+    }
+
+    pub fn link_to_page(page_id: PageId) -> Self {
+        CreateBlock::LinkToPage {
+            link_to_page: LinkToPageFields::PageId { page_id },
+        }
+    }
+
+    pub fn link_to_database(database_id: DatabaseId) -> Self {
+        CreateBlock::LinkToPage {
+            link_to_page: LinkToPageFields::DatabaseId { database_id },
+        }
+    }
+
+    /// Blocks laid out side by side, each entry of `columns` holding the content of one column.
+    ///
+    /// Notion only accepts column lists with at least two columns which aren't empty.
+    pub fn column_list(columns: Vec<Vec<CreateBlock>>) -> Self {
+        CreateBlock::ColumnList {
+            column_list: ColumnListFields {
+                children: Some(columns.into_iter().map(CreateBlock::column).collect()),
+            },
+        }
+    }
+
+    /// A single column, to be added to an existing column list.
+    pub fn column(children: Vec<CreateBlock>) -> Self {
+        CreateBlock::Column {
+            column: ColumnFields {
+                children: Some(children),
+            },
+        }
+    }
+
+    /// A table with the given rows of cells, as wide as its longest row.
+    /// Shorter rows are padded with empty cells, as Notion requires every row to fill the table.
+    pub fn table<T: IntoRichText>(
+        rows: Vec<Vec<T>>,
+        has_column_header: bool,
+        has_row_header: bool,
+    ) -> Self {
+        let table_width = rows.iter().map(Vec::len).max().unwrap_or_default();
+        let rows = rows
+            .into_iter()
+            .map(|row| {
+                let mut cells: Vec<_> = row.into_iter().map(T::into_rich_text).collect();
+                cells.resize_with(table_width, Vec::new);
+                CreateBlock::TableRow {
+                    table_row: TableRowFields { cells },
+                }
+            })
+            .collect();
+        CreateBlock::Table {
+            table: TableFields {
+                table_width: table_width as u64,
+                has_column_header,
+                has_row_header,
+                children: Some(rows),
+            },
+        }
+    }
+
+    /// A row of a table, to be appended to an existing table with as many columns.
+    pub fn table_row<T: IntoRichText>(cells: Vec<T>) -> Self {
+        CreateBlock::TableRow {
+            table_row: TableRowFields {
+                cells: cells.into_iter().map(T::into_rich_text).collect(),
+            },
+        }
+    }
+
+    /// An original synced block holding `children`, which can then be duplicated
+    /// with [CreateBlock::synced_block_reference].
+    pub fn synced_block(children: Vec<CreateBlock>) -> Self {
+        CreateBlock::SyncedBlock {
+            synced_block: SyncedBlockFields {
+                synced_from: None,
+                children: Some(children),
+            },
+        }
+    }
+
+    /// A duplicate of the original synced block `block_id`.
+    pub fn synced_block_reference(block_id: BlockId) -> Self {
+        CreateBlock::SyncedBlock {
+            synced_block: SyncedBlockFields {
+                synced_from: Some(SyncedFrom::BlockId { block_id }),
+                children: None,
+            },
+        }
+    }
+
+    pub fn table_of_contents() -> Self {
+        CreateBlock::TableOfContents {
+            table_of_contents: TableOfContents {
+                color: TextColor::Default,
+            },
+        }
+    }
+
+    /// Nests `children` below this block, so both are created in a single request.
+    /// Notion accepts up to two levels of nesting per request.
+    ///
+    /// Blocks that can't contain any children, including headings that aren't toggleable
+    /// and duplicates of synced blocks, are returned unchanged.
+    pub fn with_children(
+        mut self,
+        children: Vec<CreateBlock>,
+    ) -> Self {
+        if let Some(slot) = self.children_mut() {
+            *slot = Some(children);
+        }
+        self
+    }
+
+    /// The slot holding the children to create along with this block,
+    /// or `None` if this kind of block can't contain any.
+    pub fn children_mut(&mut self) -> Option<&mut Option<Vec<CreateBlock>>> {
+        use CreateBlock::*;
+        match self {
+            Paragraph {
+                paragraph: fields, ..
+            }
+            | Quote { quote: fields, .. }
+            | BulletedListItem {
+                bulleted_list_item: fields,
+                ..
+            }
+            | NumberedListItem {
+                numbered_list_item: fields,
+                ..
+            }
+            | Toggle { toggle: fields, .. } => Some(&mut fields.children),
+            Heading1 {
+                heading_1: heading, ..
+            }
+            | Heading2 {
+                heading_2: heading, ..
+            }
+            | Heading3 {
+                heading_3: heading, ..
+            } if heading.is_toggleable => Some(&mut heading.children),
+            Callout { callout, .. } => Some(&mut callout.children),
+            ToDo { to_do, .. } => Some(&mut to_do.children),
+            ColumnList { column_list, .. } => Some(&mut column_list.children),
+            Column { column, .. } => Some(&mut column.children),
+            Table { table, .. } => Some(&mut table.children),
+            SyncedBlock { synced_block, .. } if synced_block.is_original() => {
+                Some(&mut synced_block.children)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A partial update of an existing block, see <https://developers.notion.com/reference/update-a-block>
+///
+/// Only fields which are `Some` are sent, everything else is left untouched.
+/// The block type can't be changed, so the variant has to match the type of the updated block.
+#[derive(Serialize, Debug, Eq, PartialEq, Clone)]
+#[serde(untagged)]
+pub enum UpdateBlock {
+    Paragraph {
+        paragraph: UpdateText,
+    },
+    Heading1 {
+        heading_1: UpdateText,
+    },
+    Heading2 {
+        heading_2: UpdateText,
+    },
+    Heading3 {
+        heading_3: UpdateText,
+    },
+    Callout {
+        callout: UpdateCallout,
+    },
+    Quote {
+        quote: UpdateText,
+    },
+    BulletedListItem {
+        bulleted_list_item: UpdateText,
+    },
+    NumberedListItem {
+        numbered_list_item: UpdateText,
+    },
+    ToDo {
+        to_do: UpdateToDo,
+    },
+    Toggle {
+        toggle: UpdateText,
+    },
+    Code {
+        code: UpdateCode,
+    },
+    Equation {
+        equation: Equation,
+    },
+    /// Moves the block to the trash, or restores it with `false`.
+    Archived {
+        archived: bool,
+    },
+}
+
+#[derive(Serialize, Debug, Eq, PartialEq, Clone, Default)]
+pub struct UpdateText {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rich_text: Option<Vec<RichText>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<TextColor>,
+}
+
+#[derive(Serialize, Debug, Eq, PartialEq, Clone, Default)]
+pub struct UpdateToDo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rich_text: Option<Vec<RichText>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checked: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<TextColor>,
+}
+
+#[derive(Serialize, Debug, Eq, PartialEq, Clone, Default)]
+pub struct UpdateCallout {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rich_text: Option<Vec<RichText>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<FileOrEmojiObject>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<TextColor>,
+}
+
+#[derive(Serialize, Debug, Eq, PartialEq, Clone, Default)]
+pub struct UpdateCode {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rich_text: Option<Vec<RichText>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub caption: Option<Vec<RichText>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<CodeLanguage>,
+}
+
+impl UpdateBlock {
+    /// Checks or unchecks a to_do block.
+    pub fn checked(checked: bool) -> Self {
+        UpdateBlock::ToDo {
+            to_do: UpdateToDo {
+                checked: Some(checked),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Replaces the text of a paragraph block.
+    pub fn paragraph<T: IntoRichText>(text: T) -> Self {
+        UpdateBlock::Paragraph {
+            paragraph: UpdateText {
+                rich_text: Some(text.into_rich_text()),
+                color: None,
+            },
+        }
+    }
+
+    pub fn archived(archived: bool) -> Self {
+        UpdateBlock::Archived { archived }
+    }
+}
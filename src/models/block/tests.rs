@@ -1,301 +1,1113 @@
-#[cfg(test)]
-mod tests {
-    use crate::ids::{BlockId, UserId};
-    use crate::models::block::{
-        Block, BlockCommon, Callout, ExternalFileObject, FileOrEmojiObject, InternalFileObject,
-        Text as TextBlockModel,
+use crate::ids::{BlockId, PageId, UserId};
+use crate::models::block::{
+    Block, BlockCommon, Callout, CodeLanguage, CreateBlock, Equation, FileObject,
+    FileOrEmojiObject, HeadingFields, LinkToPageFields, MediaBlock, TableFields, UpdateBlock,
+    UpdateText,
+};
+use crate::models::text::{Annotations, IntoRichText, RichText, RichTextCommon, Text, TextColor};
+use crate::models::users::UserCommon;
+use crate::models::Object;
+use serde_json::json;
+use std::str::FromStr;
+use time::format_description::well_known::Iso8601;
+use time::OffsetDateTime;
+
+#[test]
+fn heading_1() {
+    let heading_1: Block = serde_json::from_str(include_str!("tests/heading_1.json")).unwrap();
+    assert_eq!(
+        heading_1,
+        Block::Heading1 {
+            common: BlockCommon {
+                id: BlockId::from_str("9e891834-6a03-475c-a2b8-421e17f0f3aa").unwrap(),
+                created_time: OffsetDateTime::parse("2022-05-12T21:15:00.000Z", &Iso8601::DEFAULT)
+                    .unwrap(),
+
+                last_edited_time: OffsetDateTime::parse(
+                    "2022-05-12T22:10:00.000Z",
+                    &Iso8601::DEFAULT
+                )
+                .unwrap(),
+                has_children: false,
+                created_by: UserCommon {
+                    id: UserId::from_str("6419f912-5293-4ea8-b2c8-9c3ce44f90e3").unwrap(),
+                    name: None,
+                    avatar_url: None,
+                },
+                last_edited_by: UserCommon {
+                    id: UserId::from_str("6419f912-5293-4ea8-b2c8-9c3ce44f90e3").unwrap(),
+                    name: None,
+                    avatar_url: None,
+                },
+                archived: false,
+                in_trash: None,
+            },
+            heading_1: HeadingFields {
+                rich_text: vec![
+                    RichText::Text {
+                        rich_text: RichTextCommon {
+                            plain_text: "This".to_string(),
+                            href: None,
+                            annotations: Some(Annotations {
+                                bold: Some(false),
+                                code: Some(true),
+                                color: Some(TextColor::Default),
+                                italic: Some(false),
+                                strikethrough: Some(false),
+                                underline: Some(false),
+                            }),
+                        },
+                        text: Text {
+                            content: "This".to_string(),
+                            link: None,
+                        },
+                    },
+                    RichText::Text {
+                        rich_text: RichTextCommon {
+                            plain_text: " ".to_string(),
+                            href: None,
+                            annotations: Some(Annotations {
+                                bold: Some(false),
+                                code: Some(false),
+                                color: Some(TextColor::Default),
+                                italic: Some(false),
+                                strikethrough: Some(false),
+                                underline: Some(false),
+                            }),
+                        },
+                        text: Text {
+                            content: " ".to_string(),
+                            link: None,
+                        },
+                    },
+                    RichText::Text {
+                        rich_text: RichTextCommon {
+                            plain_text: "is".to_string(),
+                            href: None,
+                            annotations: Some(Annotations {
+                                bold: Some(false),
+                                code: Some(false),
+                                color: Some(TextColor::Default),
+                                italic: Some(false),
+                                strikethrough: Some(false),
+                                underline: Some(true),
+                            }),
+                        },
+                        text: Text {
+                            content: "is".to_string(),
+                            link: None,
+                        },
+                    },
+                    RichText::Text {
+                        rich_text: RichTextCommon {
+                            plain_text: " ".to_string(),
+                            href: None,
+                            annotations: Some(Annotations {
+                                bold: Some(false),
+                                code: Some(false),
+                                color: Some(TextColor::Default),
+                                italic: Some(false),
+                                strikethrough: Some(false),
+                                underline: Some(false),
+                            }),
+                        },
+                        text: Text {
+                            content: " ".to_string(),
+                            link: None,
+                        },
+                    },
+                    RichText::Text {
+                        rich_text: RichTextCommon {
+                            plain_text: "a".to_string(),
+                            href: None,
+                            annotations: Some(Annotations {
+                                bold: Some(false),
+                                code: Some(false),
+                                color: Some(TextColor::Default),
+                                italic: Some(true),
+                                strikethrough: Some(false),
+                                underline: Some(true),
+                            }),
+                        },
+                        text: Text {
+                            content: "a".to_string(),
+                            link: None,
+                        },
+                    },
+                    RichText::Text {
+                        rich_text: RichTextCommon {
+                            plain_text: " ".to_string(),
+                            href: None,
+                            annotations: Some(Annotations {
+                                bold: Some(false),
+                                code: Some(false),
+                                color: Some(TextColor::Default),
+                                italic: Some(false),
+                                strikethrough: Some(false),
+                                underline: Some(false),
+                            }),
+                        },
+                        text: Text {
+                            content: " ".to_string(),
+                            link: None,
+                        },
+                    },
+                    RichText::Text {
+                        rich_text: RichTextCommon {
+                            plain_text: "Heading".to_string(),
+                            href: None,
+                            annotations: Some(Annotations {
+                                bold: Some(false),
+                                code: Some(false),
+                                color: Some(TextColor::Default),
+                                italic: Some(true),
+                                strikethrough: Some(false),
+                                underline: Some(false),
+                            }),
+                        },
+                        text: Text {
+                            content: "Heading".to_string(),
+                            link: None,
+                        },
+                    },
+                    RichText::Text {
+                        rich_text: RichTextCommon {
+                            plain_text: " ".to_string(),
+                            href: None,
+                            annotations: Some(Annotations {
+                                bold: Some(false),
+                                code: Some(false),
+                                color: Some(TextColor::Default),
+                                italic: Some(false),
+                                strikethrough: Some(false),
+                                underline: Some(false),
+                            }),
+                        },
+                        text: Text {
+                            content: " ".to_string(),
+                            link: None,
+                        },
+                    },
+                    RichText::Text {
+                        rich_text: RichTextCommon {
+                            plain_text: "1".to_string(),
+                            href: None,
+                            annotations: Some(Annotations {
+                                bold: Some(false),
+                                code: Some(false),
+                                color: Some(TextColor::Default),
+                                italic: Some(false),
+                                strikethrough: Some(true),
+                                underline: Some(false),
+                            }),
+                        },
+                        text: Text {
+                            content: "1".to_string(),
+                            link: None,
+                        },
+                    },
+                ],
+                color: Some(TextColor::Default),
+                is_toggleable: false,
+                children: None,
+            },
+        }
+    )
+}
+
+#[test]
+fn emoji_object() {
+    let emoji_object: FileOrEmojiObject =
+        serde_json::from_str(include_str!("tests/emoji_object.json")).unwrap();
+    assert_eq!(
+        emoji_object,
+        FileOrEmojiObject::Emoji {
+            emoji: "💡".to_string()
+        }
+    )
+}
+
+#[test]
+fn file_object() {
+    let file_object: FileOrEmojiObject =
+        serde_json::from_str(include_str!("tests/file_object.json")).unwrap();
+    let FileOrEmojiObject::File { file } = file_object else {
+        panic!("Expected a file, got {:?}", file_object);
     };
-    use crate::models::text::{Annotations, RichText, RichTextCommon, Text, TextColor};
-    use crate::models::users::UserCommon;
-    use crate::models::Object;
-    use std::str::FromStr;
-    use time::format_description::well_known::Iso8601;
-    use time::OffsetDateTime;
-
-    #[test]
-    fn heading_1() {
-        let heading_1: Block = serde_json::from_str(include_str!("tests/heading_1.json")).unwrap();
-        assert_eq!(
-            heading_1,
-            Block::Heading1 {
+    assert!(file.url.starts_with("https://s3.us-west-2.amazonaws.com/"));
+    assert_eq!(
+        file.expiry_time,
+        OffsetDateTime::parse("2022-05-13T21:10:35.817Z", &Iso8601::DEFAULT).unwrap()
+    );
+}
+
+#[test]
+fn external_file_object() {
+    let external_file_object: FileOrEmojiObject =
+        serde_json::from_str(include_str!("tests/external_file_object.json")).unwrap();
+    assert_eq!(
+        external_file_object,
+        FileOrEmojiObject::external(
+            "https://nerdist.com/wp-content/uploads/2020/07/maxresdefault.jpg"
+        )
+    )
+}
+
+#[test]
+fn parse_file_objects() {
+    let file: FileObject = serde_json::from_str(include_str!("tests/file_object.json")).unwrap();
+    assert!(matches!(file, FileObject::File { .. }));
+    assert!(file
+        .url()
+        .starts_with("https://s3.us-west-2.amazonaws.com/secure.notion-static.com/"));
+
+    let external: FileObject =
+        serde_json::from_str(include_str!("tests/external_file_object.json")).unwrap();
+    assert_eq!(
+        external,
+        FileObject::external("https://nerdist.com/wp-content/uploads/2020/07/maxresdefault.jpg")
+    );
+}
+
+#[test]
+fn callout() {
+    let callout: Object = serde_json::from_str(include_str!("tests/callout.json")).unwrap();
+    assert_eq!(
+        callout,
+        Object::Block {
+            block: Block::Callout {
                 common: BlockCommon {
-                    id: BlockId::from_str("9e891834-6a03-475c-a2b8-421e17f0f3aa").unwrap(),
+                    id: BlockId::from_str("00e8829a-a7b8-4075-884a-8f53be145d2f").unwrap(),
                     created_time: OffsetDateTime::parse(
-                        "2022-05-12T21:15:00.000Z",
+                        "2022-05-13T20:08:00.000Z",
                         &Iso8601::DEFAULT
                     )
                     .unwrap(),
-
                     last_edited_time: OffsetDateTime::parse(
-                        "2022-05-12T22:10:00.000Z",
+                        "2022-05-13T20:08:00.000Z",
                         &Iso8601::DEFAULT
                     )
                     .unwrap(),
-                    has_children: false,
+                    has_children: true,
                     created_by: UserCommon {
-                        id: UserId::from_str("6419f912-5293-4ea8-b2c8-9c3ce44f90e3").unwrap(),
+                        id: UserId::from_str("e2507360-468c-4e0f-a928-7bbcbbb45353").unwrap(),
                         name: None,
                         avatar_url: None,
                     },
                     last_edited_by: UserCommon {
-                        id: UserId::from_str("6419f912-5293-4ea8-b2c8-9c3ce44f90e3").unwrap(),
+                        id: UserId::from_str("e2507360-468c-4e0f-a928-7bbcbbb45353").unwrap(),
                         name: None,
                         avatar_url: None,
                     },
+                    archived: false,
+                    in_trash: None,
                 },
-                heading_1: TextBlockModel {
-                    rich_text: vec![
-                        RichText::Text {
-                            rich_text: RichTextCommon {
-                                plain_text: "This".to_string(),
-                                href: None,
-                                annotations: Some(Annotations {
-                                    bold: Some(false),
-                                    code: Some(true),
-                                    color: Some(TextColor::Default),
-                                    italic: Some(false),
-                                    strikethrough: Some(false),
-                                    underline: Some(false),
-                                }),
-                            },
-                            text: Text {
-                                content: "This".to_string(),
-                                link: None,
-                            },
-                        },
-                        RichText::Text {
-                            rich_text: RichTextCommon {
-                                plain_text: " ".to_string(),
-                                href: None,
-                                annotations: Some(Annotations {
-                                    bold: Some(false),
-                                    code: Some(false),
-                                    color: Some(TextColor::Default),
-                                    italic: Some(false),
-                                    strikethrough: Some(false),
-                                    underline: Some(false),
-                                }),
-                            },
-                            text: Text {
-                                content: " ".to_string(),
-                                link: None,
-                            },
-                        },
-                        RichText::Text {
-                            rich_text: RichTextCommon {
-                                plain_text: "is".to_string(),
-                                href: None,
-                                annotations: Some(Annotations {
-                                    bold: Some(false),
-                                    code: Some(false),
-                                    color: Some(TextColor::Default),
-                                    italic: Some(false),
-                                    strikethrough: Some(false),
-                                    underline: Some(true),
-                                }),
-                            },
-                            text: Text {
-                                content: "is".to_string(),
-                                link: None,
-                            },
+                callout: Callout {
+                    rich_text: vec![RichText::Text {
+                        rich_text: RichTextCommon {
+                            plain_text: "Test callout".to_string(),
+                            href: None,
+                            annotations: Some(Annotations {
+                                bold: Some(false),
+                                code: Some(false),
+                                color: Some(TextColor::Default),
+                                italic: Some(false),
+                                strikethrough: Some(false),
+                                underline: Some(false),
+                            }),
                         },
-                        RichText::Text {
-                            rich_text: RichTextCommon {
-                                plain_text: " ".to_string(),
-                                href: None,
-                                annotations: Some(Annotations {
-                                    bold: Some(false),
-                                    code: Some(false),
-                                    color: Some(TextColor::Default),
-                                    italic: Some(false),
-                                    strikethrough: Some(false),
-                                    underline: Some(false),
-                                }),
-                            },
-                            text: Text {
-                                content: " ".to_string(),
-                                link: None,
-                            },
+                        text: Text {
+                            content: "Test callout".to_string(),
+                            link: None
                         },
-                        RichText::Text {
-                            rich_text: RichTextCommon {
-                                plain_text: "a".to_string(),
-                                href: None,
-                                annotations: Some(Annotations {
-                                    bold: Some(false),
-                                    code: Some(false),
-                                    color: Some(TextColor::Default),
-                                    italic: Some(true),
-                                    strikethrough: Some(false),
-                                    underline: Some(true),
-                                }),
-                            },
-                            text: Text {
-                                content: "a".to_string(),
-                                link: None,
-                            },
-                        },
-                        RichText::Text {
-                            rich_text: RichTextCommon {
-                                plain_text: " ".to_string(),
-                                href: None,
-                                annotations: Some(Annotations {
-                                    bold: Some(false),
-                                    code: Some(false),
-                                    color: Some(TextColor::Default),
-                                    italic: Some(false),
-                                    strikethrough: Some(false),
-                                    underline: Some(false),
-                                }),
-                            },
-                            text: Text {
-                                content: " ".to_string(),
-                                link: None,
-                            },
-                        },
-                        RichText::Text {
-                            rich_text: RichTextCommon {
-                                plain_text: "Heading".to_string(),
-                                href: None,
-                                annotations: Some(Annotations {
-                                    bold: Some(false),
-                                    code: Some(false),
-                                    color: Some(TextColor::Default),
-                                    italic: Some(true),
-                                    strikethrough: Some(false),
-                                    underline: Some(false),
-                                }),
-                            },
-                            text: Text {
-                                content: "Heading".to_string(),
-                                link: None,
-                            },
-                        },
-                        RichText::Text {
-                            rich_text: RichTextCommon {
-                                plain_text: " ".to_string(),
-                                href: None,
-                                annotations: Some(Annotations {
-                                    bold: Some(false),
-                                    code: Some(false),
-                                    color: Some(TextColor::Default),
-                                    italic: Some(false),
-                                    strikethrough: Some(false),
-                                    underline: Some(false),
-                                }),
-                            },
-                            text: Text {
-                                content: " ".to_string(),
-                                link: None,
-                            },
-                        },
-                        RichText::Text {
-                            rich_text: RichTextCommon {
-                                plain_text: "1".to_string(),
-                                href: None,
-                                annotations: Some(Annotations {
-                                    bold: Some(false),
-                                    code: Some(false),
-                                    color: Some(TextColor::Default),
-                                    italic: Some(false),
-                                    strikethrough: Some(true),
-                                    underline: Some(false),
-                                }),
-                            },
-                            text: Text {
-                                content: "1".to_string(),
-                                link: None,
-                            },
-                        },
-                    ]
+                    }],
+                    icon: FileOrEmojiObject::Emoji {
+                        emoji: "💡".to_string()
+                    },
+                    children: None,
+                    color: TextColor::Green,
                 },
             }
-        )
+        }
+    )
+}
+
+fn plain_text_json(content: &str) -> serde_json::Value {
+    json!([{"type": "text", "plain_text": content, "text": {"content": content}}])
+}
+
+#[test]
+fn create_text_blocks() {
+    let text = plain_text_json("Hello");
+    let cases = vec![
+        (
+            CreateBlock::paragraph("Hello"),
+            json!({"type": "paragraph", "paragraph": {"rich_text": text, "color": "default"}}),
+        ),
+        (
+            CreateBlock::heading_1("Hello"),
+            json!({"type": "heading_1", "heading_1": {"rich_text": text}}),
+        ),
+        (
+            CreateBlock::heading_2("Hello".to_string()),
+            json!({"type": "heading_2", "heading_2": {"rich_text": text}}),
+        ),
+        (
+            CreateBlock::heading_3("Hello"),
+            json!({"type": "heading_3", "heading_3": {"rich_text": text}}),
+        ),
+        (
+            CreateBlock::bulleted_list_item("Hello"),
+            json!({"type": "bulleted_list_item", "bulleted_list_item": {"rich_text": text, "color": "default"}}),
+        ),
+        (
+            CreateBlock::numbered_list_item("Hello"),
+            json!({"type": "numbered_list_item", "numbered_list_item": {"rich_text": text, "color": "default"}}),
+        ),
+        (
+            CreateBlock::to_do("Hello", true),
+            json!({"type": "to_do", "to_do": {"rich_text": text, "checked": true, "color": "default"}}),
+        ),
+        (
+            CreateBlock::toggle("Hello"),
+            json!({"type": "toggle", "toggle": {"rich_text": text, "color": "default"}}),
+        ),
+        (
+            CreateBlock::quote("Hello"),
+            json!({"type": "quote", "quote": {"rich_text": text, "color": "default"}}),
+        ),
+        (
+            CreateBlock::callout("Hello", FileOrEmojiObject::emoji("💡"), TextColor::Default),
+            json!({"type": "callout", "callout": {
+                "rich_text": text,
+                "icon": {"type": "emoji", "emoji": "💡"},
+                "color": "default"
+            }}),
+        ),
+        (
+            CreateBlock::code("Hello", CodeLanguage::Rust),
+            json!({"type": "code", "code": {"rich_text": text, "caption": [], "language": "rust"}}),
+        ),
+    ];
+
+    for (block, expected) in cases {
+        assert_eq!(serde_json::to_value(&block).unwrap(), expected);
     }
+}
 
-    #[test]
-    fn emoji_object() {
-        let emoji_object: FileOrEmojiObject =
-            serde_json::from_str(include_str!("tests/emoji_object.json")).unwrap();
-        assert_eq!(
-            emoji_object,
-            FileOrEmojiObject::Emoji {
-                emoji: "💡".to_string()
-            }
-        )
+#[test]
+fn create_blocks_without_text() {
+    let cases = vec![
+        (
+            CreateBlock::divider(),
+            json!({"type": "divider", "divider": {}}),
+        ),
+        (
+            CreateBlock::breadcrumb(),
+            json!({"type": "breadcrumb", "breadcrumb": {}}),
+        ),
+        (
+            CreateBlock::table_of_contents(),
+            json!({"type": "table_of_contents", "table_of_contents": {"color": "default"}}),
+        ),
+        (
+            CreateBlock::equation("e = mc^2"),
+            json!({"type": "equation", "equation": {"expression": "e = mc^2"}}),
+        ),
+        (
+            CreateBlock::bookmark("https://example.com"),
+            json!({"type": "bookmark", "bookmark": {"url": "https://example.com", "caption": []}}),
+        ),
+        (
+            CreateBlock::embed("https://example.com"),
+            json!({"type": "embed", "embed": {"url": "https://example.com"}}),
+        ),
+    ];
+
+    for (block, expected) in cases {
+        assert_eq!(serde_json::to_value(&block).unwrap(), expected);
     }
+}
 
-    #[test]
-    fn file_object() {
-        let file_object: FileOrEmojiObject =
-            serde_json::from_str(include_str!("tests/file_object.json")).unwrap();
-        assert_eq!(file_object, FileOrEmojiObject::File)
+#[test]
+fn create_block_splits_long_text() {
+    let block = CreateBlock::paragraph("word ".repeat(1000).as_str());
+    match block {
+        CreateBlock::Paragraph { paragraph } => assert_eq!(paragraph.rich_text.len(), 3),
+        other => panic!("Expected a paragraph, got {:?}", other),
     }
+}
 
-    #[test]
-    fn external_file_object() {
-        let external_file_object: FileOrEmojiObject =
-            serde_json::from_str(include_str!("tests/external_file_object.json")).unwrap();
-        assert_eq!(external_file_object, FileOrEmojiObject::External)
+fn assert_round_trips(block: &Block) {
+    let serialized = serde_json::to_string(block).unwrap();
+    let deserialized: Block = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(&deserialized, block);
+}
+
+#[test]
+fn toggleable_heading_round_trip() {
+    let json: serde_json::Value =
+        serde_json::from_str(include_str!("tests/heading_2_toggleable.json")).unwrap();
+    let mut block: Block = serde_json::from_value(json).unwrap();
+    let paragraph: Block =
+        serde_json::from_str(include_str!("tests/paragraph_with_equation.json")).unwrap();
+    *block.children_mut().unwrap() = Some(vec![paragraph.clone()]);
+
+    match &block {
+        Block::Heading2 { heading_2, .. } => {
+            assert!(heading_2.is_toggleable);
+            assert_eq!(heading_2.color, Some(TextColor::Blue));
+        }
+        other => panic!("Expected a heading, got {:?}", other),
     }
+    assert_eq!(block.children(), Some(&[paragraph][..]));
+    assert_round_trips(&block);
 
-    #[test]
-    fn callout() {
-        let callout: Object = serde_json::from_str(include_str!("tests/callout.json")).unwrap();
-        assert_eq!(
-            callout,
-            Object::Block {
-                block: Block::Callout {
-                    common: BlockCommon {
-                        id: BlockId::from_str("00e8829a-a7b8-4075-884a-8f53be145d2f").unwrap(),
-                        created_time: OffsetDateTime::parse(
-                            "2022-05-13T20:08:00.000Z",
-                            &Iso8601::DEFAULT
-                        )
-                        .unwrap(),
-                        last_edited_time: OffsetDateTime::parse(
-                            "2022-05-13T20:08:00.000Z",
-                            &Iso8601::DEFAULT
-                        )
-                        .unwrap(),
-                        has_children: true,
-                        created_by: UserCommon {
-                            id: UserId::from_str("e2507360-468c-4e0f-a928-7bbcbbb45353").unwrap(),
-                            name: None,
-                            avatar_url: None,
-                        },
-                        last_edited_by: UserCommon {
-                            id: UserId::from_str("e2507360-468c-4e0f-a928-7bbcbbb45353").unwrap(),
-                            name: None,
-                            avatar_url: None,
-                        },
-                    },
-                    callout: Callout {
-                        rich_text: vec![RichText::Text {
-                            rich_text: RichTextCommon {
-                                plain_text: "Test callout".to_string(),
-                                href: None,
-                                annotations: Some(Annotations {
-                                    bold: Some(false),
-                                    code: Some(false),
-                                    color: Some(TextColor::Default),
-                                    italic: Some(false),
-                                    strikethrough: Some(false),
-                                    underline: Some(false),
-                                }),
-                            },
-                            text: Text {
-                                content: "Test callout".to_string(),
-                                link: None
-                            },
-                        }],
-                        icon: FileOrEmojiObject::Emoji {
-                            emoji: "💡".to_string()
-                        },
-                        color: TextColor::Green,
-                    },
+    let heading_1: Block = serde_json::from_str(include_str!("tests/heading_1.json")).unwrap();
+    assert!(heading_1.clone().children_mut().is_none());
+
+    assert_eq!(
+        serde_json::to_value(CreateBlock::toggleable_heading_2(
+            "Details",
+            vec![CreateBlock::paragraph("Hidden")]
+        ))
+        .unwrap(),
+        json!({"type": "heading_2", "heading_2": {
+            "rich_text": [{"type": "text", "text": {"content": "Details"}, "plain_text": "Details"}],
+            "is_toggleable": true,
+            "children": [{"type": "paragraph", "paragraph": {
+                "rich_text": [{"type": "text", "text": {"content": "Hidden"}, "plain_text": "Hidden"}],
+                "color": "default"
+            }}]
+        }})
+    );
+}
+
+#[test]
+fn quote_with_nested_list_items() {
+    let quote = CreateBlock::quote("Shopping list").with_children(vec![
+        CreateBlock::bulleted_list_item("Milk"),
+        CreateBlock::bulleted_list_item("Bread"),
+    ]);
+    let json = serde_json::to_value(&quote).unwrap();
+
+    let item = |content: &str| {
+        json!({"type": "bulleted_list_item", "bulleted_list_item": {
+            "rich_text": [{"type": "text", "text": {"content": content}, "plain_text": content}],
+            "color": "default"
+        }})
+    };
+    assert_eq!(
+        json,
+        json!({"type": "quote", "quote": {
+            "rich_text": [{"type": "text", "text": {"content": "Shopping list"}, "plain_text": "Shopping list"}],
+            "children": [item("Milk"), item("Bread")],
+            "color": "default"
+        }})
+    );
+    assert_eq!(serde_json::from_value::<CreateBlock>(json).unwrap(), quote);
+
+    assert_eq!(
+        CreateBlock::heading_1("Title").with_children(vec![CreateBlock::paragraph("Lost")]),
+        CreateBlock::heading_1("Title")
+    );
+    assert!(CreateBlock::divider().children_mut().is_none());
+}
+
+#[test]
+fn callout_round_trip() {
+    let json: serde_json::Value = serde_json::from_str(include_str!("tests/callout.json")).unwrap();
+    let block: Block = serde_json::from_value(json.clone()).unwrap();
+    assert_round_trips(&block);
+    let serialized = serde_json::to_value(&block).unwrap();
+    assert_eq!(
+        serialized["callout"]["icon"],
+        json!({"type": "emoji", "emoji": "💡"})
+    );
+    assert_eq!(serialized["callout"]["color"], json!("green"));
+
+    let created = CreateBlock::callout(
+        "Mind the gap",
+        FileOrEmojiObject::external("https://example.com/warning.png"),
+        TextColor::YellowBackground,
+    );
+    let CreateBlock::Callout { callout } = created else {
+        panic!("Expected a callout, got {:?}", created);
+    };
+    let serialized: Callout<CreateBlock> =
+        serde_json::from_value(serde_json::to_value(&callout).unwrap()).unwrap();
+    assert_eq!(serialized, callout);
+    assert_eq!(
+        serde_json::to_value(&callout).unwrap()["icon"],
+        json!({"type": "external", "external": {"url": "https://example.com/warning.png"}})
+    );
+    assert_eq!(
+        serde_json::to_value(&callout).unwrap()["color"],
+        json!("yellow_background")
+    );
+}
+
+#[test]
+fn synced_block_original() {
+    let json: serde_json::Value =
+        serde_json::from_str(include_str!("tests/synced_block_original.json")).unwrap();
+    let block: Block = serde_json::from_value(json.clone()).unwrap();
+
+    let synced_block = match &block {
+        Block::SyncedBlock { synced_block, .. } => synced_block,
+        other => panic!("Expected a synced block, got {:?}", other),
+    };
+    assert!(synced_block.is_original());
+    assert_eq!(synced_block.original_block_id(), None);
+    assert_round_trips(&block);
+    assert_eq!(
+        serde_json::to_value(&block).unwrap()["synced_block"],
+        json["synced_block"]
+    );
+}
+
+#[test]
+fn synced_block_reference() {
+    let json: serde_json::Value =
+        serde_json::from_str(include_str!("tests/synced_block_reference.json")).unwrap();
+    let block: Block = serde_json::from_value(json.clone()).unwrap();
+
+    let synced_block = match &block {
+        Block::SyncedBlock { synced_block, .. } => synced_block,
+        other => panic!("Expected a synced block, got {:?}", other),
+    };
+    let original = BlockId::from_str("7bdb2c53-3b2c-4d2a-9a31-5e6168a3a7a7").unwrap();
+    assert!(!synced_block.is_original());
+    assert_eq!(synced_block.original_block_id(), Some(&original));
+    assert_round_trips(&block);
+    assert_eq!(
+        serde_json::to_value(&block).unwrap()["synced_block"],
+        json["synced_block"]
+    );
+
+    assert_eq!(
+        serde_json::to_value(CreateBlock::synced_block_reference(original)).unwrap(),
+        json!({"type": "synced_block", "synced_block": json["synced_block"]})
+    );
+}
+
+#[test]
+fn create_synced_block_with_children() {
+    let original = CreateBlock::synced_block(vec![CreateBlock::paragraph("Shared")])
+        .with_children(vec![CreateBlock::divider()]);
+    assert_eq!(
+        serde_json::to_value(&original).unwrap(),
+        json!({
+            "type": "synced_block",
+            "synced_block": {
+                "synced_from": null,
+                "children": [
+                    serde_json::to_value(CreateBlock::divider()).unwrap()
+                ]
+            }
+        })
+    );
+
+    // The children of a duplicate mirror the original and can't be set.
+    let original_id = BlockId::from_str("7bdb2c53-3b2c-4d2a-9a31-5e6168a3a7a7").unwrap();
+    let reference = CreateBlock::synced_block_reference(original_id.clone());
+    assert_eq!(
+        reference
+            .clone()
+            .with_children(vec![CreateBlock::divider()]),
+        reference
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_str(include_str!("tests/synced_block_reference.json")).unwrap();
+    let mut block: Block = serde_json::from_value(json).unwrap();
+    if let Block::SyncedBlock { synced_block, .. } = &mut block {
+        synced_block.children = Some(vec![]);
+    }
+    assert_eq!(CreateBlock::from(block), reference);
+}
+
+#[test]
+fn serialize_block_updates() {
+    assert_eq!(
+        serde_json::to_string(&UpdateBlock::checked(true)).unwrap(),
+        r#"{"to_do":{"checked":true}}"#
+    );
+    assert_eq!(
+        serde_json::to_value(UpdateBlock::paragraph("Hello")).unwrap(),
+        json!({"paragraph": {"rich_text": plain_text_json("Hello")}})
+    );
+    assert_eq!(
+        serde_json::to_value(UpdateBlock::Heading2 {
+            heading_2: UpdateText {
+                color: Some(TextColor::Blue),
+                ..Default::default()
+            }
+        })
+        .unwrap(),
+        json!({"heading_2": {"color": "blue"}})
+    );
+    assert_eq!(
+        serde_json::to_string(&UpdateBlock::archived(true)).unwrap(),
+        r#"{"archived":true}"#
+    );
+}
+
+#[test]
+fn paragraph_with_inline_equation() {
+    let block: Block =
+        serde_json::from_str(include_str!("tests/paragraph_with_equation.json")).unwrap();
+
+    let Block::Paragraph { paragraph, .. } = block else {
+        panic!("Expected a paragraph, got {:?}", block);
+    };
+    assert_eq!(paragraph.rich_text.len(), 2);
+    assert!(matches!(paragraph.rich_text[0], RichText::Text { .. }));
+    assert_eq!(
+        paragraph.rich_text[1],
+        RichText::Equation {
+            rich_text: RichTextCommon {
+                plain_text: "E = mc^2".to_string(),
+                href: None,
+                annotations: Some(Annotations {
+                    bold: Some(false),
+                    code: Some(false),
+                    color: Some(TextColor::Default),
+                    italic: Some(false),
+                    strikethrough: Some(false),
+                    underline: Some(false),
+                }),
+            },
+            equation: Equation {
+                expression: "E = mc^2".to_string(),
+            },
+        }
+    );
+    assert_eq!(paragraph.rich_text[1].plain_text(), "E = mc^2");
+}
+
+#[test]
+fn to_create_block_drops_children_and_skips_databases() {
+    let mut callout: Block = serde_json::from_str(include_str!("tests/callout.json")).unwrap();
+    let heading: Block = serde_json::from_str(include_str!("tests/heading_1.json")).unwrap();
+    *callout.children_mut().unwrap() = Some(vec![heading.clone()]);
+
+    match callout.to_create_block() {
+        Some(CreateBlock::Callout { callout }) => assert_eq!(callout.children, None),
+        other => panic!("Expected a callout, got {:?}", other),
+    }
+    assert_eq!(heading.to_create_block(), Some(heading.clone().into()));
+
+    let database: Block = serde_json::from_value(json!({
+        "object": "block",
+        "id": "2b8d7d6c-3e4f-4a5b-8c9d-0e1f2a3b4c5d",
+        "created_time": "2022-05-13T20:08:00.000Z",
+        "last_edited_time": "2022-05-13T20:08:00.000Z",
+        "created_by": {"object": "user", "id": "e2507360-468c-4e0f-a928-7bbcbbb45353"},
+        "last_edited_by": {"object": "user", "id": "e2507360-468c-4e0f-a928-7bbcbbb45353"},
+        "has_children": false,
+        "type": "child_database",
+        "child_database": {"title": "Tasks"}
+    }))
+    .unwrap();
+    assert_eq!(database.to_create_block(), None);
+}
+
+#[test]
+fn create_table_with_rows() {
+    let table = CreateBlock::table(
+        vec![vec!["Name", "Price"], vec!["Milk", "1.50"]],
+        true,
+        false,
+    );
+    let json = json!({
+        "type": "table",
+        "table": {
+            "table_width": 2,
+            "has_column_header": true,
+            "has_row_header": false,
+            "children": [
+                {
+                    "type": "table_row",
+                    "table_row": {"cells": [plain_text_json("Name"), plain_text_json("Price")]}
+                },
+                {
+                    "type": "table_row",
+                    "table_row": {"cells": [plain_text_json("Milk"), plain_text_json("1.50")]}
                 }
+            ]
+        }
+    });
+
+    assert_eq!(serde_json::to_value(&table).unwrap(), json);
+    assert_eq!(serde_json::from_value::<CreateBlock>(json).unwrap(), table);
+}
+
+#[test]
+fn table_blocks_round_trip() {
+    let table: Block = serde_json::from_str(include_str!("tests/table.json")).unwrap();
+    let row: Block = serde_json::from_str(include_str!("tests/table_row.json")).unwrap();
+
+    match &row {
+        Block::TableRow { table_row, .. } => {
+            assert_eq!(table_row.cells.len(), 2);
+            assert_eq!(table_row.cells[0][0].plain_text(), "Name");
+            assert!(table_row.cells[1].is_empty());
+        }
+        other => panic!("Expected a table row, got {:?}", other),
+    }
+    assert_round_trips(&table);
+    assert_round_trips(&row);
+
+    // Tables are only copied together with their rows
+    assert_eq!(table.to_create_block(), None);
+    let mut with_rows = table;
+    *with_rows.children_mut().unwrap() = Some(vec![row]);
+    assert!(matches!(
+        with_rows.to_create_block(),
+        Some(CreateBlock::Table { table: TableFields { children: Some(rows), .. } }) if rows.len() == 1
+    ));
+}
+
+#[test]
+fn create_table_pads_short_rows() {
+    let table = CreateBlock::table(
+        vec![vec!["Name", "Price"], vec!["Bread"], vec![]],
+        false,
+        false,
+    );
+    let cells = match table {
+        CreateBlock::Table {
+            table:
+                TableFields {
+                    table_width: 2,
+                    children: Some(rows),
+                    ..
+                },
+        } => rows
+            .into_iter()
+            .map(|row| match row {
+                CreateBlock::TableRow { table_row } => table_row.cells,
+                other => panic!("Expected a table row, got {:?}", other),
+            })
+            .collect::<Vec<_>>(),
+        other => panic!("Expected a table two cells wide, got {:?}", other),
+    };
+    assert_eq!(
+        cells,
+        vec![
+            vec!["Name".into_rich_text(), "Price".into_rich_text()],
+            vec!["Bread".into_rich_text(), vec![]],
+            vec![vec![], vec![]],
+        ]
+    );
+}
+
+#[test]
+fn to_create_block_keeps_columns_with_the_list() {
+    let mut column_list: Block =
+        serde_json::from_str(include_str!("tests/column_list.json")).unwrap();
+    let callout: Block = serde_json::from_str(include_str!("tests/callout.json")).unwrap();
+    let heading: Block = serde_json::from_str(include_str!("tests/heading_1.json")).unwrap();
+    let column = |content: Vec<Block>| {
+        let mut column: Block = serde_json::from_value(json!({
+            "object": "block",
+            "id": "9c0d1e2f-3a4b-4c5d-8e6f-7a8b9c0d1e2f",
+            "created_time": "2022-05-13T20:10:00.000Z",
+            "last_edited_time": "2022-05-13T20:10:00.000Z",
+            "created_by": {"object": "user", "id": "e2507360-468c-4e0f-a928-7bbcbbb45353"},
+            "last_edited_by": {"object": "user", "id": "e2507360-468c-4e0f-a928-7bbcbbb45353"},
+            "has_children": true,
+            "type": "column",
+            "column": {}
+        }))
+        .unwrap();
+        assert_eq!(column.to_create_block(), None);
+        *column.children_mut().unwrap() = Some(content);
+        column
+    };
+
+    // Columns are only copied together with their list
+    assert_eq!(column_list.to_create_block(), None);
+    let mut nested_callout = callout.clone();
+    *nested_callout.children_mut().unwrap() = Some(vec![heading.clone()]);
+    *column_list.children_mut().unwrap() = Some(vec![
+        column(vec![nested_callout]),
+        column(vec![heading.clone()]),
+    ]);
+
+    // The content of the columns comes along, its children don't
+    assert_eq!(
+        column_list.to_create_block(),
+        Some(CreateBlock::column_list(vec![
+            vec![callout.to_create_block().unwrap()],
+            vec![heading.to_create_block().unwrap()],
+        ]))
+    );
+
+    *column_list.children_mut().unwrap() = Some(vec![column(vec![heading]), {
+        let mut unfetched = column(vec![]);
+        *unfetched.children_mut().unwrap() = None;
+        unfetched
+    }]);
+    assert_eq!(column_list.to_create_block(), None);
+}
+
+#[test]
+fn link_to_page_round_trip() {
+    let block: Block = serde_json::from_str(include_str!("tests/link_to_page.json")).unwrap();
+    let page_id = PageId::from_str("bb85a889-3eb3-4146-9325-80508fb5e23d").unwrap();
+
+    match &block {
+        Block::LinkToPage { link_to_page, .. } => assert_eq!(
+            link_to_page,
+            &LinkToPageFields::PageId {
+                page_id: page_id.clone()
             }
-        )
+        ),
+        other => panic!("Expected a link to a page, got {:?}", other),
+    }
+    assert_round_trips(&block);
+
+    let create = CreateBlock::link_to_page(page_id);
+    assert_eq!(block.to_create_block(), Some(create.clone()));
+    assert_eq!(
+        serde_json::to_value(&create).unwrap(),
+        json!({
+            "type": "link_to_page",
+            "link_to_page": {"type": "page_id", "page_id": "bb85a889-3eb3-4146-9325-80508fb5e23d"}
+        })
+    );
+}
+
+#[test]
+fn child_page() {
+    let block: Block = serde_json::from_str(include_str!("tests/child_page.json")).unwrap();
+
+    match &block {
+        Block::ChildPage { child_page, .. } => assert_eq!(child_page.title, "Meeting notes"),
+        other => panic!("Expected a child page, got {:?}", other),
+    }
+    assert_eq!(
+        block.as_page_id(),
+        Some(PageId::from_str("7d1c2b3a-4e5f-4a6b-9c8d-7e6f5a4b3c2d").unwrap())
+    );
+    assert_eq!(block.as_database_id(), None);
+    assert_round_trips(&block);
+}
+
+#[test]
+fn equation_block_round_trip() {
+    let block: Block = serde_json::from_str(include_str!("tests/equation.json")).unwrap();
+    let expression = r"\int_0^1 x^2 \, dx = \frac{1}{3}";
+
+    match &block {
+        Block::Equation { equation, .. } => assert_eq!(equation.expression, expression),
+        other => panic!("Expected an equation, got {:?}", other),
+    }
+    assert_round_trips(&block);
+    assert_eq!(
+        block.to_create_block(),
+        Some(CreateBlock::equation(expression))
+    );
+}
+
+#[test]
+fn bookmark_block() {
+    let block: Block = serde_json::from_str(include_str!("tests/bookmark.json")).unwrap();
+
+    match &block {
+        Block::Bookmark { bookmark, .. } => {
+            assert_eq!(bookmark.url, "https://developers.notion.com");
+            assert_eq!(bookmark.caption[0].plain_text(), "Notion API");
+        }
+        other => panic!("Expected a bookmark, got {:?}", other),
+    }
+    assert_round_trips(&block);
+}
+
+#[test]
+fn embed_block() {
+    let block: Block = serde_json::from_str(include_str!("tests/embed.json")).unwrap();
+
+    assert_eq!(
+        block.to_create_block(),
+        Some(CreateBlock::embed(
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQ"
+        ))
+    );
+    assert_round_trips(&block);
+}
+
+#[test]
+fn link_preview_block() {
+    let block: Block = serde_json::from_str(include_str!("tests/link_preview.json")).unwrap();
+
+    match &block {
+        Block::LinkPreview { link_preview, .. } => assert_eq!(
+            link_preview.url,
+            "https://github.com/jakeswenson/notion/pull/42"
+        ),
+        other => panic!("Expected a link preview, got {:?}", other),
+    }
+    // Link previews can't be created through the API
+    assert_eq!(block.to_create_block(), None);
+    assert_round_trips(&block);
+}
+
+#[test]
+fn image_hosted_by_notion() {
+    let block: Block = serde_json::from_str(include_str!("tests/image_file.json")).unwrap();
+
+    match &block {
+        Block::Image {
+            image:
+                MediaBlock {
+                    file: FileObject::File { file },
+                    caption,
+                    ..
+                },
+            ..
+        } => {
+            assert_eq!(
+                file.expiry_time,
+                OffsetDateTime::parse("2022-05-13T21:08:00.000Z", &Iso8601::DEFAULT).unwrap()
+            );
+            assert_eq!(caption[0].plain_text(), "Architecture");
+        }
+        other => panic!("Expected a Notion hosted image, got {:?}", other),
+    }
+    assert_round_trips(&block);
+}
+
+#[test]
+fn external_video() {
+    let block: Block = serde_json::from_str(include_str!("tests/video_external.json")).unwrap();
+
+    assert_eq!(
+        block.to_create_block(),
+        Some(CreateBlock::video(
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQ"
+        ))
+    );
+    assert_round_trips(&block);
+    assert_eq!(
+        serde_json::to_value(CreateBlock::image("https://example.com/cat.png")).unwrap(),
+        json!({
+            "type": "image",
+            "image": {
+                "type": "external",
+                "external": {"url": "https://example.com/cat.png"},
+                "caption": []
+            }
+        })
+    );
+}
+
+#[test]
+fn file_url_expiry() {
+    let file: FileObject = serde_json::from_str(include_str!("tests/file_object.json")).unwrap();
+    let expiry_time = OffsetDateTime::parse("2022-05-13T21:10:35.817Z", &Iso8601::DEFAULT).unwrap();
+
+    assert!(!file.is_expired(expiry_time - time::Duration::milliseconds(1)));
+    assert!(file.is_expired(expiry_time));
+    assert!(file.is_expired(expiry_time + time::Duration::milliseconds(1)));
+    assert!(!FileObject::external("https://example.com/cat.png").is_expired(expiry_time));
+}
+
+#[test]
+fn two_column_layout_round_trip() {
+    let column_list: Block = serde_json::from_str(include_str!("tests/column_list.json")).unwrap();
+    assert!(matches!(
+        &column_list,
+        Block::ColumnList { column_list, .. } if column_list.children.is_none()
+    ));
+    assert_round_trips(&column_list);
+
+    let layout = CreateBlock::column_list(vec![
+        vec![CreateBlock::paragraph("Left")],
+        vec![CreateBlock::paragraph("Right")],
+    ]);
+    let column = |content: &str| {
+        json!({
+            "type": "column",
+            "column": {
+                "children": [{
+                    "type": "paragraph",
+                    "paragraph": {"rich_text": plain_text_json(content), "color": "default"}
+                }]
+            }
+        })
+    };
+    let json = json!({
+        "type": "column_list",
+        "column_list": {"children": [column("Left"), column("Right")]}
+    });
+
+    assert_eq!(serde_json::to_value(&layout).unwrap(), json);
+    assert_eq!(serde_json::from_value::<CreateBlock>(json).unwrap(), layout);
+}
+
+#[test]
+fn table_of_contents_block() {
+    let block: Block = serde_json::from_str(include_str!("tests/table_of_contents.json")).unwrap();
+
+    match &block {
+        Block::TableOfContents {
+            table_of_contents, ..
+        } => assert_eq!(table_of_contents.color, TextColor::GrayBackground),
+        other => panic!("Expected a table of contents, got {:?}", other),
     }
+    assert_round_trips(&block);
+}
+
+#[test]
+fn breadcrumb_block() {
+    let block: Block = serde_json::from_str(include_str!("tests/breadcrumb.json")).unwrap();
+
+    assert!(matches!(block, Block::Breadcrumb { .. }));
+    assert_eq!(block.to_create_block(), Some(CreateBlock::breadcrumb()));
+    assert_round_trips(&block);
+}
+
+#[test]
+fn paragraph_color() {
+    let mut paragraph: Block =
+        serde_json::from_str(include_str!("tests/paragraph_with_equation.json")).unwrap();
+    assert_eq!(paragraph.color(), Some(TextColor::Default));
+
+    assert!(paragraph.set_color(TextColor::BlueBackground));
+    assert_eq!(paragraph.color(), Some(TextColor::BlueBackground));
+    assert_eq!(
+        serde_json::to_value(&paragraph).unwrap()["paragraph"]["color"],
+        "blue_background"
+    );
+}
+
+#[test]
+fn callout_color() {
+    let mut callout: Block = serde_json::from_str(include_str!("tests/callout.json")).unwrap();
+    assert_eq!(callout.color(), Some(TextColor::Green));
+
+    assert!(callout.set_color(TextColor::Red));
+    assert!(matches!(
+        &callout,
+        Block::Callout { callout, .. } if callout.color == TextColor::Red
+    ));
+
+    let mut divider: Block = serde_json::from_value(json!({
+        "object": "block",
+        "id": "5e845049-255f-4822-b2ad-8d3f5ec4a7e8",
+        "created_time": "2021-05-15T17:16:00.000Z",
+        "last_edited_time": "2021-05-15T17:16:00.000Z",
+        "created_by": {"object": "user", "id": "6419f912-5293-4ea8-b2c8-9c3ce44f90e3"},
+        "last_edited_by": {"object": "user", "id": "6419f912-5293-4ea8-b2c8-9c3ce44f90e3"},
+        "has_children": false,
+        "archived": false,
+        "type": "divider",
+        "divider": {}
+    }))
+    .unwrap();
+    assert_eq!(divider.color(), None);
+    assert!(!divider.set_color(TextColor::Red));
 }
@@ -0,0 +1,139 @@
+use crate::ids::{AsIdentifier, CommentId, DiscussionId, PageId};
+use crate::models::text::RichText;
+use crate::models::users::UserCommon;
+use crate::models::Parent;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+/// A comment on a page or block.
+/// See <https://developers.notion.com/reference/comment-object>
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct Comment {
+    pub id: CommentId,
+    /// The page or block the comment is attached to.
+    pub parent: Parent,
+    /// The discussion thread the comment belongs to.
+    pub discussion_id: DiscussionId,
+    #[serde(with = "time::serde::iso8601")]
+    pub created_time: OffsetDateTime,
+    #[serde(with = "time::serde::iso8601")]
+    pub last_edited_time: OffsetDateTime,
+    pub created_by: UserCommon,
+    pub rich_text: Vec<RichText>,
+}
+
+impl AsIdentifier<CommentId> for Comment {
+    fn as_id(&self) -> &CommentId {
+        &self.id
+    }
+}
+
+/// Either starts a new discussion on a page, or replies to an existing one.
+/// See <https://developers.notion.com/reference/create-a-comment>
+#[derive(Serialize, Debug, Eq, PartialEq, Clone)]
+#[serde(untagged)]
+pub enum CreateCommentRequest {
+    Page {
+        parent: Parent,
+        rich_text: Vec<RichText>,
+    },
+    Discussion {
+        discussion_id: DiscussionId,
+        rich_text: Vec<RichText>,
+    },
+}
+
+impl CreateCommentRequest {
+    /// Starts a new discussion at the top of a page.
+    pub fn on_page(
+        page_id: PageId,
+        rich_text: Vec<RichText>,
+    ) -> Self {
+        CreateCommentRequest::Page {
+            parent: Parent::Page { page_id },
+            rich_text,
+        }
+    }
+
+    /// Adds a comment to an existing discussion thread.
+    pub fn reply(
+        discussion_id: DiscussionId,
+        rich_text: Vec<RichText>,
+    ) -> Self {
+        CreateCommentRequest::Discussion {
+            discussion_id,
+            rich_text,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ids::{DiscussionId, PageId};
+    use crate::models::comments::{Comment, CreateCommentRequest};
+    use crate::models::text::{RichText, RichTextCommon, Text};
+    use crate::models::{ListResponse, Object, Parent};
+    use serde_json::json;
+    use std::str::FromStr;
+
+    fn text(content: &str) -> Vec<RichText> {
+        vec![RichText::Text {
+            rich_text: RichTextCommon {
+                plain_text: content.to_string(),
+                href: None,
+                annotations: None,
+            },
+            text: Text {
+                content: content.to_string(),
+                link: None,
+            },
+        }]
+    }
+
+    #[test]
+    fn deserialize_comment() {
+        let comment: Comment = serde_json::from_str(include_str!("tests/comment.json")).unwrap();
+        assert_eq!(
+            comment.parent,
+            Parent::Page {
+                page_id: PageId::from_str("5c6a2821-6bb1-4a7e-b6e1-c50111515c3d").unwrap()
+            }
+        );
+        assert_eq!(
+            comment.discussion_id,
+            DiscussionId::from_str("f1407351-36f5-4c49-a13c-49f8ba11776d").unwrap()
+        );
+        assert_eq!(comment.rich_text[0].plain_text(), "Hello world");
+    }
+
+    #[test]
+    fn deserialize_comment_list() {
+        let list: ListResponse<Object> =
+            serde_json::from_str(include_str!("tests/comment_list.json")).unwrap();
+        let comments = list.expect_comments().unwrap();
+        assert_eq!(comments.results.len(), 2);
+        assert!(matches!(comments.results[1].parent, Parent::Block { .. }));
+    }
+
+    #[test]
+    fn serialize_create_comment() {
+        let page_id = PageId::from_str("5c6a2821-6bb1-4a7e-b6e1-c50111515c3d").unwrap();
+        assert_eq!(
+            serde_json::to_value(CreateCommentRequest::on_page(page_id, text("Hello"))).unwrap(),
+            json!({
+                "parent": {"type": "page_id", "page_id": "5c6a2821-6bb1-4a7e-b6e1-c50111515c3d"},
+                "rich_text": [{"type": "text", "plain_text": "Hello", "text": {"content": "Hello"}}]
+            })
+        );
+
+        let discussion_id = DiscussionId::from_str("f1407351-36f5-4c49-a13c-49f8ba11776d").unwrap();
+        assert_eq!(
+            serde_json::to_value(CreateCommentRequest::reply(discussion_id, text("Reply")))
+                .unwrap(),
+            json!({
+                "discussion_id": "f1407351-36f5-4c49-a13c-49f8ba11776d",
+                "rich_text": [{"type": "text", "plain_text": "Reply", "text": {"content": "Reply"}}]
+            })
+        );
+    }
+}
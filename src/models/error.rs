@@ -26,26 +26,112 @@ pub struct ErrorResponse {
     pub status: StatusCode,
     pub code: ErrorCode,
     pub message: String,
+    /// Identifies the failed request when contacting Notion's support.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
 }
 
 /// <https://developers.notion.com/reference/errors>
 #[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Clone, Hash)]
-#[serde(rename_all = "snake_case")]
+#[serde(from = "String", into = "String")]
 pub enum ErrorCode {
     InvalidJson,
     InvalidRequestUrl,
     InvalidRequest,
+    InvalidGrant,
     ValidationError,
-    MissionVersion,
+    MissingVersion,
     Unauthorized,
     RestrictedResource,
     ObjectNotFound,
     ConflictError,
     RateLimited,
     InternalServerError,
+    BadGateway,
     ServiceUnavailable,
-    #[serde(other)] // serde issue #912
-    Unknown,
+    DatabaseConnectionUnavailable,
+    GatewayTimeout,
+    /// A code this version of the crate doesn't know about yet.
+    Other(String),
+}
+
+impl ErrorCode {
+    /// The code as sent by the API, e.g. `validation_error`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            ErrorCode::InvalidJson => "invalid_json",
+            ErrorCode::InvalidRequestUrl => "invalid_request_url",
+            ErrorCode::InvalidRequest => "invalid_request",
+            ErrorCode::InvalidGrant => "invalid_grant",
+            ErrorCode::ValidationError => "validation_error",
+            ErrorCode::MissingVersion => "missing_version",
+            ErrorCode::Unauthorized => "unauthorized",
+            ErrorCode::RestrictedResource => "restricted_resource",
+            ErrorCode::ObjectNotFound => "object_not_found",
+            ErrorCode::ConflictError => "conflict_error",
+            ErrorCode::RateLimited => "rate_limited",
+            ErrorCode::InternalServerError => "internal_server_error",
+            ErrorCode::BadGateway => "bad_gateway",
+            ErrorCode::ServiceUnavailable => "service_unavailable",
+            ErrorCode::DatabaseConnectionUnavailable => "database_connection_unavailable",
+            ErrorCode::GatewayTimeout => "gateway_timeout",
+            ErrorCode::Other(code) => code,
+        }
+    }
+
+    /// Whether the code is one of rate limiting or a server error, see [ErrorResponse::is_retryable].
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            ErrorCode::RateLimited
+                | ErrorCode::InternalServerError
+                | ErrorCode::BadGateway
+                | ErrorCode::ServiceUnavailable
+                | ErrorCode::DatabaseConnectionUnavailable
+                | ErrorCode::GatewayTimeout
+        )
+    }
+}
+
+impl ErrorResponse {
+    /// Whether the request may succeed when sent again unchanged:
+    /// it was rate limited or failed on Notion's side.
+    pub fn is_retryable(&self) -> bool {
+        self.status.code() == 429 || self.status.code() >= 500 || self.code.is_retryable()
+    }
+}
+
+impl From<String> for ErrorCode {
+    fn from(code: String) -> Self {
+        match code.as_str() {
+            "invalid_json" => ErrorCode::InvalidJson,
+            "invalid_request_url" => ErrorCode::InvalidRequestUrl,
+            "invalid_request" => ErrorCode::InvalidRequest,
+            "invalid_grant" => ErrorCode::InvalidGrant,
+            "validation_error" => ErrorCode::ValidationError,
+            "missing_version" => ErrorCode::MissingVersion,
+            "unauthorized" => ErrorCode::Unauthorized,
+            "restricted_resource" => ErrorCode::RestrictedResource,
+            "object_not_found" => ErrorCode::ObjectNotFound,
+            "conflict_error" => ErrorCode::ConflictError,
+            "rate_limited" => ErrorCode::RateLimited,
+            "internal_server_error" => ErrorCode::InternalServerError,
+            "bad_gateway" => ErrorCode::BadGateway,
+            "service_unavailable" => ErrorCode::ServiceUnavailable,
+            "database_connection_unavailable" => ErrorCode::DatabaseConnectionUnavailable,
+            "gateway_timeout" => ErrorCode::GatewayTimeout,
+            _ => ErrorCode::Other(code),
+        }
+    }
+}
+
+impl From<ErrorCode> for String {
+    fn from(code: ErrorCode) -> Self {
+        match code {
+            ErrorCode::Other(code) => code,
+            code => code.as_str().to_string(),
+        }
+    }
 }
 
 impl Display for ErrorCode {
@@ -53,13 +139,13 @@ impl Display for ErrorCode {
         &self,
         f: &mut Formatter<'_>,
     ) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+        f.write_str(self.as_str())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::models::error::{ErrorCode, ErrorResponse};
+    use crate::models::error::{ErrorCode, ErrorResponse, StatusCode};
 
     #[test]
     fn deserialize_error() {
@@ -71,6 +157,43 @@ mod tests {
     fn deserialize_unknown_error() {
         let error: ErrorResponse =
             serde_json::from_str(include_str!("tests/unknown_error.json")).unwrap();
-        assert_eq!(error.code, ErrorCode::Unknown)
+        assert_eq!(error.code, ErrorCode::Other("asadfsdfasd".to_string()))
+    }
+
+    #[test]
+    fn error_codes_round_trip() {
+        for code in [
+            ErrorCode::ValidationError,
+            ErrorCode::MissingVersion,
+            ErrorCode::ConflictError,
+            ErrorCode::Other("new_error".to_string()),
+        ] {
+            let json = serde_json::to_value(&code).unwrap();
+            assert_eq!(json, serde_json::Value::String(code.to_string()));
+            assert_eq!(serde_json::from_value::<ErrorCode>(json).unwrap(), code);
+        }
+    }
+
+    fn error_response(
+        status: u16,
+        code: ErrorCode,
+    ) -> ErrorResponse {
+        ErrorResponse {
+            status: StatusCode(status),
+            code,
+            message: "message".to_string(),
+            request_id: None,
+        }
+    }
+
+    #[test]
+    fn retryable_error_responses() {
+        assert!(error_response(429, ErrorCode::RateLimited).is_retryable());
+        assert!(error_response(502, ErrorCode::BadGateway).is_retryable());
+        assert!(error_response(503, ErrorCode::Other("new_error".to_string())).is_retryable());
+
+        assert!(!error_response(400, ErrorCode::ValidationError).is_retryable());
+        assert!(!error_response(401, ErrorCode::Unauthorized).is_retryable());
+        assert!(!error_response(404, ErrorCode::ObjectNotFound).is_retryable());
     }
 }
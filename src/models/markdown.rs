@@ -0,0 +1,1164 @@
+//! Converts between blocks and [CommonMark](https://commonmark.org/) flavoured markdown.
+//!
+//! When rendering, blocks that have no markdown equivalent (images, child pages, tables, ...)
+//! are skipped. Children are only rendered if they have been fetched, e.g. with
+//! [`NotionApi::get_block_children_recursive`](crate::NotionApi::get_block_children_recursive).
+//!
+//! [parse_blocks] goes the other way for the common subset of markdown: headings, paragraphs,
+//! lists, to-dos, block quotes, fenced code, dividers and inline emphasis, code and links.
+
+use crate::models::block::{Block, CodeLanguage, CreateBlock, FileOrEmojiObject};
+use crate::models::text::{RichText, RichTextBuilder};
+
+/// Renders a sequence of sibling blocks, numbering consecutive numbered list items.
+pub fn render_blocks(blocks: &[Block]) -> String {
+    let mut output = String::new();
+    let mut previous_list = None;
+    let mut number = 0;
+
+    for block in blocks {
+        let list = ListKind::of(block);
+        number = match (list, previous_list) {
+            (Some(ListKind::Numbered), Some(ListKind::Numbered)) => number + 1,
+            _ => 1,
+        };
+
+        let rendered = render_block(block, number);
+        if rendered.is_empty() {
+            continue;
+        }
+        if !output.is_empty() {
+            let same_list = list.is_some() && list == previous_list;
+            output.push_str(if same_list { "\n" } else { "\n\n" });
+        }
+        output.push_str(&rendered);
+        previous_list = list;
+    }
+
+    output
+}
+
+/// Renders rich text segments including their annotations and links.
+pub fn render_rich_text(rich_text: &[RichText]) -> String {
+    rich_text.iter().map(RichText::to_markdown).collect()
+}
+
+impl Block {
+    pub fn to_markdown(&self) -> String {
+        render_block(self, 1)
+    }
+}
+
+impl RichText {
+    pub fn to_markdown(&self) -> String {
+        match self {
+            RichText::Text { rich_text, text } => {
+                let annotations = rich_text.annotations.clone().unwrap_or_default();
+                let link = text
+                    .link
+                    .as_ref()
+                    .map(|link| link.url.as_str())
+                    .or(rich_text.href.as_deref());
+
+                annotate(
+                    &text.content,
+                    annotations.code.unwrap_or(false),
+                    &[
+                        (annotations.strikethrough.unwrap_or(false), "~~"),
+                        (annotations.italic.unwrap_or(false), "*"),
+                        (annotations.bold.unwrap_or(false), "**"),
+                    ],
+                    link,
+                )
+            }
+            RichText::Mention {
+                rich_text, href, ..
+            } => annotate(
+                &rich_text.plain_text,
+                false,
+                &[],
+                href.as_deref().or(rich_text.href.as_deref()),
+            ),
+            RichText::Equation { equation, .. } => format!("${}$", equation.expression),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+enum ListKind {
+    Bulleted,
+    Numbered,
+    ToDo,
+}
+
+impl ListKind {
+    fn of(block: &Block) -> Option<Self> {
+        match block {
+            Block::BulletedListItem { .. } => Some(ListKind::Bulleted),
+            Block::NumberedListItem { .. } => Some(ListKind::Numbered),
+            Block::ToDo { .. } => Some(ListKind::ToDo),
+            _ => None,
+        }
+    }
+}
+
+fn render_block(
+    block: &Block,
+    number: usize,
+) -> String {
+    let children = || render_blocks(block.children().unwrap_or_default());
+
+    match block {
+        Block::Paragraph { paragraph, .. } => {
+            followed_by(render_rich_text(&paragraph.rich_text), children())
+        }
+        Block::Toggle { toggle, .. } => {
+            followed_by(render_rich_text(&toggle.rich_text), children())
+        }
+        Block::Heading1 { heading_1, .. } => {
+            format!("# {}", render_rich_text(&heading_1.rich_text))
+        }
+        Block::Heading2 { heading_2, .. } => {
+            format!("## {}", render_rich_text(&heading_2.rich_text))
+        }
+        Block::Heading3 { heading_3, .. } => {
+            format!("### {}", render_rich_text(&heading_3.rich_text))
+        }
+        Block::BulletedListItem {
+            bulleted_list_item, ..
+        } => list_item(
+            "- ",
+            &render_rich_text(&bulleted_list_item.rich_text),
+            &children(),
+        ),
+        Block::NumberedListItem {
+            numbered_list_item, ..
+        } => list_item(
+            &format!("{}. ", number),
+            &render_rich_text(&numbered_list_item.rich_text),
+            &children(),
+        ),
+        Block::ToDo { to_do, .. } => list_item(
+            if to_do.checked { "- [x] " } else { "- [ ] " },
+            &render_rich_text(&to_do.rich_text),
+            &children(),
+        ),
+        Block::Quote { quote, .. } => {
+            quoted(&followed_by(render_rich_text(&quote.rich_text), children()))
+        }
+        Block::Callout { callout, .. } => {
+            let text = render_rich_text(&callout.rich_text);
+            let text = match &callout.icon {
+                FileOrEmojiObject::Emoji { emoji } => format!("{} {}", emoji, text),
+                _ => text,
+            };
+            quoted(&followed_by(text, children()))
+        }
+        Block::Code { code, .. } => {
+            let content: String = code.rich_text.iter().map(RichText::plain_text).collect();
+            let fence = "`".repeat((longest_run(&content, '`') + 1).max(3));
+            format!(
+                "{fence}{language}\n{content}\n{fence}",
+                fence = fence,
+                language = language_tag(&code.language),
+                content = content
+            )
+        }
+        Block::Equation { equation, .. } => format!("$$\n{}\n$$", equation.expression),
+        Block::Divider { .. } => "---".to_string(),
+        Block::Bookmark { bookmark, .. } if !bookmark.caption.is_empty() => format!(
+            "[{}]({})",
+            render_rich_text(&bookmark.caption),
+            bookmark.url
+        ),
+        Block::Bookmark { bookmark, .. } => format!("<{}>", bookmark.url),
+        Block::Embed { embed, .. } => format!("<{}>", embed.url),
+        Block::LinkPreview { link_preview, .. } => format!("<{}>", link_preview.url),
+        Block::ColumnList { .. }
+        | Block::Column { .. }
+        | Block::SyncedBlock { .. }
+        | Block::Template { .. } => children(),
+        _ => String::new(),
+    }
+}
+
+/// Wraps `content` in the enabled markers, keeping surrounding whitespace outside of them
+/// since markdown doesn't allow emphasis to start or end with a space.
+fn annotate(
+    content: &str,
+    code: bool,
+    markers: &[(bool, &str)],
+    link: Option<&str>,
+) -> String {
+    let trimmed = content.trim();
+    if trimmed.is_empty() {
+        return content.to_string();
+    }
+    let start = content.len() - content.trim_start().len();
+    let (leading, rest) = content.split_at(start);
+    let trailing = &rest[trimmed.len()..];
+
+    let mut text = if code {
+        let fence = "`".repeat(longest_run(trimmed, '`') + 1);
+        format!("{fence}{}{fence}", trimmed, fence = fence)
+    } else {
+        escape(trimmed)
+    };
+    for (enabled, marker) in markers {
+        if *enabled {
+            text = format!("{marker}{}{marker}", text, marker = marker);
+        }
+    }
+    if let Some(link) = link {
+        text = format!("[{}]({})", text, link);
+    }
+
+    format!("{}{}{}", leading, text, trailing)
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '`' | '~' | '[' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn longest_run(
+    text: &str,
+    needle: char,
+) -> usize {
+    let mut longest = 0;
+    let mut current = 0;
+    for c in text.chars() {
+        if c == needle {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    longest
+}
+
+fn language_tag(language: &CodeLanguage) -> String {
+    match language {
+        CodeLanguage::PlainText => String::new(),
+        language => serde_json::to_value(language)
+            .ok()
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_default(),
+    }
+}
+
+fn followed_by(
+    text: String,
+    children: String,
+) -> String {
+    match (text.is_empty(), children.is_empty()) {
+        (_, true) => text,
+        (true, false) => children,
+        (false, false) => format!("{}\n\n{}", text, children),
+    }
+}
+
+/// Puts `marker` in front of the first line and aligns the following lines and children with it.
+fn list_item(
+    marker: &str,
+    text: &str,
+    children: &str,
+) -> String {
+    let indent = " ".repeat(marker.len());
+    let mut output = marker.to_string();
+    output.push_str(indent_lines(text, &indent).trim_start());
+    if !children.is_empty() {
+        output.push('\n');
+        output.push_str(&indent_lines(children, &indent));
+    }
+    output
+}
+
+fn indent_lines(
+    text: &str,
+    indent: &str,
+) -> String {
+    text.split('\n')
+        .map(|line| {
+            if line.is_empty() {
+                String::new()
+            } else {
+                format!("{}{}", indent, line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn quoted(text: &str) -> String {
+    text.split('\n')
+        .map(|line| {
+            if line.is_empty() {
+                ">".to_string()
+            } else {
+                format!("> {}", line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Parses markdown into blocks that can be appended to a page.
+///
+/// Nested list items and block contents become children of their block. Line breaks within a
+/// paragraph are kept, since Notion displays them. Text longer than the API's limit is split
+/// into several segments. Constructs not listed in the [module documentation](self),
+/// such as tables or HTML, are kept as plain paragraphs.
+pub fn parse_blocks(markdown: &str) -> Vec<CreateBlock> {
+    let lines: Vec<&str> = markdown.lines().collect();
+    parse_lines(&lines)
+}
+
+fn parse_lines(lines: &[&str]) -> Vec<CreateBlock> {
+    let mut blocks = vec![];
+    let mut index = 0;
+
+    while index < lines.len() {
+        let line = lines[index];
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() {
+            index += 1;
+            continue;
+        }
+
+        let (block, consumed) = if let Some((marker, length)) = code_fence(trimmed) {
+            parse_code(&lines[index..], marker, length)
+        } else if let Some((level, text)) = heading(trimmed) {
+            let text = parse_inline(text);
+            let block = match level {
+                1 => CreateBlock::heading_1(text),
+                2 => CreateBlock::heading_2(text),
+                _ => CreateBlock::heading_3(text),
+            };
+            (block, 1)
+        } else if is_thematic_break(trimmed) {
+            (CreateBlock::divider(), 1)
+        } else if trimmed.starts_with('>') {
+            parse_quote(&lines[index..])
+        } else if let Some(marker) = list_marker(line) {
+            parse_list_item(&lines[index..], marker)
+        } else {
+            let length = paragraph_length(&lines[index..]);
+            let text = paragraph_text(&lines[index..index + length]);
+            (CreateBlock::paragraph(text), length)
+        };
+
+        blocks.push(block);
+        index += consumed;
+    }
+
+    blocks
+}
+
+fn parse_code(
+    lines: &[&str],
+    marker: char,
+    length: usize,
+) -> (CreateBlock, usize) {
+    let indent = indentation(lines[0]);
+    let info = lines[0].trim_start()[length..].trim();
+    let language = info
+        .split_whitespace()
+        .next()
+        .and_then(|tag| serde_json::from_value(tag.to_lowercase().into()).ok())
+        .unwrap_or(CodeLanguage::PlainText);
+
+    let body = &lines[1..];
+    let end = body.iter().position(|line| {
+        let trimmed = line.trim();
+        run_length(trimmed, marker) >= length && trimmed.chars().all(|c| c == marker)
+    });
+    let content = body[..end.unwrap_or(body.len())]
+        .iter()
+        .map(|line| dedent(line, indent))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let consumed = 1 + end.map_or(body.len(), |end| end + 1);
+    (CreateBlock::code(content, language), consumed)
+}
+
+fn parse_quote(lines: &[&str]) -> (CreateBlock, usize) {
+    let content: Vec<&str> = lines
+        .iter()
+        .map_while(|line| line.trim_start().strip_prefix('>'))
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .collect();
+
+    let (text, children) = text_and_children(&content);
+    (nest(CreateBlock::quote(text), children), content.len())
+}
+
+struct ListMarker<'a> {
+    numbered: bool,
+    /// The column the item's content starts at, which continuation lines are aligned with.
+    indent: usize,
+    text: &'a str,
+}
+
+fn parse_list_item(
+    lines: &[&str],
+    marker: ListMarker,
+) -> (CreateBlock, usize) {
+    let mut content = vec![marker.text];
+    let mut index = 1;
+
+    while index < lines.len() {
+        let line = lines[index];
+        if line.trim().is_empty() {
+            // Blank lines only belong to the item if it continues after them.
+            match lines[index..]
+                .iter()
+                .position(|line| !line.trim().is_empty())
+            {
+                Some(blank) if indentation(lines[index + blank]) >= marker.indent => {
+                    content.extend(std::iter::repeat_n("", blank));
+                    index += blank;
+                }
+                _ => break,
+            }
+        } else if indentation(line) >= marker.indent {
+            content.push(dedent(line, marker.indent));
+            index += 1;
+        } else if !starts_block(line) && content.last().is_some_and(|last| !last.is_empty()) {
+            // A lazy continuation of the item's text.
+            content.push(line.trim_start());
+            index += 1;
+        } else {
+            break;
+        }
+    }
+
+    let checked = match content[0].get(..3) {
+        Some("[ ]") => Some(false),
+        Some("[x]") | Some("[X]") => Some(true),
+        _ => None,
+    }
+    .filter(|_| !marker.numbered)
+    .filter(|_| content[0][3..].is_empty() || content[0][3..].starts_with(' '));
+    if checked.is_some() {
+        content[0] = content[0][3..].trim_start();
+    }
+
+    let (text, children) = text_and_children(&content);
+    let block = match checked {
+        _ if marker.numbered => CreateBlock::numbered_list_item(text),
+        Some(checked) => CreateBlock::to_do(text, checked),
+        None => CreateBlock::bulleted_list_item(text),
+    };
+    (nest(block, children), index)
+}
+
+/// Splits the content of a list item or quote into its leading text and the blocks following it.
+fn text_and_children(lines: &[&str]) -> (Vec<RichText>, Vec<CreateBlock>) {
+    let length = match lines.first() {
+        Some(first) if starts_block(first) => 0,
+        _ => paragraph_length(lines),
+    };
+    (
+        paragraph_text(&lines[..length]),
+        parse_lines(&lines[length..]),
+    )
+}
+
+fn nest(
+    block: CreateBlock,
+    children: Vec<CreateBlock>,
+) -> CreateBlock {
+    if children.is_empty() {
+        block
+    } else {
+        block.with_children(children)
+    }
+}
+
+/// The number of lines up to a blank line or a line starting another block.
+fn paragraph_length(lines: &[&str]) -> usize {
+    let mut length = 0;
+    for line in lines {
+        if line.trim().is_empty() || (length > 0 && starts_block(line)) {
+            break;
+        }
+        length += 1;
+    }
+    length
+}
+
+fn paragraph_text(lines: &[&str]) -> Vec<RichText> {
+    let text = lines
+        .iter()
+        .map(|line| line.trim())
+        .collect::<Vec<_>>()
+        .join("\n");
+    parse_inline(&text)
+}
+
+fn starts_block(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    code_fence(trimmed).is_some()
+        || heading(trimmed).is_some()
+        || is_thematic_break(trimmed)
+        || trimmed.starts_with('>')
+        || list_marker(line).is_some()
+}
+
+fn code_fence(trimmed: &str) -> Option<(char, usize)> {
+    let marker = trimmed.chars().next().filter(|c| matches!(c, '`' | '~'))?;
+    let length = run_length(trimmed, marker);
+    let info = &trimmed[length..];
+    // Backticks in the info string would make this an inline code span instead.
+    (length >= 3 && !(marker == '`' && info.contains('`'))).then_some((marker, length))
+}
+
+fn heading(trimmed: &str) -> Option<(usize, &str)> {
+    let level = run_length(trimmed, '#');
+    let rest = &trimmed[level..];
+    if !(1..=6).contains(&level) || !(rest.is_empty() || rest.starts_with([' ', '\t'])) {
+        return None;
+    }
+
+    let text = rest.trim();
+    let without_closing = text.trim_end_matches('#');
+    if without_closing.is_empty() || without_closing.ends_with([' ', '\t']) {
+        Some((level, without_closing.trim_end()))
+    } else {
+        Some((level, text))
+    }
+}
+
+fn is_thematic_break(trimmed: &str) -> bool {
+    match trimmed.chars().next() {
+        Some(marker @ ('-' | '*' | '_')) => {
+            trimmed
+                .chars()
+                .all(|c| c == marker || c == ' ' || c == '\t')
+                && trimmed.chars().filter(|c| *c == marker).count() >= 3
+        }
+        _ => false,
+    }
+}
+
+fn list_marker(line: &str) -> Option<ListMarker<'_>> {
+    let trimmed = line.trim_start();
+    let (numbered, marker_length) = match trimmed.chars().next()? {
+        '-' | '*' | '+' => (false, 1),
+        _ => {
+            let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+            if !(1..=9).contains(&digits) || !trimmed[digits..].starts_with(['.', ')']) {
+                return None;
+            }
+            (true, digits + 1)
+        }
+    };
+
+    let rest = &trimmed[marker_length..];
+    if !(rest.is_empty() || rest.starts_with(' ')) {
+        return None;
+    }
+    // More than four spaces after the marker would start indented code, which isn't supported,
+    // so the content is taken to start after the first one.
+    let spaces = match run_length(rest, ' ') {
+        spaces if spaces > 4 || spaces == rest.len() => rest.len().min(1),
+        spaces => spaces,
+    };
+
+    Some(ListMarker {
+        numbered,
+        indent: indentation(line) + marker_length + spaces,
+        text: &rest[spaces..],
+    })
+}
+
+/// The width of the leading whitespace, counting tabs as four spaces.
+fn indentation(line: &str) -> usize {
+    line.chars()
+        .map_while(|c| match c {
+            ' ' => Some(1),
+            '\t' => Some(4),
+            _ => None,
+        })
+        .sum()
+}
+
+/// Removes up to `width` columns of leading whitespace.
+fn dedent(
+    line: &str,
+    width: usize,
+) -> &str {
+    let mut removed = 0;
+    for (index, c) in line.char_indices() {
+        if removed >= width || !matches!(c, ' ' | '\t') {
+            return &line[index..];
+        }
+        removed += if c == '\t' { 4 } else { 1 };
+    }
+    ""
+}
+
+fn run_length(
+    text: &str,
+    needle: char,
+) -> usize {
+    text.chars().take_while(|c| *c == needle).count() * needle.len_utf8()
+}
+
+/// The annotations and link that apply to a piece of inline text.
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+struct Style {
+    bold: bool,
+    italic: bool,
+    strikethrough: bool,
+    code: bool,
+    link: Option<String>,
+}
+
+impl Style {
+    fn flag(
+        &mut self,
+        delimiter: &str,
+    ) -> &mut bool {
+        match delimiter {
+            "**" | "__" => &mut self.bold,
+            "~~" => &mut self.strikethrough,
+            _ => &mut self.italic,
+        }
+    }
+
+    fn apply(
+        self,
+        mut builder: RichTextBuilder,
+    ) -> RichTextBuilder {
+        if self.bold {
+            builder = builder.bold();
+        }
+        if self.italic {
+            builder = builder.italic();
+        }
+        if self.strikethrough {
+            builder = builder.strikethrough();
+        }
+        if self.code {
+            builder = builder.code();
+        }
+        if let Some(url) = self.link {
+            builder = builder.link(url);
+        }
+        builder
+    }
+}
+
+const DELIMITERS: [&str; 5] = ["**", "__", "~~", "*", "_"];
+
+/// Parses emphasis, strikethrough, code spans and links into annotated rich text.
+fn parse_inline(text: &str) -> Vec<RichText> {
+    let mut segments = vec![];
+    inline_segments(text, &Style::default(), &mut segments);
+    segments
+        .into_iter()
+        .fold(RichTextBuilder::new(), |builder, (content, style)| {
+            style.apply(builder.text(content))
+        })
+        .build()
+}
+
+fn inline_segments(
+    text: &str,
+    outer: &Style,
+    segments: &mut Vec<(String, Style)>,
+) {
+    let mut style = outer.clone();
+    let mut buffer = String::new();
+    let mut rest = text;
+
+    'text: while let Some(c) = rest.chars().next() {
+        let previous = text[..text.len() - rest.len()].chars().next_back();
+
+        match c {
+            '\\' => match rest[1..].chars().next() {
+                Some(escaped) if escaped.is_ascii_punctuation() => {
+                    buffer.push(escaped);
+                    rest = &rest[2..];
+                    continue;
+                }
+                // A backslash at the end of a line is a hard line break.
+                Some('\n') => {
+                    rest = &rest[1..];
+                    continue;
+                }
+                _ => {}
+            },
+            '`' => {
+                let fence = run_length(rest, '`');
+                match code_span(rest, fence) {
+                    Some((content, after)) => {
+                        push_segment(segments, std::mem::take(&mut buffer), &style);
+                        let code = Style {
+                            code: true,
+                            ..style.clone()
+                        };
+                        push_segment(segments, content.to_string(), &code);
+                        rest = after;
+                    }
+                    None => {
+                        buffer.push_str(&rest[..fence]);
+                        rest = &rest[fence..];
+                    }
+                }
+                continue;
+            }
+            '[' => {
+                if let Some((label, url, after)) = link(rest) {
+                    push_segment(segments, std::mem::take(&mut buffer), &style);
+                    let linked = Style {
+                        link: Some(url.to_string()),
+                        ..style.clone()
+                    };
+                    inline_segments(label, &linked, segments);
+                    rest = after;
+                    continue;
+                }
+            }
+            _ => {
+                for delimiter in DELIMITERS {
+                    if let Some(after) = rest.strip_prefix(delimiter) {
+                        let enabled = *style.flag(delimiter);
+                        let next = after.chars().next();
+                        // Underscores within words, as in snake_case, are no emphasis.
+                        let intraword = delimiter.starts_with('_');
+
+                        let closes = enabled
+                            && previous.is_some_and(|c| !c.is_whitespace())
+                            && !(intraword && next.is_some_and(char::is_alphanumeric));
+                        let opens = !enabled
+                            && next.is_some_and(|c| !c.is_whitespace())
+                            && !(intraword && previous.is_some_and(char::is_alphanumeric))
+                            && has_closing(after, delimiter);
+
+                        if closes || opens {
+                            push_segment(segments, std::mem::take(&mut buffer), &style);
+                            *style.flag(delimiter) = !enabled;
+                            rest = after;
+                            continue 'text;
+                        }
+                    }
+                }
+            }
+        }
+
+        buffer.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    push_segment(segments, buffer, &style);
+}
+
+/// Appends `content`, merging it into the previous segment if that has the same style.
+fn push_segment(
+    segments: &mut Vec<(String, Style)>,
+    content: String,
+    style: &Style,
+) {
+    if content.is_empty() {
+        return;
+    }
+    match segments.last_mut() {
+        Some((previous, previous_style)) if previous_style == style => previous.push_str(&content),
+        _ => segments.push((content, style.clone())),
+    }
+}
+
+/// A code span opened by `fence` backticks, and the text following it.
+fn code_span(
+    text: &str,
+    fence: usize,
+) -> Option<(&str, &str)> {
+    let body = &text[fence..];
+    let mut offset = 0;
+
+    while let Some(found) = body[offset..].find('`') {
+        let start = offset + found;
+        let run = run_length(&body[start..], '`');
+        if run == fence {
+            let content = &body[..start];
+            let content = match content.strip_prefix(' ').and_then(|c| c.strip_suffix(' ')) {
+                Some(inner) if !inner.trim().is_empty() => inner,
+                _ => content,
+            };
+            return Some((content, &body[start + run..]));
+        }
+        offset = start + run;
+    }
+    None
+}
+
+/// An inline link `[label](url)`: its label, url and the text following it.
+fn link(text: &str) -> Option<(&str, &str, &str)> {
+    let mut depth = 0;
+    let mut escaped = false;
+    let mut label_end = None;
+
+    for (index, c) in text.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    label_end = Some(index);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let label_end = label_end?;
+    let destination = text[label_end + 1..].strip_prefix('(')?;
+    let destination_end = destination.find(')')?;
+    let url = destination[..destination_end]
+        .split_whitespace()
+        .next()
+        .unwrap_or_default();
+    let url = url
+        .strip_prefix('<')
+        .and_then(|url| url.strip_suffix('>'))
+        .unwrap_or(url);
+
+    Some((
+        &text[1..label_end],
+        url,
+        &destination[destination_end + 1..],
+    ))
+}
+
+fn has_closing(
+    text: &str,
+    delimiter: &str,
+) -> bool {
+    text.match_indices(delimiter)
+        .any(|(index, _)| index > 0 && !text[..index].ends_with(char::is_whitespace))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_blocks, parse_inline, render_blocks, render_rich_text};
+    use crate::models::block::{Block, CodeLanguage, CreateBlock};
+    use crate::models::text::{RichText, RichTextBuilder, MAX_TEXT_CONTENT_LENGTH};
+    use serde_json::{json, Value};
+
+    fn text(
+        content: &str,
+        annotations: Value,
+        link: Option<&str>,
+    ) -> Value {
+        json!({
+            "type": "text",
+            "text": {"content": content, "link": link.map(|url| json!({"url": url}))},
+            "annotations": annotations,
+            "plain_text": content,
+            "href": link
+        })
+    }
+
+    fn plain(content: &str) -> Value {
+        text(content, json!({}), None)
+    }
+
+    fn block(
+        kind: &str,
+        fields: Value,
+    ) -> Value {
+        json!({
+            "object": "block",
+            "id": "0c7a7c9a-0000-4000-8000-000000000000",
+            "type": kind,
+            "created_time": "2021-05-15T17:16:00.000Z",
+            "last_edited_time": "2021-05-15T17:16:00.000Z",
+            "created_by": {"object": "user", "id": "e2507360-468c-4e0f-a928-7bbcbbb45353"},
+            "last_edited_by": {"object": "user", "id": "e2507360-468c-4e0f-a928-7bbcbbb45353"},
+            "has_children": false,
+            kind: fields
+        })
+    }
+
+    fn blocks(blocks: Vec<Value>) -> Vec<Block> {
+        serde_json::from_value(Value::Array(blocks)).unwrap()
+    }
+
+    #[test]
+    fn annotations() {
+        let rich_text: Vec<RichText> = serde_json::from_value(json!([
+            text("Bold ", json!({"bold": true}), None),
+            text("italic", json!({"italic": true}), None),
+            plain(", "),
+            text("code", json!({"code": true}), None),
+            plain(" and "),
+            text("a link", json!({"bold": true}), Some("https://example.com")),
+            plain(" with *stars*"),
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            render_rich_text(&rich_text),
+            "**Bold** *italic*, `code` and [**a link**](https://example.com) with \\*stars\\*"
+        );
+    }
+
+    fn render_annotated(annotations: Value) -> String {
+        let rich_text: RichText = serde_json::from_value(text("text", annotations, None)).unwrap();
+        rich_text.to_markdown()
+    }
+
+    #[test]
+    fn single_annotations() {
+        assert_eq!(render_annotated(json!({"bold": true})), "**text**");
+        assert_eq!(render_annotated(json!({"italic": true})), "*text*");
+        assert_eq!(render_annotated(json!({"code": true})), "`text`");
+        assert_eq!(render_annotated(json!({"strikethrough": true})), "~~text~~");
+        assert_eq!(render_annotated(json!({"underline": true})), "text");
+    }
+
+    #[test]
+    fn combined_annotations_nest() {
+        assert_eq!(
+            render_annotated(json!({"bold": true, "italic": true})),
+            "***text***"
+        );
+        assert_eq!(
+            render_annotated(json!({"bold": true, "italic": true, "strikethrough": true})),
+            "***~~text~~***"
+        );
+        assert_eq!(
+            render_annotated(json!({"bold": true, "code": true})),
+            "**`text`**"
+        );
+    }
+
+    #[test]
+    fn headings_paragraphs_and_code() {
+        let blocks = blocks(vec![
+            block("heading_1", json!({"rich_text": [plain("One")]})),
+            block("heading_2", json!({"rich_text": [plain("Two")]})),
+            block("heading_3", json!({"rich_text": [plain("Three")]})),
+            block(
+                "paragraph",
+                json!({"rich_text": [plain("Some text")], "color": "default"}),
+            ),
+            block("divider", json!({})),
+            block(
+                "quote",
+                json!({"rich_text": [plain("Quoted\nover lines")], "color": "default"}),
+            ),
+            block(
+                "code",
+                json!({"rich_text": [plain("fn main() {}")], "caption": [], "language": "rust"}),
+            ),
+        ]);
+
+        assert_eq!(
+            render_blocks(&blocks),
+            "# One\n\n## Two\n\n### Three\n\nSome text\n\n---\n\n> Quoted\n> over lines\n\n```rust\nfn main() {}\n```"
+        );
+    }
+
+    #[test]
+    fn nested_lists() {
+        let mut items = blocks(vec![
+            block(
+                "numbered_list_item",
+                json!({"rich_text": [plain("First")], "color": "default"}),
+            ),
+            block(
+                "numbered_list_item",
+                json!({"rich_text": [plain("Second")], "color": "default"}),
+            ),
+            block(
+                "to_do",
+                json!({"rich_text": [plain("Done")], "checked": true, "color": "default"}),
+            ),
+            block(
+                "to_do",
+                json!({"rich_text": [plain("Todo")], "checked": false, "color": "default"}),
+            ),
+        ]);
+        let nested = blocks(vec![
+            block(
+                "bulleted_list_item",
+                json!({"rich_text": [plain("Nested")], "color": "default"}),
+            ),
+            block(
+                "bulleted_list_item",
+                json!({"rich_text": [plain("Bullets")], "color": "default"}),
+            ),
+        ]);
+        *items[1].children_mut().unwrap() = Some(nested);
+
+        assert_eq!(
+            render_blocks(&items),
+            "1. First\n2. Second\n   - Nested\n   - Bullets\n\n- [x] Done\n- [ ] Todo"
+        );
+    }
+
+    #[test]
+    fn parse_headings_and_paragraphs() {
+        assert_eq!(
+            parse_blocks("# One\n\n## Two ##\n#### Four\n\nSome text\nover lines\n\n---\n\nNext"),
+            vec![
+                CreateBlock::heading_1("One"),
+                CreateBlock::heading_2("Two"),
+                CreateBlock::heading_3("Four"),
+                CreateBlock::paragraph("Some text\nover lines"),
+                CreateBlock::divider(),
+                CreateBlock::paragraph("Next"),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_lists() {
+        assert_eq!(
+            parse_blocks("- One\n* Two\n  - Nested\n  continued\n\n1. First\n2) Second\n- [ ] Todo\n- [x] Done"),
+            vec![
+                CreateBlock::bulleted_list_item("One"),
+                CreateBlock::bulleted_list_item("Two").with_children(vec![
+                    CreateBlock::bulleted_list_item("Nested\ncontinued")
+                ]),
+                CreateBlock::numbered_list_item("First"),
+                CreateBlock::numbered_list_item("Second"),
+                CreateBlock::to_do("Todo", false),
+                CreateBlock::to_do("Done", true),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_list_items_with_several_paragraphs() {
+        assert_eq!(
+            parse_blocks("1. First\n\n   More on the first\n2. Second"),
+            vec![
+                CreateBlock::numbered_list_item("First")
+                    .with_children(vec![CreateBlock::paragraph("More on the first")]),
+                CreateBlock::numbered_list_item("Second"),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_code_fences() {
+        assert_eq!(
+            parse_blocks(
+                "```rust\nfn main() {\n    println!(\"# not a heading\");\n}\n```\n\n~~~\nplain\n~~~\n\n```C++ extra\nint x;\n```\n\n```not-a-language\nunclosed"
+            ),
+            vec![
+                CreateBlock::code(
+                    "fn main() {\n    println!(\"# not a heading\");\n}",
+                    CodeLanguage::Rust
+                ),
+                CreateBlock::code("plain", CodeLanguage::PlainText),
+                CreateBlock::code("int x;", CodeLanguage::CPlusPlus),
+                CreateBlock::code("unclosed", CodeLanguage::PlainText),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_quotes() {
+        assert_eq!(
+            parse_blocks("> Quoted\n> over lines\n>\n> - with a list\n\nAfter"),
+            vec![
+                CreateBlock::quote("Quoted\nover lines")
+                    .with_children(vec![CreateBlock::bulleted_list_item("with a list")]),
+                CreateBlock::paragraph("After"),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_inline_annotations() {
+        assert_eq!(
+            parse_inline("**Bold** *italic* _also_ `code` ~~gone~~ [**a link**](https://example.com) snake_case 2 * 3"),
+            RichTextBuilder::new()
+                .text("Bold")
+                .bold()
+                .text(" ")
+                .text("italic")
+                .italic()
+                .text(" ")
+                .text("also")
+                .italic()
+                .text(" ")
+                .text("code")
+                .code()
+                .text(" ")
+                .text("gone")
+                .strikethrough()
+                .text(" ")
+                .text("a link")
+                .bold()
+                .link("https://example.com")
+                .text(" snake_case 2 * 3")
+                .build()
+        );
+    }
+
+    #[test]
+    fn parse_nested_annotations_and_escapes() {
+        assert_eq!(
+            parse_inline("***both*** **`bold code`** \\*not italic\\* `` a`b ``"),
+            RichTextBuilder::new()
+                .text("both")
+                .bold()
+                .italic()
+                .text(" ")
+                .text("bold code")
+                .bold()
+                .code()
+                .text(" *not italic* ")
+                .text("a`b")
+                .code()
+                .build()
+        );
+    }
+
+    #[test]
+    fn parse_rendered_rich_text() {
+        let markdown =
+            "**Bold** *italic*, `code` and [**a link**](https://example.com) with \\*stars\\*";
+        assert_eq!(render_rich_text(&parse_inline(markdown)), markdown);
+    }
+
+    #[test]
+    fn parse_long_paragraphs_into_chunks() {
+        let text = "word ".repeat(1000);
+        let blocks = parse_blocks(&text);
+
+        let rich_text = match &blocks[..] {
+            [CreateBlock::Paragraph { paragraph }] => &paragraph.rich_text,
+            other => panic!("Unexpected blocks {:?}", other),
+        };
+        assert_eq!(rich_text.len(), 3);
+        assert!(rich_text
+            .iter()
+            .all(|text| text.plain_text().len() <= MAX_TEXT_CONTENT_LENGTH));
+        assert_eq!(
+            rich_text
+                .iter()
+                .map(RichText::plain_text)
+                .collect::<String>(),
+            text.trim()
+        );
+    }
+}
@@ -1,5 +1,7 @@
 pub mod block;
+pub mod comments;
 pub mod error;
+pub mod markdown;
 pub mod paging;
 pub mod properties;
 pub mod search;
@@ -7,29 +9,26 @@ pub mod search;
 mod tests;
 pub mod text;
 pub mod users;
+pub mod validation;
 
-use crate::models::properties::{PropertyConfiguration, PropertyItem, PropertyValue};
-use crate::models::text::RichText;
+use crate::models::properties::{
+    DateValue, PropertyConfiguration, PropertyItem, PropertySchema, PropertyValue,
+};
+use crate::models::text::{IntoRichText, RichText};
 use crate::Error;
 use serde::{Deserialize, Serialize, Serializer};
 use std::collections::{BTreeMap, HashMap};
 use std::hash::{Hash, Hasher};
 
 use crate::ids::{AsIdentifier, BlockId, DatabaseId, PageId};
-use crate::models::block::{Block, CreateBlock, FileOrEmojiObject};
+use crate::models::block::{Block, CreateBlock, FileObject, FileOrEmojiObject};
+use crate::models::comments::Comment;
 use crate::models::error::ErrorResponse;
-use crate::models::paging::PagingCursor;
+use crate::models::paging::{Paging, PagingCursor};
 use crate::models::users::User;
 pub use serde_json::value::Number;
 pub use time::{Date, OffsetDateTime};
 
-#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
-#[serde(rename_all = "snake_case")]
-enum ObjectType {
-    Database,
-    List,
-}
-
 /// Represents a Notion Database
 /// See <https://developers.notion.com/reference/database>
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
@@ -53,6 +52,26 @@ pub struct Database {
     // A Property object.
     #[serde(serialize_with = "ordered_map")]
     pub properties: HashMap<String, PropertyConfiguration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cover: Option<FileObject>,
+    // The fields below are missing from payloads of older API versions.
+    /// Description of the database as it appears in Notion.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub description: Vec<RichText>,
+    /// Whether the database is shown inline in its parent page rather than as a page of its own.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_inline: Option<bool>,
+    /// The archived status of the database.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archived: Option<bool>,
+    /// Whether the database has been moved to the trash, only sent by newer API versions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_trash: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Set if the database is published to the web.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_url: Option<String>,
 }
 
 impl Hash for Database {
@@ -91,6 +110,30 @@ impl<T> ListResponse<T> {
     pub fn results(&self) -> &[T] {
         &self.results
     }
+
+    /// The paging parameters to request the next page of results,
+    /// or `None` if this was the last page.
+    pub fn next_paging(&self) -> Option<Paging> {
+        if !self.has_more {
+            return None;
+        }
+        self.next_cursor.clone().map(|cursor| Paging {
+            start_cursor: Some(cursor),
+            page_size: None,
+        })
+    }
+}
+
+/// Lists of a single type of object leave out the objects of types added to the API after this
+/// version of the crate. They're logged so they don't go missing without a trace.
+fn is_known(object: &Object) -> bool {
+    match object {
+        Object::Unknown(value) => {
+            tracing::warn!("Skipping an object of an unknown type: {}", value);
+            false
+        }
+        _ => true,
+    }
 }
 
 impl ListResponse<Object> {
@@ -111,13 +154,14 @@ impl ListResponse<Object> {
         }
     }
 
-    pub(crate) fn expect_databases(self) -> Result<ListResponse<Database>, crate::Error> {
+    pub(crate) fn expect_databases(self) -> Result<ListResponse<Database>, Object> {
         let databases: Result<Vec<_>, _> = self
             .results
             .into_iter()
+            .filter(is_known)
             .map(|object| match object {
                 Object::Database { database } => Ok(database),
-                response => Err(Error::UnexpectedResponse { response }),
+                response => Err(response),
             })
             .collect();
 
@@ -128,13 +172,50 @@ impl ListResponse<Object> {
         })
     }
 
-    pub(crate) fn expect_pages(self) -> Result<ListResponse<Page>, crate::Error> {
+    pub(crate) fn expect_pages(self) -> Result<ListResponse<Page>, Object> {
         let items: Result<Vec<_>, _> = self
             .results
             .into_iter()
+            .filter(is_known)
             .map(|object| match object {
                 Object::Page { page } => Ok(page),
-                response => Err(Error::UnexpectedResponse { response }),
+                response => Err(response),
+            })
+            .collect();
+
+        Ok(ListResponse {
+            results: items?,
+            has_more: self.has_more,
+            next_cursor: self.next_cursor,
+        })
+    }
+
+    pub(crate) fn expect_comments(self) -> Result<ListResponse<Comment>, Object> {
+        let items: Result<Vec<_>, _> = self
+            .results
+            .into_iter()
+            .filter(is_known)
+            .map(|object| match object {
+                Object::Comment { comment } => Ok(comment),
+                response => Err(response),
+            })
+            .collect();
+
+        Ok(ListResponse {
+            results: items?,
+            has_more: self.has_more,
+            next_cursor: self.next_cursor,
+        })
+    }
+
+    pub(crate) fn expect_users(self) -> Result<ListResponse<User>, Object> {
+        let items: Result<Vec<_>, _> = self
+            .results
+            .into_iter()
+            .filter(is_known)
+            .map(|object| match object {
+                Object::User { user } => Ok(user),
+                response => Err(response),
             })
             .collect();
 
@@ -145,13 +226,32 @@ impl ListResponse<Object> {
         })
     }
 
-    pub(crate) fn expect_blocks(self) -> Result<ListResponse<Block>, crate::Error> {
+    pub(crate) fn expect_property_items(self) -> Result<ListResponse<PropertyItem>, Object> {
         let items: Result<Vec<_>, _> = self
             .results
             .into_iter()
+            .filter(is_known)
+            .map(|object| match object {
+                Object::PropertyItem { property_item } => Ok(property_item),
+                response => Err(response),
+            })
+            .collect();
+
+        Ok(ListResponse {
+            results: items?,
+            has_more: self.has_more,
+            next_cursor: self.next_cursor,
+        })
+    }
+
+    pub(crate) fn expect_blocks(self) -> Result<ListResponse<Block>, Object> {
+        let items: Result<Vec<_>, _> = self
+            .results
+            .into_iter()
+            .filter(is_known)
             .map(|object| match object {
                 Object::Block { block } => Ok(block),
-                response => Err(Error::UnexpectedResponse { response }),
+                response => Err(response),
             })
             .collect();
 
@@ -163,16 +263,70 @@ impl ListResponse<Object> {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
-#[serde(tag = "object")]
-#[serde(rename_all = "snake_case")]
+/// A page property retrieved on its own.
+/// See <https://developers.notion.com/reference/retrieve-a-page-property>
+#[derive(Debug, Eq, PartialEq, Clone)]
+#[allow(clippy::large_enum_variant)]
 pub enum PropertyResponse {
-    List {
-        results: ListResponse<PropertyValue>,
-    },
-    PropertyItem {
-        property_item: PropertyValue,
-    },
+    /// A property holding a single value, like a number or a select.
+    PropertyItem(PropertyItem),
+    /// Every item of a paginated property, like a title, relation or people property,
+    /// collected from all pages of the response.
+    List(Vec<PropertyItem>),
+}
+
+impl PropertyResponse {
+    /// The property items as page property values, one per item.
+    pub fn into_values(self) -> Vec<PropertyValue> {
+        match self {
+            PropertyResponse::PropertyItem(item) => vec![item.into()],
+            PropertyResponse::List(items) => items.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Combines the items of a paginated property into a single value,
+    /// e.g. a relation with all related pages.
+    /// Returns `None` for an empty list, which doesn't tell the type of the property.
+    pub fn into_value(self) -> Option<PropertyValue> {
+        let mut values = self.into_values().into_iter();
+        let mut combined = values.next()?;
+        for value in values {
+            combined = append_value(combined, value);
+        }
+        Some(combined)
+    }
+}
+
+/// Appends the elements of `value` to those of `combined` if both are the same kind of list.
+fn append_value(
+    combined: PropertyValue,
+    value: PropertyValue,
+) -> PropertyValue {
+    use PropertyValue::*;
+    match (combined, value) {
+        (Title { id, mut title }, Title { title: more, .. }) => {
+            title.extend(more);
+            Title { id, title }
+        }
+        (
+            Text { id, mut rich_text },
+            Text {
+                rich_text: more, ..
+            },
+        ) => {
+            rich_text.extend(more);
+            Text { id, rich_text }
+        }
+        (Relation { id, relation }, Relation { relation: more, .. }) => Relation {
+            id,
+            relation: Some(relation.into_iter().chain(more).flatten().collect()),
+        },
+        (People { id, mut people }, People { people: more, .. }) => {
+            people.extend(more);
+            People { id, people }
+        }
+        (combined, _) => combined,
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
@@ -194,6 +348,35 @@ pub enum Parent {
     Workspace,
 }
 
+impl Parent {
+    pub fn as_page_id(&self) -> Option<&PageId> {
+        match self {
+            Parent::Page { page_id } => Some(page_id),
+            _ => None,
+        }
+    }
+
+    pub fn as_database_id(&self) -> Option<&DatabaseId> {
+        match self {
+            Parent::Database { database_id } => Some(database_id),
+            _ => None,
+        }
+    }
+
+    /// Only set for pages and databases nested in a block, like a column, rather than a page.
+    pub fn as_block_id(&self) -> Option<&BlockId> {
+        match self {
+            Parent::Block { block_id } => Some(block_id),
+            _ => None,
+        }
+    }
+
+    /// Whether this is a top level page or database of the workspace.
+    pub fn is_workspace(&self) -> bool {
+        matches!(self, Parent::Workspace)
+    }
+}
+
 fn ordered_map<S, K: Ord + Serialize, V: Serialize>(
     value: &HashMap<K, V>,
     serializer: S,
@@ -226,6 +409,178 @@ impl Properties {
             _ => None,
         })
     }
+
+    /// The rich text of the text property `name`, a long text column of a database.
+    /// Returns `None` if it is missing or not a text property.
+    pub fn get_rich_text(
+        &self,
+        name: &str,
+    ) -> Option<&Vec<RichText>> {
+        match self.properties.get(name)? {
+            PropertyValue::Text { rich_text, .. } => Some(rich_text),
+            _ => None,
+        }
+    }
+
+    /// The plain text of the text property `name`, see [Properties::get_rich_text].
+    pub fn get_text(
+        &self,
+        name: &str,
+    ) -> Option<String> {
+        self.get_rich_text(name)
+            .map(|rich_text| rich_text.iter().map(RichText::plain_text).collect())
+    }
+
+    /// The value of the number property `name`.
+    /// Returns `None` if it is empty, missing or not a number property.
+    pub fn get_number(
+        &self,
+        name: &str,
+    ) -> Option<f64> {
+        match self.properties.get(name)? {
+            PropertyValue::Number { number, .. } => number.as_ref()?.as_f64(),
+            _ => None,
+        }
+    }
+
+    /// The name of the selected option of the select property `name`.
+    pub fn get_select(
+        &self,
+        name: &str,
+    ) -> Option<&str> {
+        match self.properties.get(name)? {
+            PropertyValue::Select { select, .. } => select.as_ref()?.name.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// The names of the selected options of the multi select property `name`,
+    /// empty if it isn't a multi select property.
+    pub fn get_multi_select(
+        &self,
+        name: &str,
+    ) -> Vec<&str> {
+        match self.properties.get(name) {
+            Some(PropertyValue::MultiSelect {
+                multi_select: Some(selected),
+                ..
+            }) => selected
+                .iter()
+                .filter_map(|value| value.name.as_deref())
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    pub fn get_checkbox(
+        &self,
+        name: &str,
+    ) -> Option<bool> {
+        match self.properties.get(name)? {
+            PropertyValue::Checkbox { checkbox, .. } => Some(*checkbox),
+            _ => None,
+        }
+    }
+
+    pub fn get_date(
+        &self,
+        name: &str,
+    ) -> Option<&DateValue> {
+        match self.properties.get(name)? {
+            PropertyValue::Date { date, .. } => date.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn get_url(
+        &self,
+        name: &str,
+    ) -> Option<&str> {
+        match self.properties.get(name)? {
+            PropertyValue::Url { url, .. } => url.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn get_email(
+        &self,
+        name: &str,
+    ) -> Option<&str> {
+        match self.properties.get(name)? {
+            PropertyValue::Email { email, .. } => email.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn get_phone(
+        &self,
+        name: &str,
+    ) -> Option<&str> {
+        match self.properties.get(name)? {
+            PropertyValue::PhoneNumber { phone_number, .. } => phone_number.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Conversion of the properties of a database row into a type of your own,
+/// see [`NotionApi::query_database_into`](crate::NotionApi::query_database_into).
+///
+/// ```
+/// use notion::models::{FromProperties, FromPropertiesError, Properties};
+///
+/// struct Task {
+///     name: String,
+///     done: bool,
+/// }
+///
+/// impl FromProperties for Task {
+///     fn from_properties(properties: &Properties) -> Result<Self, FromPropertiesError> {
+///         Ok(Task {
+///             name: properties.title().ok_or_else(|| FromPropertiesError::missing("Name"))?,
+///             done: properties
+///                 .get_checkbox("Done")
+///                 .ok_or_else(|| FromPropertiesError::missing("Done"))?,
+///         })
+///     }
+/// }
+/// ```
+pub trait FromProperties: Sized {
+    fn from_properties(properties: &Properties) -> Result<Self, FromPropertiesError>;
+}
+
+/// Conversion of a type of your own into the properties of a page,
+/// e.g. to create a database row with [PageCreateRequest].
+pub trait IntoProperties {
+    fn into_properties(self) -> Properties;
+}
+
+/// Why the properties of a page couldn't be converted with [FromProperties].
+#[derive(Debug, Eq, PartialEq, Clone, thiserror::Error)]
+#[error("{message}")]
+pub struct FromPropertiesError {
+    pub message: String,
+}
+
+impl FromPropertiesError {
+    pub fn new<S: Into<String>>(message: S) -> Self {
+        FromPropertiesError {
+            message: message.into(),
+        }
+    }
+
+    /// The property `name` is missing.
+    pub fn missing(name: &str) -> Self {
+        Self::new(format!("Missing property {:?}", name))
+    }
+
+    /// The property `name` exists, but has a different type or is empty.
+    pub fn wrong_type(name: &str) -> Self {
+        Self::new(format!(
+            "Property {:?} has an unexpected type or is empty",
+            name
+        ))
+    }
 }
 
 #[derive(Serialize, Debug, Eq, PartialEq, Clone)]
@@ -236,6 +591,8 @@ pub struct PageCreateRequest {
     pub children: Option<Vec<CreateBlock>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub icon: Option<FileOrEmojiObject>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cover: Option<FileObject>,
 }
 
 #[derive(Serialize, Debug, Eq, PartialEq, Clone)]
@@ -243,6 +600,12 @@ pub struct UpdateBlockChildrenRequest {
     pub children: Vec<CreateBlock>,
 }
 
+impl From<Vec<CreateBlock>> for UpdateBlockChildrenRequest {
+    fn from(children: Vec<CreateBlock>) -> Self {
+        UpdateBlockChildrenRequest { children }
+    }
+}
+
 #[derive(Serialize, Debug, Eq, PartialEq)]
 pub struct PageUpdateRequest {
     pub properties: Properties,
@@ -250,6 +613,210 @@ pub struct PageUpdateRequest {
     pub archived: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub icon: Option<FileOrEmojiObject>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cover: Option<FileObject>,
+    /// Moves the page into another page or database.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<Parent>,
+}
+
+impl PageUpdateRequest {
+    /// A request that only changes the archived status of a page.
+    /// `properties` is still sent as an empty object, as the API expects.
+    pub fn archived(archived: bool) -> Self {
+        PageUpdateRequest {
+            properties: Properties {
+                properties: HashMap::new(),
+            },
+            archived: Some(archived),
+            icon: None,
+            cover: None,
+            parent: None,
+        }
+    }
+
+    /// A request that only moves a page to a new parent page or database.
+    pub fn moved_to(parent: Parent) -> Self {
+        PageUpdateRequest {
+            properties: Properties {
+                properties: HashMap::new(),
+            },
+            archived: None,
+            icon: None,
+            cover: None,
+            parent: Some(parent),
+        }
+    }
+
+    /// A request that only replaces the icon of a page.
+    pub fn icon(icon: FileOrEmojiObject) -> Self {
+        PageUpdateRequest {
+            properties: Properties {
+                properties: HashMap::new(),
+            },
+            archived: None,
+            icon: Some(icon),
+            cover: None,
+            parent: None,
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        match self.parent {
+            None | Some(Parent::Page { .. }) | Some(Parent::Database { .. }) => Ok(()),
+            Some(_) => Err(Error::InvalidRequest {
+                message: "a page can only be moved into a page or a database".to_string(),
+            }),
+        }
+    }
+}
+
+/// Changes to the title, description and schema of a database.
+/// See <https://developers.notion.com/reference/update-a-database>
+#[derive(Serialize, Debug, Eq, PartialEq, Clone, Default)]
+pub struct DatabaseUpdateRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<Vec<RichText>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<Vec<RichText>>,
+    /// Properties to change, keyed by their current name or id.
+    /// A `None` value removes the property from the database.
+    #[serde(
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "ordered_map"
+    )]
+    pub properties: HashMap<String, Option<PropertyUpdate>>,
+    /// Moves the database to the trash, or restores it from there.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived: Option<bool>,
+}
+
+impl DatabaseUpdateRequest {
+    /// A request that only changes the archived status of a database.
+    pub fn archived(archived: bool) -> Self {
+        DatabaseUpdateRequest {
+            archived: Some(archived),
+            ..Default::default()
+        }
+    }
+
+    /// Renames the property `name` to `new_name`.
+    pub fn rename_property<S: Into<String>, T: Into<String>>(
+        mut self,
+        name: S,
+        new_name: T,
+    ) -> Self {
+        self.properties.insert(
+            name.into(),
+            Some(PropertyUpdate {
+                name: Some(new_name.into()),
+                schema: None,
+            }),
+        );
+        self
+    }
+
+    /// Adds the property `name`, or changes its type if it already exists.
+    pub fn set_property<S: Into<String>>(
+        mut self,
+        name: S,
+        schema: PropertySchema,
+    ) -> Self {
+        self.properties.insert(
+            name.into(),
+            Some(PropertyUpdate {
+                name: None,
+                schema: Some(schema),
+            }),
+        );
+        self
+    }
+
+    pub fn remove_property<S: Into<String>>(
+        mut self,
+        name: S,
+    ) -> Self {
+        self.properties.insert(name.into(), None);
+        self
+    }
+}
+
+/// A new database, created as a child of a page.
+/// See <https://developers.notion.com/reference/create-a-database>
+#[derive(Serialize, Debug, Eq, PartialEq, Clone)]
+pub struct DatabaseCreateRequest {
+    /// Databases can only be created inside of a [Parent::Page].
+    pub parent: Parent,
+    pub title: Vec<RichText>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<FileOrEmojiObject>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cover: Option<FileObject>,
+    /// The schema of the database, exactly one property has to be a [PropertySchema::Title].
+    #[serde(serialize_with = "ordered_map")]
+    pub properties: HashMap<String, PropertySchema>,
+}
+
+impl DatabaseCreateRequest {
+    /// A database inside of the page `parent`, with a single title property named `title_property`.
+    pub fn new<T: IntoRichText, S: Into<String>>(
+        parent: PageId,
+        title: T,
+        title_property: S,
+    ) -> Self {
+        DatabaseCreateRequest {
+            parent: Parent::Page { page_id: parent },
+            title: title.into_rich_text(),
+            icon: None,
+            cover: None,
+            properties: HashMap::from([(title_property.into(), PropertySchema::Title {})]),
+        }
+    }
+
+    /// Adds the property `name` to the schema.
+    pub fn property<S: Into<String>>(
+        mut self,
+        name: S,
+        schema: PropertySchema,
+    ) -> Self {
+        self.properties.insert(name.into(), schema);
+        self
+    }
+
+    /// Checks the constraints the API would otherwise reject the request for.
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        if !matches!(self.parent, Parent::Page { .. }) {
+            return Err(Error::InvalidRequest {
+                message: "a database has to be created inside of a page".to_string(),
+            });
+        }
+
+        let titles = self
+            .properties
+            .values()
+            .filter(|schema| matches!(schema, PropertySchema::Title {}))
+            .count();
+        if titles != 1 {
+            return Err(Error::InvalidRequest {
+                message: format!(
+                    "a database needs exactly one title property, found {}",
+                    titles
+                ),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Debug, Eq, PartialEq, Clone)]
+pub struct PropertyUpdate {
+    /// The new name of the property.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The new type and configuration of the property.
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<PropertySchema>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
@@ -263,11 +830,16 @@ pub struct Page {
     pub last_edited_time: OffsetDateTime,
     /// The archived status of the page.
     pub archived: bool,
+    /// Whether the page has been moved to the trash, only sent by newer API versions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_trash: Option<bool>,
     pub properties: Properties,
     pub parent: Parent,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub icon: Option<FileOrEmojiObject>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub cover: Option<FileObject>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub blocks: Option<Vec<Block>>,
@@ -289,6 +861,26 @@ impl Page {
     pub fn title_object(&self) -> Option<&Vec<RichText>> {
         self.properties.title_object()
     }
+
+    /// The text of all blocks of the page containing text, one block per line,
+    /// including nested blocks. Empty if the blocks haven't been fetched.
+    pub fn plain_text(&self) -> String {
+        fn collect(
+            blocks: &[Block],
+            lines: &mut Vec<String>,
+        ) {
+            for block in blocks {
+                if let Some(rich_text) = block.rich_text() {
+                    lines.push(rich_text.iter().map(RichText::plain_text).collect());
+                }
+                collect(block.children().unwrap_or_default(), lines);
+            }
+        }
+
+        let mut lines = vec![];
+        collect(self.blocks.as_deref().unwrap_or_default(), &mut lines);
+        lines.join("\n")
+    }
 }
 
 impl AsIdentifier<PageId> for Page {
@@ -297,7 +889,10 @@ impl AsIdentifier<PageId> for Page {
     }
 }
 
+// Deserialized by hand so only objects of unknown types become `Unknown`,
+// the derived implementation is used for all the known ones.
 #[derive(Eq, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(remote = "Self")]
 #[serde(tag = "object")]
 #[serde(rename_all = "snake_case")]
 pub enum Object {
@@ -325,15 +920,68 @@ pub enum Object {
         #[serde(flatten)]
         error: ErrorResponse,
     },
+    Comment {
+        #[serde(flatten)]
+        comment: Comment,
+    },
     #[serde(rename = "property_item")]
     PropertyItem {
         #[serde(flatten)]
         property_item: PropertyItem,
     },
+    /// An object type this version of the crate doesn't know yet, kept as the raw JSON.
+    #[serde(skip)]
+    Unknown(serde_json::Value),
+}
+
+/// The `object` of every object but [Object::Unknown].
+const OBJECT_TYPES: &[&str] = &[
+    "block",
+    "database",
+    "page",
+    "list",
+    "user",
+    "error",
+    "comment",
+    "property_item",
+];
+
+impl<'de> Deserialize<'de> for Object {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let known = value
+            .get("object")
+            .and_then(serde_json::Value::as_str)
+            .is_some_and(|object| OBJECT_TYPES.contains(&object));
+        if known {
+            // The derived implementation, see `#[serde(remote = "Self")]`.
+            Object::deserialize(value).map_err(serde::de::Error::custom)
+        } else {
+            Ok(Object::Unknown(value))
+        }
+    }
+}
+
+impl Serialize for Object {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match self {
+            Object::Unknown(value) => value.serialize(serializer),
+            known => Object::serialize(known, serializer),
+        }
+    }
 }
 
 impl Object {
     pub fn is_database(&self) -> bool {
         matches!(self, Object::Database { .. })
     }
+
+    /// Whether this is an object type added to the API after this version of the crate.
+    /// Lists of a single type of object, e.g. of pages, leave them out and log a warning.
+    pub fn is_unknown(&self) -> bool {
+        matches!(self, Object::Unknown(_))
+    }
 }
@@ -12,6 +12,31 @@ pub struct Paging {
     pub page_size: Option<u8>,
 }
 
+/// The largest number of results Notion returns in a single page.
+pub const MAX_PAGE_SIZE: u8 = 100;
+
+impl Paging {
+    /// Requests pages of `page_size` results, clamped to the range of 1 to [MAX_PAGE_SIZE]
+    /// the API accepts.
+    pub fn new(page_size: u8) -> Self {
+        Paging {
+            start_cursor: None,
+            page_size: Some(page_size.clamp(1, MAX_PAGE_SIZE)),
+        }
+    }
+
+    /// Continues from the cursor returned with a previous page.
+    pub fn with_cursor(
+        self,
+        cursor: PagingCursor,
+    ) -> Self {
+        Paging {
+            start_cursor: Some(cursor),
+            ..self
+        }
+    }
+}
+
 pub trait Pageable {
     fn start_from(
         self,
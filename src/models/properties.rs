@@ -1,9 +1,12 @@
+use crate::models::block::FileObject;
 use crate::models::text::RichText;
 use crate::models::users::User;
 
-use crate::ids::{DatabaseId, PageId, PropertyId};
+use crate::ids::{DatabaseId, PageId, PropertyId, UserId};
 use crate::models::Number;
 use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use time::format_description::well_known::Rfc3339;
 use time::{Date, OffsetDateTime};
 
 pub mod formulas;
@@ -12,6 +15,7 @@ pub mod formulas;
 mod tests;
 
 /// How the number is displayed in Notion.
+/// See <https://developers.notion.com/reference/property-object#number>
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Copy, Clone, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum NumberFormat {
@@ -19,6 +23,8 @@ pub enum NumberFormat {
     NumberWithCommas,
     Percent,
     Dollar,
+    CanadianDollar,
+    SingaporeDollar,
     Euro,
     Pound,
     Yen,
@@ -26,6 +32,33 @@ pub enum NumberFormat {
     Rupee,
     Won,
     Yuan,
+    Real,
+    Lira,
+    Rupiah,
+    Franc,
+    HongKongDollar,
+    NewZealandDollar,
+    Krona,
+    NorwegianKrone,
+    MexicanPeso,
+    Rand,
+    NewTaiwanDollar,
+    DanishKrone,
+    Zloty,
+    Baht,
+    Forint,
+    Koruna,
+    Shekel,
+    ChileanPeso,
+    PhilippinePeso,
+    Dirham,
+    ColombianPeso,
+    Riyal,
+    Ringgit,
+    Leu,
+    ArgentinePeso,
+    UruguayanPeso,
+    PeruvianSol,
 }
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Hash, Clone)]
@@ -92,15 +125,34 @@ pub struct Relation {
     /// The database this relation refers to.
     /// New linked pages must belong to this database in order to be valid.
     pub database_id: DatabaseId,
-    /// By default, relations are formed as two synced properties across databases:
-    ///     if you make a change to one property, it updates the synced property at the same time.
-    /// `synced_property_name` refers to the name of the property in the related database.
-    pub synced_property_name: Option<String>,
-    /// By default, relations are formed as two synced properties across databases:
+    #[serde(flatten)]
+    pub relation_type: RelationType,
+}
+
+/// Whether the relation has a synced property in the related database.
+/// <https://developers.notion.com/reference/property-object#relation>
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RelationType {
+    /// The relation only exists on this database.
+    SingleProperty {
+        single_property: SinglePropertyRelation,
+    },
+    /// The relation is mirrored by a synced property in the related database:
     ///     if you make a change to one property, it updates the synced property at the same time.
-    /// `synced_property_id` refers to the id of the property in the related database.
+    DualProperty { dual_property: DualPropertyRelation },
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Default, Clone)]
+pub struct SinglePropertyRelation {}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct DualPropertyRelation {
+    /// The name of the property in the related database.
+    pub synced_property_name: String,
+    /// The id of the property in the related database.
     /// This is usually a short string of random letters and symbols.
-    pub synced_property_id: Option<PropertyId>,
+    pub synced_property_id: PropertyId,
 }
 
 /// The function used to roll up the values of the relation property.
@@ -172,6 +224,7 @@ pub enum PropertyConfiguration {
     /// See <https://developers.notion.com/reference/database#select-configuration>
     Select { id: PropertyId, select: Select },
     /// Represents a Status property
+    /// See <https://developers.notion.com/reference/property-object#status>
     Status { id: PropertyId, status: Status },
     /// Represents a Multi-select Property
     /// See <https://developers.notion.com/reference/database#multi-select-configuration>
@@ -216,10 +269,127 @@ pub enum PropertyConfiguration {
     LastEditedTime { id: PropertyId },
     /// See <https://developers.notion.com/reference/database#last-edited-by-configuration>
     LastEditedBy { id: PropertyId },
+    /// See <https://developers.notion.com/reference/property-object#unique-id>
+    UniqueId {
+        id: PropertyId,
+        unique_id: UniqueIdConfiguration,
+    },
     /// Undocumented property of wiki pages
     Verification { id: PropertyId },
 }
 
+/// The type and configuration of a database property when creating or updating a database.
+/// Unlike [PropertyConfiguration] it doesn't require the ids Notion assigns to properties.
+/// See <https://developers.notion.com/reference/update-a-database>
+#[derive(Serialize, Debug, Eq, PartialEq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum PropertySchema {
+    Title {},
+    RichText {},
+    Number(NumberDetails),
+    Select(SelectSchema),
+    MultiSelect(SelectSchema),
+    Date {},
+    People {},
+    Files {},
+    Checkbox {},
+    Url {},
+    Email {},
+    PhoneNumber {},
+    Formula(Formula),
+    Relation(Relation),
+    Rollup(RollupSchema),
+    CreatedTime {},
+    CreatedBy {},
+    LastEditedTime {},
+    LastEditedBy {},
+}
+
+#[derive(Serialize, Debug, Eq, PartialEq, Clone, Default)]
+pub struct SelectSchema {
+    /// The complete list of options. Existing options missing from it are removed.
+    pub options: Vec<SelectOptionSchema>,
+}
+
+/// An option of a select or multi select property being created or updated.
+/// Existing options are matched by `id` if present, otherwise by `name`.
+#[derive(Serialize, Debug, Eq, PartialEq, Clone)]
+pub struct SelectOptionSchema {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<SelectOptionId>,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<Color>,
+}
+
+impl SelectSchema {
+    /// Adds an option unless one with the same name is already present.
+    pub fn with_option<S: Into<String>>(
+        mut self,
+        name: S,
+        color: Color,
+    ) -> Self {
+        let name = name.into();
+        if !self.contains(&name) {
+            self.options.push(SelectOptionSchema {
+                id: None,
+                name,
+                color: Some(color),
+            });
+        }
+        self
+    }
+
+    /// Adds the options of `other` whose names aren't present yet.
+    /// Since an update replaces the whole list, merge new options into the existing
+    /// configuration (see `From<Select>`) to avoid removing options.
+    pub fn merge(
+        mut self,
+        other: SelectSchema,
+    ) -> Self {
+        for option in other.options {
+            if !self.contains(&option.name) {
+                self.options.push(option);
+            }
+        }
+        self
+    }
+
+    fn contains(
+        &self,
+        name: &str,
+    ) -> bool {
+        self.options.iter().any(|option| option.name == name)
+    }
+}
+
+impl From<Select> for SelectSchema {
+    fn from(select: Select) -> Self {
+        SelectSchema {
+            options: select.options.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<SelectOption> for SelectOptionSchema {
+    fn from(option: SelectOption) -> Self {
+        SelectOptionSchema {
+            id: Some(option.id),
+            name: option.name,
+            color: Some(option.color),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Eq, PartialEq, Clone)]
+pub struct RollupSchema {
+    /// The name of the relation property to roll up.
+    pub relation_property_name: String,
+    /// The name of the property of the related pages used as an input to `function`.
+    pub rollup_property_name: String,
+    pub function: RollupFunction,
+}
+
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
 pub struct SelectedValue {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -229,21 +399,90 @@ pub struct SelectedValue {
     pub color: Color,
 }
 
+/// Notion dates either only have a day (`2021-05-19`) or also a time (`2021-05-19T12:00:00.000+02:00`).
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
 #[serde(untagged)]
 pub enum DateOrDateTime {
     Date(Date),
-    #[serde(with = "time::serde::iso8601")]
+    // iso8601 would serialize the year with six digits, which the API rejects
+    #[serde(
+        serialize_with = "time::serde::rfc3339::serialize",
+        deserialize_with = "time::serde::iso8601::deserialize"
+    )]
     DateTime(OffsetDateTime),
 }
 
+impl Display for DateOrDateTime {
+    /// Formats the date as `YYYY-MM-DD` and date times in RFC 3339 format.
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            DateOrDateTime::Date(date) => write!(f, "{}", date),
+            DateOrDateTime::DateTime(date_time) => {
+                let formatted = date_time.format(&Rfc3339).map_err(|_| std::fmt::Error)?;
+                f.write_str(&formatted)
+            }
+        }
+    }
+}
+
+impl From<Date> for DateOrDateTime {
+    fn from(date: Date) -> Self {
+        DateOrDateTime::Date(date)
+    }
+}
+
+impl From<OffsetDateTime> for DateOrDateTime {
+    fn from(date_time: OffsetDateTime) -> Self {
+        DateOrDateTime::DateTime(date_time)
+    }
+}
+
+/// See <https://developers.notion.com/reference/page-property-values#date>
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
 pub struct DateValue {
     pub start: DateOrDateTime,
+    /// Set if the date is a range.
     pub end: Option<DateOrDateTime>,
+    /// The IANA time zone the times are displayed in, e.g. `Europe/Berlin`.
+    /// Times are still given with an offset when it is set.
     pub time_zone: Option<String>,
 }
 
+impl DateValue {
+    /// A single day without a time, serialized as `YYYY-MM-DD`.
+    pub fn from_date(date: Date) -> Self {
+        DateValue {
+            start: date.into(),
+            end: None,
+            time_zone: None,
+        }
+    }
+
+    /// A point in time, serialized in RFC 3339 format, e.g. `2021-05-19T10:30:00Z`.
+    pub fn from_datetime(date_time: OffsetDateTime) -> Self {
+        DateValue {
+            start: date_time.into(),
+            end: None,
+            time_zone: None,
+        }
+    }
+
+    /// A range from `start` to `end`, both given as dates or as date times.
+    pub fn range<T: Into<DateOrDateTime>>(
+        start: T,
+        end: T,
+    ) -> Self {
+        DateValue {
+            start: start.into(),
+            end: Some(end.into()),
+            time_zone: None,
+        }
+    }
+}
+
 /// Formula property value objects represent the result of evaluating a formula
 /// described in the database's properties.
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
@@ -267,134 +506,705 @@ pub struct RelationValue {
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum RollupValue {
-    Number { number: Option<Number> },
-    Date { date: Option<OffsetDateTime> },
-    Array { array: Vec<RollupPropertyValue> },
+    Number {
+        number: Option<Number>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        function: Option<RollupFunction>,
+    },
+    Date {
+        date: Option<DateValue>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        function: Option<RollupFunction>,
+    },
+    Array {
+        array: Vec<RollupPropertyValue>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        function: Option<RollupFunction>,
+    },
+}
+
+impl RollupValue {
+    /// The rolled up values as display strings, e.g. for a summary of the related pages.
+    ///
+    /// Numbers, text and dates are included, date ranges are joined by ` → `.
+    /// Empty values and elements of any other type are skipped.
+    pub fn flatten_plain(&self) -> Vec<String> {
+        match self {
+            RollupValue::Number { number, .. } => number.iter().map(Number::to_string).collect(),
+            RollupValue::Date { date, .. } => date.iter().map(plain_date).collect(),
+            RollupValue::Array { array, .. } => array
+                .iter()
+                .filter_map(|value| match value {
+                    RollupPropertyValue::Number { number } => {
+                        number.as_ref().map(Number::to_string)
+                    }
+                    RollupPropertyValue::Text { rich_text } => Some(
+                        rich_text
+                            .iter()
+                            .map(RichText::plain_text)
+                            .collect::<String>(),
+                    )
+                    .filter(|text| !text.is_empty()),
+                    RollupPropertyValue::Date { date } => date.as_ref().map(plain_date),
+                    _ => None,
+                })
+                .collect(),
+        }
+    }
+}
+
+fn plain_date(date: &DateValue) -> String {
+    match &date.end {
+        Some(end) => format!("{} → {}", date.start, end),
+        None => date.start.to_string(),
+    }
 }
 
+/// A file of a files property, either uploaded to Notion or linked from elsewhere.
+/// See <https://developers.notion.com/reference/page-property-values#files>
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
 pub struct FileReference {
     pub name: String,
-    pub url: String,
-    pub mime_type: String,
+    #[serde(flatten)]
+    pub file: FileObject,
+}
+
+impl FileReference {
+    /// A link to a file hosted outside of Notion, the only kind of file that can be set
+    /// through the API.
+    pub fn external<N: Into<String>, U: Into<String>>(
+        name: N,
+        url: U,
+    ) -> Self {
+        FileReference {
+            name: name.into(),
+            file: FileObject::external(url),
+        }
+    }
 }
 
+// Deserialized by hand so only properties of unknown types become `Unknown`,
+// the derived implementation is used for all the known ones.
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+#[serde(remote = "Self")]
 #[serde(tag = "type")]
 #[serde(rename_all = "snake_case")]
 pub enum PropertyValue {
     // <https://developers.notion.com/reference/property-object#title-configuration>
     Title {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         title: Vec<RichText>,
     },
     /// <https://developers.notion.com/reference/property-object#text-configuration>
     #[serde(rename = "rich_text")]
     Text {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         rich_text: Vec<RichText>,
     },
     /// <https://developers.notion.com/reference/property-object#number-configuration>
     Number {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         number: Option<Number>,
     },
     /// <https://developers.notion.com/reference/property-object#select-configuration>
     Select {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         select: Option<SelectedValue>,
     },
     /// <https://developers.notion.com/reference/property-object#status-configuration>
     Status {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         status: Option<SelectedValue>,
     },
     /// <https://developers.notion.com/reference/property-object#multi-select-configuration>
     MultiSelect {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         multi_select: Option<Vec<SelectedValue>>,
     },
     /// <https://developers.notion.com/reference/property-object#date-configuration>
     Date {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         date: Option<DateValue>,
     },
     /// <https://developers.notion.com/reference/property-object#formula-configuration>
     Formula {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         formula: FormulaResultValue,
     },
     /// <https://developers.notion.com/reference/property-object#relation-configuration>
     /// It is actually an array of relations
     Relation {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         relation: Option<Vec<RelationValue>>,
     },
     /// <https://developers.notion.com/reference/property-object#rollup-configuration>
     Rollup {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         rollup: Option<RollupValue>,
     },
     /// <https://developers.notion.com/reference/property-object#people-configuration>
-    People { id: PropertyId, people: Vec<User> },
+    People {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
+        id: PropertyId,
+        people: Vec<User>,
+    },
     /// <https://developers.notion.com/reference/property-object#files-configuration>
     Files {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         files: Option<Vec<FileReference>>,
     },
     /// <https://developers.notion.com/reference/property-object#checkbox-configuration>
-    Checkbox { id: PropertyId, checkbox: bool },
+    Checkbox {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
+        id: PropertyId,
+        checkbox: bool,
+    },
     /// <https://developers.notion.com/reference/property-object#url-configuration>
-    Url { id: PropertyId, url: Option<String> },
+    Url {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
+        id: PropertyId,
+        url: Option<String>,
+    },
     /// <https://developers.notion.com/reference/property-object#email-configuration>
     Email {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         email: Option<String>,
     },
     /// <https://developers.notion.com/reference/property-object#phone-number-configuration>
     PhoneNumber {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         phone_number: Option<String>,
     },
     /// <https://developers.notion.com/reference/property-object#created-time-configuration>
     CreatedTime {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         #[serde(with = "time::serde::iso8601")]
         created_time: OffsetDateTime,
     },
     /// <https://developers.notion.com/reference/property-object#created-by-configuration>
-    CreatedBy { id: PropertyId, created_by: User },
+    CreatedBy {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
+        id: PropertyId,
+        created_by: User,
+    },
     /// <https://developers.notion.com/reference/property-object#last-edited-time-configuration>
     LastEditedTime {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         #[serde(with = "time::serde::iso8601")]
         last_edited_time: OffsetDateTime,
     },
     /// <https://developers.notion.com/reference/property-object#last-edited-by-configuration>
     LastEditedBy {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         last_edited_by: Option<User>,
     },
     UniqueId {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         unique_id: UniqueIdValue,
     },
     Verification {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         verification: VerificationValue,
     },
+    /// A property type this version of the crate doesn't know yet, kept as the raw JSON
+    /// so pages with such properties can still be read and written back.
+    /// Values of known types that fail to parse are an error instead.
+    #[serde(skip)]
+    Unknown(serde_json::Value),
+}
+
+/// The `type` of every property value but [PropertyValue::Unknown].
+const PROPERTY_VALUE_TYPES: &[&str] = &[
+    "title",
+    "rich_text",
+    "number",
+    "select",
+    "status",
+    "multi_select",
+    "date",
+    "formula",
+    "relation",
+    "rollup",
+    "people",
+    "files",
+    "checkbox",
+    "url",
+    "email",
+    "phone_number",
+    "created_time",
+    "created_by",
+    "last_edited_time",
+    "last_edited_by",
+    "unique_id",
+    "verification",
+];
+
+impl<'de> Deserialize<'de> for PropertyValue {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let known = value
+            .get("type")
+            .and_then(serde_json::Value::as_str)
+            .is_some_and(|kind| PROPERTY_VALUE_TYPES.contains(&kind));
+        if known {
+            // The derived implementation, see `#[serde(remote = "Self")]`.
+            PropertyValue::deserialize(value).map_err(serde::de::Error::custom)
+        } else {
+            Ok(PropertyValue::Unknown(value))
+        }
+    }
+}
+
+impl Serialize for PropertyValue {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match self {
+            PropertyValue::Unknown(value) => value.serialize(serializer),
+            known => PropertyValue::serialize(known, serializer),
+        }
+    }
+}
+
+impl PropertyValue {
+    /// The ids of the users in a people property, including partial users
+    /// the integration isn't allowed to read the details of.
+    /// Returns an empty list for any other property.
+    pub fn people_ids(&self) -> Vec<UserId> {
+        match self {
+            PropertyValue::People { people, .. } => {
+                people.iter().map(|user| user.id().clone()).collect()
+            }
+            _ => vec![],
+        }
+    }
+
+    /// Whether the property has no value, e.g. an empty title, a date without a value
+    /// or a multi-select without options.
+    ///
+    /// Text is empty if it has no plain text, a rollup if its result is empty.
+    /// Checkboxes and the properties Notion fills in itself, like the creation time, never are.
+    /// Properties of unknown types are empty if their value is missing or `null`.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            PropertyValue::Title { title: text, .. }
+            | PropertyValue::Text {
+                rich_text: text, ..
+            } => text.iter().all(|text| text.plain_text().is_empty()),
+            PropertyValue::Number { number, .. } => number.is_none(),
+            PropertyValue::Select { select, .. } | PropertyValue::Status { status: select, .. } => {
+                select.is_none()
+            }
+            PropertyValue::MultiSelect { multi_select, .. } => {
+                multi_select.as_ref().is_none_or(Vec::is_empty)
+            }
+            PropertyValue::Date { date, .. } => date.is_none(),
+            PropertyValue::Formula { formula, .. } => match formula {
+                FormulaResultValue::String { string } => {
+                    string.as_ref().is_none_or(String::is_empty)
+                }
+                FormulaResultValue::Number { number } => number.is_none(),
+                FormulaResultValue::Boolean { boolean } => boolean.is_none(),
+                FormulaResultValue::Date { date } => date.is_none(),
+            },
+            PropertyValue::Relation { relation, .. } => relation.as_ref().is_none_or(Vec::is_empty),
+            PropertyValue::Rollup { rollup, .. } => match rollup {
+                None => true,
+                Some(RollupValue::Number { number, .. }) => number.is_none(),
+                Some(RollupValue::Date { date, .. }) => date.is_none(),
+                Some(RollupValue::Array { array, .. }) => array.is_empty(),
+            },
+            PropertyValue::People { people, .. } => people.is_empty(),
+            PropertyValue::Files { files, .. } => files.as_ref().is_none_or(Vec::is_empty),
+            PropertyValue::Url { url: text, .. }
+            | PropertyValue::Email { email: text, .. }
+            | PropertyValue::PhoneNumber {
+                phone_number: text, ..
+            } => text.as_ref().is_none_or(String::is_empty),
+            PropertyValue::LastEditedBy { last_edited_by, .. } => last_edited_by.is_none(),
+            PropertyValue::Checkbox { .. }
+            | PropertyValue::CreatedTime { .. }
+            | PropertyValue::CreatedBy { .. }
+            | PropertyValue::LastEditedTime { .. }
+            | PropertyValue::UniqueId { .. }
+            | PropertyValue::Verification { .. } => false,
+            PropertyValue::Unknown(value) => value
+                .get("type")
+                .and_then(serde_json::Value::as_str)
+                .and_then(|kind| value.get(kind))
+                .is_none_or(serde_json::Value::is_null),
+        }
+    }
+
+    /// The value flattened into a scalar or a list of strings, e.g. to write it to a CSV file.
+    ///
+    /// Text is rendered as plain text, select options and users by their name,
+    /// relations by the ids of the related pages.
+    /// Returns `None` for empty values, rollups of arrays and unknown property types.
+    pub fn as_plain_value(&self) -> Option<PlainPropertyValue> {
+        use PlainPropertyValue::*;
+
+        let value = match self {
+            PropertyValue::Title { title: text, .. }
+            | PropertyValue::Text {
+                rich_text: text, ..
+            } => Text(text.iter().map(RichText::plain_text).collect()),
+            PropertyValue::Number { number, .. } => Number(number.as_ref()?.as_f64()?),
+            PropertyValue::Select { select, .. } | PropertyValue::Status { status: select, .. } => {
+                Text(select.as_ref()?.name.clone()?)
+            }
+            PropertyValue::MultiSelect { multi_select, .. } => List(
+                multi_select
+                    .iter()
+                    .flatten()
+                    .filter_map(|value| value.name.clone())
+                    .collect(),
+            ),
+            PropertyValue::Date { date, .. } => Date(date.clone()?),
+            PropertyValue::Formula { formula, .. } => match formula {
+                FormulaResultValue::String { string } => Text(string.clone()?),
+                FormulaResultValue::Number { number } => Number(number.as_ref()?.as_f64()?),
+                FormulaResultValue::Boolean { boolean } => Bool((*boolean)?),
+                FormulaResultValue::Date { date } => Date(date.clone()?),
+            },
+            PropertyValue::Relation { relation, .. } => List(
+                relation
+                    .iter()
+                    .flatten()
+                    .map(|relation| relation.id.to_string())
+                    .collect(),
+            ),
+            PropertyValue::Rollup { rollup, .. } => match rollup.as_ref()? {
+                RollupValue::Number { number, .. } => Number(number.as_ref()?.as_f64()?),
+                RollupValue::Date { date, .. } => Date(date.clone()?),
+                RollupValue::Array { .. } => return None,
+            },
+            PropertyValue::People { people, .. } => List(people.iter().map(user_name).collect()),
+            PropertyValue::Files { files, .. } => List(
+                files
+                    .iter()
+                    .flatten()
+                    .map(|file| file.name.clone())
+                    .collect(),
+            ),
+            PropertyValue::Checkbox { checkbox, .. } => Bool(*checkbox),
+            PropertyValue::Url { url: text, .. }
+            | PropertyValue::Email { email: text, .. }
+            | PropertyValue::PhoneNumber {
+                phone_number: text, ..
+            } => Text(text.clone()?),
+            PropertyValue::CreatedTime {
+                created_time: time, ..
+            }
+            | PropertyValue::LastEditedTime {
+                last_edited_time: time,
+                ..
+            } => Date(DateValue {
+                start: DateOrDateTime::DateTime(*time),
+                end: None,
+                time_zone: None,
+            }),
+            PropertyValue::CreatedBy { created_by, .. } => Text(user_name(created_by)),
+            PropertyValue::LastEditedBy { last_edited_by, .. } => {
+                Text(user_name(last_edited_by.as_ref()?))
+            }
+            PropertyValue::UniqueId { unique_id, .. } => Text(unique_id.to_string()),
+            PropertyValue::Verification { verification, .. } => {
+                Text(verification.state.as_str().to_string())
+            }
+            PropertyValue::Unknown(_) => return None,
+        };
+
+        Some(value)
+    }
+}
+
+/// The name of a user, or their id if the integration can't read the name.
+fn user_name(user: &User) -> String {
+    match user {
+        User::Person { common, .. } | User::Bot { common, .. } => {
+            common.name.clone().unwrap_or_else(|| common.id.to_string())
+        }
+        User::Stub { id } => id.to_string(),
+    }
+}
+
+/// A property value flattened by [PropertyValue::as_plain_value].
+#[derive(Debug, PartialEq, Clone)]
+pub enum PlainPropertyValue {
+    Text(String),
+    Number(f64),
+    Bool(bool),
+    Date(DateValue),
+    List(Vec<String>),
+}
+
+/// The property types a field of a `#[derive(NotionRow)]` struct can be written as,
+/// for field types that fit more than one. Other field types ignore it.
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Default)]
+pub enum FieldKind {
+    #[default]
+    Text,
+    Title,
+    Select,
+    Url,
+    Email,
+    PhoneNumber,
+}
+
+/// Conversion between a property value and the type of a field of a row struct,
+/// see [FromProperties](crate::models::FromProperties) and
+/// [IntoProperties](crate::models::IntoProperties).
+///
+/// Reading is lenient: a `String` field can be read from any property with a plain text value.
+pub trait PropertyField: Sized {
+    /// `value` is `None` if the page has no such property.
+    /// Returns `None` if the value is empty or can't be converted.
+    fn from_property(value: Option<&PropertyValue>) -> Option<Self>;
+
+    /// The value of the property, or `None` to leave it out.
+    /// The value doesn't know the id of its property, Notion goes by the name it's stored under.
+    fn into_property(
+        self,
+        kind: FieldKind,
+    ) -> Option<PropertyValue>;
+}
+
+impl PropertyField for String {
+    fn from_property(value: Option<&PropertyValue>) -> Option<Self> {
+        match value?.as_plain_value()? {
+            PlainPropertyValue::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    fn into_property(
+        self,
+        kind: FieldKind,
+    ) -> Option<PropertyValue> {
+        let id = PropertyId::default();
+        let value = match kind {
+            FieldKind::Text => PropertyValue::Text {
+                id,
+                rich_text: RichText::from_plain_chunked(&self),
+            },
+            FieldKind::Title => PropertyValue::Title {
+                id,
+                title: RichText::from_plain_chunked(&self),
+            },
+            FieldKind::Select => PropertyValue::Select {
+                id,
+                select: Some(SelectedValue {
+                    id: None,
+                    name: Some(self),
+                    color: Color::Default,
+                }),
+            },
+            FieldKind::Url => PropertyValue::Url {
+                id,
+                url: Some(self),
+            },
+            FieldKind::Email => PropertyValue::Email {
+                id,
+                email: Some(self),
+            },
+            FieldKind::PhoneNumber => PropertyValue::PhoneNumber {
+                id,
+                phone_number: Some(self),
+            },
+        };
+        Some(value)
+    }
 }
 
+impl PropertyField for f64 {
+    fn from_property(value: Option<&PropertyValue>) -> Option<Self> {
+        match value?.as_plain_value()? {
+            PlainPropertyValue::Number(number) => Some(number),
+            _ => None,
+        }
+    }
+
+    fn into_property(
+        self,
+        _kind: FieldKind,
+    ) -> Option<PropertyValue> {
+        Some(PropertyValue::Number {
+            id: PropertyId::default(),
+            number: Number::from_f64(self),
+        })
+    }
+}
+
+/// Numbers with a fractional part aren't converted.
+impl PropertyField for i64 {
+    fn from_property(value: Option<&PropertyValue>) -> Option<Self> {
+        f64::from_property(value)
+            .filter(|number| number.fract() == 0.0)
+            .map(|number| number as i64)
+    }
+
+    fn into_property(
+        self,
+        _kind: FieldKind,
+    ) -> Option<PropertyValue> {
+        Some(PropertyValue::Number {
+            id: PropertyId::default(),
+            number: Some(self.into()),
+        })
+    }
+}
+
+impl PropertyField for bool {
+    fn from_property(value: Option<&PropertyValue>) -> Option<Self> {
+        match value?.as_plain_value()? {
+            PlainPropertyValue::Bool(checked) => Some(checked),
+            _ => None,
+        }
+    }
+
+    fn into_property(
+        self,
+        _kind: FieldKind,
+    ) -> Option<PropertyValue> {
+        Some(PropertyValue::Checkbox {
+            id: PropertyId::default(),
+            checkbox: self,
+        })
+    }
+}
+
+impl PropertyField for DateValue {
+    fn from_property(value: Option<&PropertyValue>) -> Option<Self> {
+        match value?.as_plain_value()? {
+            PlainPropertyValue::Date(date) => Some(date),
+            _ => None,
+        }
+    }
+
+    fn into_property(
+        self,
+        _kind: FieldKind,
+    ) -> Option<PropertyValue> {
+        Some(PropertyValue::Date {
+            id: PropertyId::default(),
+            date: Some(self),
+        })
+    }
+}
+
+/// Written as the names of the options of a multi select property.
+impl PropertyField for Vec<String> {
+    fn from_property(value: Option<&PropertyValue>) -> Option<Self> {
+        match value?.as_plain_value()? {
+            PlainPropertyValue::List(list) => Some(list),
+            _ => None,
+        }
+    }
+
+    fn into_property(
+        self,
+        _kind: FieldKind,
+    ) -> Option<PropertyValue> {
+        let options = self
+            .into_iter()
+            .map(|name| SelectedValue {
+                id: None,
+                name: Some(name),
+                color: Color::Default,
+            })
+            .collect();
+        Some(PropertyValue::MultiSelect {
+            id: PropertyId::default(),
+            multi_select: Some(options),
+        })
+    }
+}
+
+/// An optional field is `None` if the property is missing or empty,
+/// and isn't written at all when it's `None`.
+impl<T: PropertyField> PropertyField for Option<T> {
+    fn from_property(value: Option<&PropertyValue>) -> Option<Self> {
+        Some(T::from_property(value))
+    }
+
+    fn into_property(
+        self,
+        kind: FieldKind,
+    ) -> Option<PropertyValue> {
+        self?.into_property(kind)
+    }
+}
+
+/// The verification status of a page in a wiki.
+/// See <https://developers.notion.com/reference/page-property-values#verification>
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
 pub struct VerificationValue {
-    pub state: String,
+    pub state: VerificationState,
+    /// Only set while the page is verified.
     pub verified_by: Option<User>,
-    pub date: Option<OffsetDateTime>,
+    /// When the page was verified, the end is set if the verification expires.
+    pub date: Option<DateValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Copy, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationState {
+    Verified,
+    Unverified,
+}
+
+impl VerificationState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VerificationState::Verified => "verified",
+            VerificationState::Unverified => "unverified",
+        }
+    }
 }
 
+/// <https://developers.notion.com/reference/page-property-values#unique-id>
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
 pub struct UniqueIdValue {
-    pub number: Number,
+    pub number: i64,
+    pub prefix: Option<String>,
+}
+
+impl Display for UniqueIdValue {
+    /// Formats the id as it is shown in Notion, e.g. `TASK-123`.
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> std::fmt::Result {
+        match &self.prefix {
+            Some(prefix) => write!(f, "{}-{}", prefix, self.number),
+            None => write!(f, "{}", self.number),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct UniqueIdConfiguration {
+    /// The prefix shown before the number, if any.
     pub prefix: Option<String>,
 }
 
@@ -581,3 +1391,64 @@ pub enum PropertyItem {
         last_edited_by: Option<User>,
     },
 }
+
+impl From<PropertyItem> for PropertyValue {
+    /// Converts a single property item into the equivalent page property value.
+    /// Items of paginated properties hold a single element, which becomes a one element list.
+    fn from(item: PropertyItem) -> Self {
+        match item {
+            PropertyItem::Title { id, title } => PropertyValue::Title {
+                id,
+                title: vec![title],
+            },
+            PropertyItem::Text { id, rich_text } => PropertyValue::Text {
+                id,
+                rich_text: vec![rich_text],
+            },
+            PropertyItem::Number { id, number } => PropertyValue::Number { id, number },
+            PropertyItem::Select { id, select } => PropertyValue::Select { id, select },
+            PropertyItem::Status { id, status } => PropertyValue::Status { id, status },
+            PropertyItem::MultiSelect { id, multi_select } => PropertyValue::MultiSelect {
+                id,
+                multi_select: multi_select.map(|selected| vec![selected]),
+            },
+            PropertyItem::Date { id, date } => PropertyValue::Date { id, date },
+            PropertyItem::Formula { id, formula } => PropertyValue::Formula { id, formula },
+            PropertyItem::Relation { id, relation } => PropertyValue::Relation {
+                id,
+                relation: relation.map(|relation| vec![relation]),
+            },
+            PropertyItem::Rollup { id, rollup } => PropertyValue::Rollup { id, rollup },
+            PropertyItem::People { id, people } => PropertyValue::People {
+                id,
+                people: vec![people],
+            },
+            PropertyItem::Files { id, files } => PropertyValue::Files {
+                id,
+                files: files.map(|file| vec![file]),
+            },
+            PropertyItem::Checkbox { id, checkbox } => PropertyValue::Checkbox { id, checkbox },
+            PropertyItem::Url { id, url } => PropertyValue::Url { id, url },
+            PropertyItem::Email { id, email } => PropertyValue::Email { id, email },
+            PropertyItem::PhoneNumber { id, phone_number } => {
+                PropertyValue::PhoneNumber { id, phone_number }
+            }
+            PropertyItem::CreatedTime { id, created_time } => {
+                PropertyValue::CreatedTime { id, created_time }
+            }
+            PropertyItem::CreatedBy { id, created_by } => {
+                PropertyValue::CreatedBy { id, created_by }
+            }
+            PropertyItem::LastEditedTime {
+                id,
+                last_edited_time,
+            } => PropertyValue::LastEditedTime {
+                id,
+                last_edited_time,
+            },
+            PropertyItem::LastEditedBy { id, last_edited_by } => {
+                PropertyValue::LastEditedBy { id, last_edited_by }
+            }
+        }
+    }
+}
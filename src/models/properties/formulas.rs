@@ -1,6 +1,11 @@
 #[cfg(test)]
 mod tests {
-    use crate::models::properties::{FormulaResultValue, PropertyValue};
+    use crate::models::properties::{
+        DateOrDateTime, DateValue, Formula, FormulaResultValue, PropertyConfiguration,
+        PropertyValue,
+    };
+    use serde_json::json;
+    use time::{Date, Month};
 
     #[test]
     fn parse_number_formula_prop() {
@@ -24,4 +29,87 @@ mod tests {
         )
         .unwrap();
     }
+
+    fn assert_formula_round_trips(
+        json: serde_json::Value,
+        expected: FormulaResultValue,
+    ) {
+        let property: PropertyValue = serde_json::from_value(json!({
+            "id": "abc",
+            "type": "formula",
+            "formula": json.clone()
+        }))
+        .unwrap();
+        match &property {
+            PropertyValue::Formula { formula, .. } => assert_eq!(formula, &expected),
+            other => panic!("Expected a formula, got {:?}", other),
+        }
+
+        let serialized = serde_json::to_value(&property).unwrap();
+        assert_eq!(serialized["formula"], json);
+        let deserialized: PropertyValue = serde_json::from_value(serialized).unwrap();
+        assert_eq!(deserialized, property);
+    }
+
+    #[test]
+    fn formula_results_round_trip() {
+        assert_formula_round_trips(
+            json!({"type": "string", "string": "Done"}),
+            FormulaResultValue::String {
+                string: Some("Done".to_string()),
+            },
+        );
+        assert_formula_round_trips(
+            json!({"type": "number", "number": 12.5}),
+            FormulaResultValue::Number {
+                number: serde_json::Number::from_f64(12.5),
+            },
+        );
+        assert_formula_round_trips(
+            json!({"type": "number", "number": null}),
+            FormulaResultValue::Number { number: None },
+        );
+        assert_formula_round_trips(
+            json!({"type": "boolean", "boolean": true}),
+            FormulaResultValue::Boolean {
+                boolean: Some(true),
+            },
+        );
+        assert_formula_round_trips(
+            json!({
+                "type": "date",
+                "date": {"start": "2021-05-19", "end": null, "time_zone": null}
+            }),
+            FormulaResultValue::Date {
+                date: Some(DateValue {
+                    start: DateOrDateTime::Date(
+                        Date::from_calendar_date(2021, Month::May, 19).unwrap(),
+                    ),
+                    end: None,
+                    time_zone: None,
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn formula_configuration_round_trip() {
+        let json = json!({
+            "id": "abc",
+            "type": "formula",
+            "formula": {"expression": "prop(\"Price\") * prop(\"Quantity\")"}
+        });
+        let configuration: PropertyConfiguration = serde_json::from_value(json.clone()).unwrap();
+
+        match &configuration {
+            PropertyConfiguration::Formula { formula, .. } => assert_eq!(
+                formula,
+                &Formula {
+                    expression: "prop(\"Price\") * prop(\"Quantity\")".to_string()
+                }
+            ),
+            other => panic!("Expected a formula, got {:?}", other),
+        }
+        assert_eq!(serde_json::to_value(&configuration).unwrap(), json);
+    }
 }
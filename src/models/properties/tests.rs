@@ -1,8 +1,19 @@
+use crate::ids::{DatabaseId, PropertyId, UserId};
+use crate::models::block::FileObject;
 use crate::models::{
-    properties::{DateOrDateTime, RollupPropertyValue, RollupValue},
+    properties::{
+        Color, DateOrDateTime, DateValue, DualPropertyRelation, FileReference, NumberDetails,
+        NumberFormat, PlainPropertyValue, PropertyConfiguration, RelationType, RollupFunction,
+        RollupPropertyValue, RollupValue, Select, SelectOptionId, SelectSchema,
+        SinglePropertyRelation, UniqueIdConfiguration, UniqueIdValue, VerificationState,
+    },
+    users::User,
     PropertyValue,
 };
-use time::{Date, Month};
+use serde_json::json;
+use std::str::FromStr;
+use time::format_description::well_known::Rfc3339;
+use time::{Date, Month, OffsetDateTime};
 
 #[test]
 fn verify_date_parsing() {
@@ -18,6 +29,89 @@ fn parse_date_property() {
         serde_json::from_str(include_str!("tests/date_property.json")).unwrap();
 }
 
+fn assert_date_round_trips(
+    json: serde_json::Value,
+    expected: DateValue,
+) {
+    let property: PropertyValue = serde_json::from_value(json!({
+        "id": "VXfM",
+        "type": "date",
+        "date": json.clone()
+    }))
+    .unwrap();
+    assert_eq!(
+        property,
+        PropertyValue::Date {
+            id: PropertyId::from_str("VXfM").unwrap(),
+            date: Some(expected),
+        }
+    );
+    assert_eq!(serde_json::to_value(&property).unwrap()["date"], json);
+}
+
+#[test]
+fn date_only_round_trip() {
+    assert_date_round_trips(
+        json!({"start": "2021-09-30", "end": null, "time_zone": null}),
+        DateValue {
+            start: Date::from_calendar_date(2021, Month::September, 30)
+                .unwrap()
+                .into(),
+            end: None,
+            time_zone: None,
+        },
+    );
+}
+
+#[test]
+fn date_time_round_trip() {
+    assert_date_round_trips(
+        json!({
+            "start": "2021-09-30T14:30:00+02:00",
+            "end": null,
+            "time_zone": "Europe/Berlin"
+        }),
+        DateValue {
+            start: OffsetDateTime::parse("2021-09-30T14:30:00+02:00", &Rfc3339)
+                .unwrap()
+                .into(),
+            end: None,
+            time_zone: Some("Europe/Berlin".to_string()),
+        },
+    );
+}
+
+#[test]
+fn date_range_round_trip() {
+    assert_date_round_trips(
+        json!({
+            "start": "2021-09-30T09:00:00Z",
+            "end": "2021-10-02T17:00:00Z",
+            "time_zone": null
+        }),
+        DateValue {
+            start: OffsetDateTime::parse("2021-09-30T09:00:00Z", &Rfc3339)
+                .unwrap()
+                .into(),
+            end: Some(
+                OffsetDateTime::parse("2021-10-02T17:00:00Z", &Rfc3339)
+                    .unwrap()
+                    .into(),
+            ),
+            time_zone: None,
+        },
+    );
+}
+
+#[test]
+fn parse_date_time_with_milliseconds() {
+    let date: DateOrDateTime = serde_json::from_value(json!("2021-05-15T17:16:00.000Z")).unwrap();
+    assert_eq!(
+        date,
+        DateOrDateTime::DateTime(OffsetDateTime::parse("2021-05-15T17:16:00Z", &Rfc3339).unwrap())
+    );
+}
+
 #[test]
 fn parse_null_select_property() {
     let _property: PropertyValue =
@@ -50,10 +144,615 @@ fn parse_rollup_property() {
     ));
 
     if let PropertyValue::Rollup {
-        rollup: Some(RollupValue::Array { array }),
+        rollup: Some(RollupValue::Array { array, function }),
         ..
     } = property
     {
-        assert!(matches!(array[0], RollupPropertyValue::Text { .. }))
+        assert!(matches!(array[0], RollupPropertyValue::Text { .. }));
+        assert_eq!(function, Some(RollupFunction::ShowOriginal));
     }
 }
+
+fn assert_round_trips(property: &PropertyValue) {
+    let serialized = serde_json::to_string(property).unwrap();
+    let deserialized: PropertyValue = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(&deserialized, property);
+}
+
+#[test]
+fn rollup_array_round_trip() {
+    let property: PropertyValue =
+        serde_json::from_str(include_str!("tests/rollup_property.json")).unwrap();
+    assert_round_trips(&property);
+}
+
+#[test]
+fn rollup_flatten_plain() {
+    let rollup: RollupValue = serde_json::from_value(json!({
+        "type": "array",
+        "array": [
+            {"type": "number", "number": 3},
+            {"type": "number", "number": 2.5},
+            {"type": "date", "date": {"start": "2022-05-13", "end": null, "time_zone": null}},
+            {"type": "number", "number": null}
+        ],
+        "function": "show_original"
+    }))
+    .unwrap();
+
+    assert_eq!(rollup.flatten_plain(), vec!["3", "2.5", "2022-05-13"]);
+}
+
+#[test]
+fn rollup_number_round_trip() {
+    let json: serde_json::Value =
+        serde_json::from_str(include_str!("tests/rollup_number_property.json")).unwrap();
+    let property: PropertyValue = serde_json::from_value(json.clone()).unwrap();
+
+    assert_eq!(
+        property,
+        PropertyValue::Rollup {
+            id: PropertyId::from_str("a%3BuP").unwrap(),
+            rollup: Some(RollupValue::Number {
+                number: Some(42.into()),
+                function: Some(RollupFunction::Sum),
+            }),
+        }
+    );
+    assert_round_trips(&property);
+    assert_eq!(serde_json::to_value(&property).unwrap(), json);
+}
+
+#[test]
+fn rollup_date_round_trip() {
+    let json: serde_json::Value =
+        serde_json::from_str(include_str!("tests/rollup_date_property.json")).unwrap();
+    let property: PropertyValue = serde_json::from_value(json.clone()).unwrap();
+
+    let date = match &property {
+        PropertyValue::Rollup {
+            rollup: Some(RollupValue::Date {
+                date: Some(date), ..
+            }),
+            ..
+        } => date,
+        other => panic!("Expected a date rollup, got {:?}", other),
+    };
+    assert_eq!(
+        date.start,
+        DateOrDateTime::Date(Date::from_calendar_date(2021, Month::May, 10).unwrap())
+    );
+    assert_round_trips(&property);
+    assert_eq!(serde_json::to_value(&property).unwrap(), json);
+}
+
+#[test]
+fn rollup_configuration_round_trip() {
+    let json: serde_json::Value =
+        serde_json::from_str(include_str!("tests/rollup_configuration.json")).unwrap();
+    let configuration: PropertyConfiguration = serde_json::from_value(json.clone()).unwrap();
+
+    let rollup = match &configuration {
+        PropertyConfiguration::Rollup { rollup, .. } => rollup,
+        other => panic!("Expected a rollup configuration, got {:?}", other),
+    };
+    assert_eq!(rollup.relation_property_name, "Tasks");
+    assert_eq!(rollup.rollup_property_name, "Estimate");
+    assert_eq!(rollup.function, RollupFunction::Sum);
+
+    let serialized = serde_json::to_value(&configuration).unwrap();
+    assert_eq!(serialized["rollup"], json["rollup"]);
+}
+
+#[test]
+fn status_property_round_trip() {
+    let json: serde_json::Value =
+        serde_json::from_str(include_str!("tests/status_property.json")).unwrap();
+    let property: PropertyValue = serde_json::from_value(json.clone()).unwrap();
+
+    assert!(matches!(
+        &property,
+        PropertyValue::Status {
+            status: Some(status),
+            ..
+        } if status.name.as_deref() == Some("In progress") && status.color == Color::Blue
+    ));
+    assert_eq!(serde_json::to_value(&property).unwrap(), json);
+}
+
+#[test]
+fn status_configuration_round_trip() {
+    let json: serde_json::Value =
+        serde_json::from_str(include_str!("tests/status_configuration.json")).unwrap();
+    let configuration: PropertyConfiguration = serde_json::from_value(json.clone()).unwrap();
+
+    let status = match &configuration {
+        PropertyConfiguration::Status { status, .. } => status,
+        other => panic!("Expected a status configuration, got {:?}", other),
+    };
+    assert_eq!(status.options.len(), 3);
+    assert_eq!(status.groups.len(), 3);
+    assert_eq!(status.groups[2].name, "Complete");
+    assert_eq!(
+        status.groups[2].option_ids,
+        vec![SelectOptionId(
+            "b9d8a7c4-3199-4b8e-9c6f-2fd2b3b50bde".to_string()
+        )]
+    );
+
+    let serialized = serde_json::to_value(&configuration).unwrap();
+    assert_eq!(serialized["status"], json["status"]);
+}
+
+#[test]
+fn single_relation_configuration() {
+    let json: serde_json::Value =
+        serde_json::from_str(include_str!("tests/relation_single_configuration.json")).unwrap();
+    let configuration: PropertyConfiguration = serde_json::from_value(json.clone()).unwrap();
+
+    let relation = match &configuration {
+        PropertyConfiguration::Relation { relation, .. } => relation,
+        other => panic!("Expected a relation configuration, got {:?}", other),
+    };
+    assert_eq!(
+        relation.database_id,
+        DatabaseId::from_str("6c4240a9-a3ce-413e-9fd0-8a51a4d0a49b").unwrap()
+    );
+    assert_eq!(
+        relation.relation_type,
+        RelationType::SingleProperty {
+            single_property: SinglePropertyRelation {}
+        }
+    );
+
+    let serialized = serde_json::to_value(&configuration).unwrap();
+    assert_eq!(serialized["relation"], json["relation"]);
+}
+
+#[test]
+fn dual_relation_configuration() {
+    let json: serde_json::Value =
+        serde_json::from_str(include_str!("tests/relation_dual_configuration.json")).unwrap();
+    let configuration: PropertyConfiguration = serde_json::from_value(json.clone()).unwrap();
+
+    let relation = match &configuration {
+        PropertyConfiguration::Relation { relation, .. } => relation,
+        other => panic!("Expected a relation configuration, got {:?}", other),
+    };
+    assert_eq!(
+        relation.relation_type,
+        RelationType::DualProperty {
+            dual_property: DualPropertyRelation {
+                synced_property_name: "Tasks".to_string(),
+                synced_property_id: PropertyId::from_str("JU]K").unwrap(),
+            }
+        }
+    );
+
+    let serialized = serde_json::to_value(&configuration).unwrap();
+    assert_eq!(serialized["relation"], json["relation"]);
+}
+
+#[test]
+fn unique_id_property() {
+    let property: PropertyValue =
+        serde_json::from_str(include_str!("tests/unique_id_property.json")).unwrap();
+
+    let unique_id = match property {
+        PropertyValue::UniqueId { unique_id, .. } => unique_id,
+        other => panic!("Expected a unique id, got {:?}", other),
+    };
+    assert_eq!(unique_id.number, 123);
+    assert_eq!(unique_id.to_string(), "TASK-123");
+    assert_eq!(
+        UniqueIdValue {
+            prefix: None,
+            ..unique_id
+        }
+        .to_string(),
+        "123"
+    );
+}
+
+#[test]
+fn unique_id_configuration() {
+    let configuration: PropertyConfiguration =
+        serde_json::from_str(include_str!("tests/unique_id_configuration.json")).unwrap();
+
+    assert_eq!(
+        configuration,
+        PropertyConfiguration::UniqueId {
+            id: PropertyId::from_str("%3FtLj").unwrap(),
+            unique_id: UniqueIdConfiguration { prefix: None },
+        }
+    );
+}
+
+#[test]
+fn people_property_with_partial_users() {
+    let property: PropertyValue =
+        serde_json::from_str(include_str!("tests/people_property.json")).unwrap();
+
+    assert_eq!(
+        property.people_ids(),
+        vec![
+            UserId::from_str("a6e3e3c8-9d49-4a11-8b2a-7f1c1b0d2e3f").unwrap(),
+            UserId::from_str("0e4f8b7a-1c2d-4e5f-9a8b-7c6d5e4f3a2b").unwrap(),
+            UserId::from_str("5f6e7d8c-9b0a-4c1d-8e2f-3a4b5c6d7e8f").unwrap(),
+        ]
+    );
+
+    let people = match &property {
+        PropertyValue::People { people, .. } => people,
+        other => panic!("Expected people, got {:?}", other),
+    };
+    assert!(matches!(&people[0], User::Person { person, .. } if person.email.is_some()));
+    assert!(matches!(&people[1], User::Person { common, person, .. }
+        if person.email.is_none() && common.name.as_deref() == Some("John Doe")));
+    assert!(matches!(&people[2], User::Stub { .. }));
+}
+
+#[test]
+fn plain_property_values() {
+    let plain = |json: serde_json::Value| {
+        serde_json::from_value::<PropertyValue>(json)
+            .unwrap()
+            .as_plain_value()
+    };
+
+    assert_eq!(
+        plain(json!({
+            "id": "title",
+            "type": "title",
+            "title": [
+                {"type": "text", "text": {"content": "Buy "}, "plain_text": "Buy "},
+                {"type": "text", "text": {"content": "milk"}, "plain_text": "milk"}
+            ]
+        })),
+        Some(PlainPropertyValue::Text("Buy milk".to_string()))
+    );
+    assert_eq!(
+        plain(json!({"id": "a", "type": "number", "number": 2.5})),
+        Some(PlainPropertyValue::Number(2.5))
+    );
+    assert_eq!(
+        plain(json!({"id": "a", "type": "number", "number": null})),
+        None
+    );
+    assert_eq!(
+        plain(json!({
+            "id": "b",
+            "type": "multi_select",
+            "multi_select": [
+                {"id": "1", "name": "urgent", "color": "red"},
+                {"id": "2", "name": "home", "color": "blue"}
+            ]
+        })),
+        Some(PlainPropertyValue::List(vec![
+            "urgent".to_string(),
+            "home".to_string()
+        ]))
+    );
+    assert_eq!(
+        plain(json!({
+            "id": "c",
+            "type": "date",
+            "date": {"start": "2021-05-19", "end": "2021-05-21", "time_zone": null}
+        })),
+        Some(PlainPropertyValue::Date(DateValue {
+            start: DateOrDateTime::Date(Date::from_calendar_date(2021, Month::May, 19).unwrap()),
+            end: Some(DateOrDateTime::Date(
+                Date::from_calendar_date(2021, Month::May, 21).unwrap()
+            )),
+            time_zone: None,
+        }))
+    );
+}
+
+#[test]
+fn merge_select_options() {
+    let existing: Select = serde_json::from_value(json!({
+        "options": [
+            {"id": "a", "name": "urgent", "color": "red"},
+            {"id": "b", "name": "home", "color": "blue"}
+        ]
+    }))
+    .unwrap();
+
+    let merged = SelectSchema::from(existing).merge(
+        SelectSchema::default()
+            .with_option("home", Color::Green)
+            .with_option("work", Color::Yellow),
+    );
+
+    assert_eq!(
+        serde_json::to_value(&merged).unwrap(),
+        json!({
+            "options": [
+                {"id": "a", "name": "urgent", "color": "red"},
+                {"id": "b", "name": "home", "color": "blue"},
+                {"name": "work", "color": "yellow"}
+            ]
+        })
+    );
+}
+
+#[test]
+fn construct_date_values() {
+    let day = Date::from_calendar_date(2021, Month::May, 19).unwrap();
+    let next_day = Date::from_calendar_date(2021, Month::May, 20).unwrap();
+    let time = OffsetDateTime::parse("2021-05-19T10:30:00Z", &Rfc3339).unwrap();
+    let offset_time = OffsetDateTime::parse("2021-05-19T12:30:00+02:00", &Rfc3339).unwrap();
+
+    assert_eq!(
+        serde_json::to_value(DateValue::from_date(day)).unwrap(),
+        json!({"start": "2021-05-19", "end": null, "time_zone": null})
+    );
+    assert_eq!(
+        serde_json::to_value(DateValue::from_datetime(time)).unwrap(),
+        json!({"start": "2021-05-19T10:30:00Z", "end": null, "time_zone": null})
+    );
+    assert_eq!(
+        serde_json::to_value(DateValue::range(day, next_day)).unwrap(),
+        json!({"start": "2021-05-19", "end": "2021-05-20", "time_zone": null})
+    );
+    assert_eq!(
+        serde_json::to_value(DateValue::range(time, offset_time)).unwrap(),
+        json!({
+            "start": "2021-05-19T10:30:00Z",
+            "end": "2021-05-19T12:30:00+02:00",
+            "time_zone": null
+        })
+    );
+}
+
+#[test]
+fn parse_created_by_and_last_edited_by() {
+    let created_by: PropertyValue =
+        serde_json::from_str(include_str!("tests/created_by_property.json")).unwrap();
+    let last_edited_by: PropertyValue =
+        serde_json::from_str(include_str!("tests/last_edited_by_property.json")).unwrap();
+
+    // the integration can't read the creator, so only a partial user is returned
+    match &created_by {
+        PropertyValue::CreatedBy {
+            created_by: User::Stub { id },
+            ..
+        } => assert_eq!(
+            id,
+            &UserId::from_str("e2507360-468c-4e0f-a928-7bbcbbb45353").unwrap()
+        ),
+        other => panic!("Expected a partial creator, got {:?}", other),
+    }
+    assert!(matches!(
+        last_edited_by,
+        PropertyValue::LastEditedBy {
+            last_edited_by: Some(User::Person { .. }),
+            ..
+        }
+    ));
+    assert_round_trips(&created_by);
+    assert_round_trips(&last_edited_by);
+
+    let configuration: PropertyConfiguration =
+        serde_json::from_value(json!({"id": "%7BJ%3Dm", "type": "created_by", "created_by": {}}))
+            .unwrap();
+    assert_eq!(
+        configuration,
+        PropertyConfiguration::CreatedBy {
+            id: PropertyId::from_str("%7BJ%3Dm").unwrap()
+        }
+    );
+}
+
+#[test]
+fn created_and_last_edited_time_round_trip() {
+    let created_time: PropertyValue = serde_json::from_value(json!({
+        "id": "s%3Bkq",
+        "type": "created_time",
+        "created_time": "2022-05-13T20:08:00.000Z"
+    }))
+    .unwrap();
+    let last_edited_time: PropertyValue = serde_json::from_value(json!({
+        "id": "q%5Bmd",
+        "type": "last_edited_time",
+        "last_edited_time": "2022-05-14T09:31:00.000Z"
+    }))
+    .unwrap();
+
+    assert_eq!(
+        created_time,
+        PropertyValue::CreatedTime {
+            id: PropertyId::from_str("s%3Bkq").unwrap(),
+            created_time: OffsetDateTime::parse("2022-05-13T20:08:00Z", &Rfc3339).unwrap(),
+        }
+    );
+    assert_eq!(
+        last_edited_time,
+        PropertyValue::LastEditedTime {
+            id: PropertyId::from_str("q%5Bmd").unwrap(),
+            last_edited_time: OffsetDateTime::parse("2022-05-14T09:31:00Z", &Rfc3339).unwrap(),
+        }
+    );
+    assert_round_trips(&created_time);
+    assert_round_trips(&last_edited_time);
+
+    let configuration: PropertyConfiguration = serde_json::from_value(json!({
+        "id": "q%5Bmd",
+        "type": "last_edited_time",
+        "last_edited_time": {}
+    }))
+    .unwrap();
+    assert!(matches!(
+        configuration,
+        PropertyConfiguration::LastEditedTime { .. }
+    ));
+}
+
+#[test]
+fn parse_verified_page_with_expiration() {
+    let property: PropertyValue =
+        serde_json::from_str(include_str!("tests/verification_property.json")).unwrap();
+
+    match &property {
+        PropertyValue::Verification { verification, .. } => {
+            assert_eq!(verification.state, VerificationState::Verified);
+            assert_eq!(
+                verification.verified_by.as_ref().map(User::id),
+                Some(&UserId::from_str("01e46064-d5fb-4444-8ecc-ad47d076f804").unwrap())
+            );
+            let date = verification.date.as_ref().unwrap();
+            assert_eq!(
+                date.end,
+                Some(DateOrDateTime::DateTime(
+                    OffsetDateTime::parse("2023-10-30T04:00:00Z", &Rfc3339).unwrap()
+                ))
+            );
+        }
+        other => panic!("Expected a verification, got {:?}", other),
+    }
+    assert_round_trips(&property);
+}
+
+#[test]
+fn files_round_trip() {
+    let property: PropertyValue =
+        serde_json::from_str(include_str!("tests/files_property.json")).unwrap();
+
+    match &property {
+        PropertyValue::Files {
+            files: Some(files), ..
+        } => {
+            assert_eq!(files.len(), 2);
+            assert!(matches!(files[0].file, FileObject::File { .. }));
+            assert_eq!(
+                files[1],
+                FileReference::external("Logo", "https://example.com/logo.png")
+            );
+        }
+        other => panic!("Expected files, got {:?}", other),
+    }
+    assert_round_trips(&property);
+}
+
+#[test]
+fn unknown_property_type_is_kept() {
+    let json = json!({
+        "id": "x%3Fz",
+        "type": "sentiment",
+        "sentiment": {"score": 0.8, "label": "positive"}
+    });
+    let property: PropertyValue = serde_json::from_value(json.clone()).unwrap();
+
+    assert_eq!(property, PropertyValue::Unknown(json.clone()));
+    assert_eq!(property.as_plain_value(), None);
+    assert_eq!(serde_json::to_value(&property).unwrap(), json);
+}
+
+#[test]
+fn malformed_known_property_type_is_an_error() {
+    let error = serde_json::from_value::<PropertyValue>(json!({
+        "id": "a",
+        "type": "number",
+        "number": "abc"
+    }))
+    .unwrap_err();
+    assert!(error.to_string().contains("invalid type"), "{error}");
+
+    assert!(serde_json::from_value::<PropertyValue>(json!({
+        "id": "b",
+        "type": "date",
+        "date": {"start": "yesterday"}
+    }))
+    .is_err());
+
+    // Without a known type there is nothing to check the value against.
+    let untyped = json!({"id": "c", "number": "abc"});
+    assert_eq!(
+        serde_json::from_value::<PropertyValue>(untyped.clone()).unwrap(),
+        PropertyValue::Unknown(untyped)
+    );
+}
+
+#[test]
+fn parse_number_formats() {
+    let configuration: PropertyConfiguration = serde_json::from_value(json!({
+        "id": "%3Ea%3D%3C",
+        "type": "number",
+        "number": {"format": "dollar"}
+    }))
+    .unwrap();
+    assert_eq!(
+        configuration,
+        PropertyConfiguration::Number {
+            id: PropertyId::from_str("%3Ea%3D%3C").unwrap(),
+            number: NumberDetails {
+                format: NumberFormat::Dollar
+            },
+        }
+    );
+
+    for (format, expected) in [
+        ("number_with_commas", NumberFormat::NumberWithCommas),
+        ("percent", NumberFormat::Percent),
+        ("hong_kong_dollar", NumberFormat::HongKongDollar),
+        ("peruvian_sol", NumberFormat::PeruvianSol),
+    ] {
+        assert_eq!(
+            serde_json::from_value::<NumberFormat>(json!(format)).unwrap(),
+            expected
+        );
+        assert_eq!(serde_json::to_value(expected).unwrap(), json!(format));
+    }
+}
+
+#[test]
+fn empty_property_values() {
+    let empty_text: PropertyValue = serde_json::from_value(json!({
+        "id": "a",
+        "type": "rich_text",
+        "rich_text": [{
+            "type": "text",
+            "text": {"content": ""},
+            "plain_text": ""
+        }]
+    }))
+    .unwrap();
+    assert!(empty_text.is_empty());
+
+    let empty_multi_select: PropertyValue = serde_json::from_value(json!({
+        "id": "b",
+        "type": "multi_select",
+        "multi_select": []
+    }))
+    .unwrap();
+    assert!(empty_multi_select.is_empty());
+
+    let number: PropertyValue = serde_json::from_value(json!({
+        "id": "c",
+        "type": "number",
+        "number": 0
+    }))
+    .unwrap();
+    assert!(!number.is_empty());
+
+    let null_date: PropertyValue = serde_json::from_value(json!({
+        "id": "d",
+        "type": "date",
+        "date": null
+    }))
+    .unwrap();
+    assert!(null_date.is_empty());
+
+    let unchecked: PropertyValue = serde_json::from_value(json!({
+        "id": "e",
+        "type": "checkbox",
+        "checkbox": false
+    }))
+    .unwrap();
+    assert!(!unchecked.is_empty());
+
+    let unknown =
+        PropertyValue::Unknown(json!({"id": "f", "type": "sentiment", "sentiment": null}));
+    assert!(unknown.is_empty());
+}
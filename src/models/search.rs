@@ -5,6 +5,8 @@ use serde::ser::SerializeMap;
 use serde::{Serialize, Serializer};
 use time::OffsetDateTime;
 
+pub mod builder;
+
 #[derive(Serialize, Debug, Eq, PartialEq, Hash, Copy, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum SortDirection {
@@ -56,6 +58,72 @@ pub struct SearchRequest {
     paging: Option<Paging>,
 }
 
+impl SearchRequest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the results to pages and databases whose title matches `query`.
+    pub fn query<T: Into<String>>(
+        self,
+        query: T,
+    ) -> Self {
+        SearchRequest {
+            query: Some(query.into()),
+            ..self
+        }
+    }
+
+    /// Only return pages.
+    pub fn filter_pages(self) -> Self {
+        self.filter_object(FilterValue::Page)
+    }
+
+    /// Only return databases.
+    pub fn filter_databases(self) -> Self {
+        self.filter_object(FilterValue::Database)
+    }
+
+    fn filter_object(
+        self,
+        value: FilterValue,
+    ) -> Self {
+        SearchRequest {
+            filter: Some(Filter {
+                property: FilterProperty::Object,
+                value,
+            }),
+            ..self
+        }
+    }
+
+    pub fn sort_by_last_edited(
+        self,
+        direction: SortDirection,
+    ) -> Self {
+        SearchRequest {
+            sort: Some(Sort {
+                timestamp: SortTimestamp::LastEditedTime,
+                direction,
+            }),
+            ..self
+        }
+    }
+
+    pub fn page_size(
+        self,
+        page_size: u8,
+    ) -> Self {
+        SearchRequest {
+            paging: Some(Paging {
+                start_cursor: self.paging.and_then(|p| p.start_cursor),
+                ..Paging::new(page_size)
+            }),
+            ..self
+        }
+    }
+}
+
 impl Pageable for SearchRequest {
     fn start_from(
         self,
@@ -159,27 +227,27 @@ pub enum DateCondition {
     /// Only return pages where the page property value matches the provided date exactly.
     /// Note that the comparison is done against the date.
     /// Any time information sent will be ignored.
-    #[serde(with = "time::serde::iso8601")]
+    #[serde(with = "time::serde::rfc3339")]
     Equals(OffsetDateTime),
     /// Only return pages where the page property value is before the provided date.
     /// Note that the comparison is done against the date.
     /// Any time information sent will be ignored.
-    #[serde(with = "time::serde::iso8601")]
+    #[serde(with = "time::serde::rfc3339")]
     Before(OffsetDateTime),
     /// Only return pages where the page property value is after the provided date.
     /// Note that the comparison is done against the date.
     /// Any time information sent will be ignored.
-    #[serde(with = "time::serde::iso8601")]
+    #[serde(with = "time::serde::rfc3339")]
     After(OffsetDateTime),
     /// Only return pages where the page property value is on or before the provided date.
     /// Note that the comparison is done against the date.
     /// Any time information sent will be ignored.
-    #[serde(with = "time::serde::iso8601")]
+    #[serde(with = "time::serde::rfc3339")]
     OnOrBefore(OffsetDateTime),
     /// Only return pages where the page property value is on or after the provided date.
     /// Note that the comparison is done against the date.
     /// Any time information sent will be ignored.
-    #[serde(with = "time::serde::iso8601")]
+    #[serde(with = "time::serde::rfc3339")]
     OnOrAfter(OffsetDateTime),
     /// Only return pages where the page property value is empty.
     #[serde(serialize_with = "serialize_to_true")]
@@ -210,6 +278,7 @@ pub enum DateCondition {
 #[derive(Serialize, Debug, Eq, PartialEq, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum PeopleCondition {
+    /// Only return pages where the page property value contains the provided value.
     Contains(UserId),
     /// Only return pages where the page property value does not contain the provided value.
     DoesNotContain(UserId),
@@ -274,6 +343,10 @@ pub enum PropertyCondition {
     MultiSelect(MultiSelectCondition),
     Date(DateCondition),
     People(PeopleCondition),
+    /// The same operators as [PropertyCondition::People], for created by properties.
+    CreatedBy(PeopleCondition),
+    /// The same operators as [PropertyCondition::People], for last edited by properties.
+    LastEditedBy(PeopleCondition),
     Files(FilesCondition),
     Relation(RelationCondition),
     Formula(FormulaCondition),
@@ -291,6 +364,33 @@ pub enum FilterCondition {
     And { and: Vec<FilterCondition> },
     /// Returns pages when **any** of the filters inside the provided vector match.
     Or { or: Vec<FilterCondition> },
+    /// Filters by when pages were created or last edited rather than by a property.
+    /// See <https://developers.notion.com/reference/post-database-query-filter#timestamp>
+    #[serde(serialize_with = "serialize_timestamp_condition")]
+    Timestamp {
+        timestamp: DatabaseSortTimestamp,
+        date_operator: DateCondition,
+    },
+}
+
+/// Timestamp filters nest the condition under the name of the timestamp,
+/// e.g. `{"timestamp": "created_time", "created_time": {"after": ...}}`.
+fn serialize_timestamp_condition<S>(
+    timestamp: &DatabaseSortTimestamp,
+    date_operator: &DateCondition,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let key = match timestamp {
+        DatabaseSortTimestamp::CreatedTime => "created_time",
+        DatabaseSortTimestamp::LastEditedTime => "last_edited_time",
+    };
+    let mut map = serializer.serialize_map(Some(2))?;
+    map.serialize_entry("timestamp", timestamp)?;
+    map.serialize_entry(key, date_operator)?;
+    map.end()
 }
 
 #[derive(Serialize, Debug, Eq, PartialEq, Hash, Copy, Clone)]
@@ -323,6 +423,15 @@ pub struct DatabaseQuery {
     pub paging: Option<Paging>,
 }
 
+impl From<FilterCondition> for DatabaseQuery {
+    fn from(filter: FilterCondition) -> Self {
+        DatabaseQuery {
+            filter: Some(filter),
+            ..Default::default()
+        }
+    }
+}
+
 impl Pageable for DatabaseQuery {
     fn start_from(
         self,
@@ -532,4 +641,36 @@ mod tests {
             Ok(())
         }
     }
+
+    mod search_request {
+        use crate::models::search::{SearchRequest, SortDirection};
+        use serde_json::json;
+
+        #[test]
+        fn filter_pages() {
+            assert_eq!(
+                serde_json::to_value(SearchRequest::new().filter_pages()).unwrap(),
+                json!({"filter": {"property": "object", "value": "page"}})
+            );
+        }
+
+        #[test]
+        fn filter_databases_sorted() {
+            let request = SearchRequest::new()
+                .query("Tasks")
+                .filter_databases()
+                .sort_by_last_edited(SortDirection::Descending)
+                .page_size(10);
+
+            assert_eq!(
+                serde_json::to_value(request).unwrap(),
+                json!({
+                    "query": "Tasks",
+                    "filter": {"property": "object", "value": "database"},
+                    "sort": {"timestamp": "last_edited_time", "direction": "descending"},
+                    "page_size": 10
+                })
+            );
+        }
+    }
 }
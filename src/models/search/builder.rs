@@ -0,0 +1,742 @@
+//! Fluent construction of [`FilterCondition`]s and sorts for database queries.
+//!
+//! ```
+//! use notion::models::search::builder::{FilterBuilder, SortBuilder};
+//! use notion::models::search::{DatabaseQuery, DatabaseSortTimestamp, SortDirection};
+//!
+//! let filter = FilterBuilder::property("Status")
+//!     .select_equals("Done")
+//!     .and(FilterBuilder::property("Estimate").number_greater_than(3));
+//! let sorts = SortBuilder::property("Due", SortDirection::Ascending)
+//!     .then_timestamp(DatabaseSortTimestamp::LastEditedTime, SortDirection::Descending);
+//!
+//! let query = DatabaseQuery {
+//!     filter: Some(filter),
+//!     sorts: Some(sorts.build()),
+//!     ..Default::default()
+//! };
+//! ```
+
+use crate::ids::{PageId, UserId};
+use crate::models::search::{
+    CheckboxCondition, DatabaseSort, DatabaseSortTimestamp, DateCondition, FilesCondition,
+    FilterCondition, MultiSelectCondition, NumberCondition, PeopleCondition, PropertyCondition,
+    RelationCondition, SelectCondition, SortDirection, TextCondition,
+};
+use crate::models::Number;
+use time::OffsetDateTime;
+
+/// Entry point for building a filter against a single database property.
+///
+/// Each operator method consumes the builder and returns the finished
+/// [`FilterCondition`], which can then be combined with [`FilterCondition::and`]
+/// and [`FilterCondition::or`].
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct FilterBuilder {
+    property: String,
+}
+
+impl FilterBuilder {
+    pub fn property<S: Into<String>>(name: S) -> Self {
+        FilterBuilder {
+            property: name.into(),
+        }
+    }
+
+    /// Returns pages when **all** of the provided filters match.
+    pub fn all<I: IntoIterator<Item = FilterCondition>>(filters: I) -> FilterCondition {
+        FilterCondition::And {
+            and: filters.into_iter().collect(),
+        }
+    }
+
+    /// Returns pages when **any** of the provided filters match.
+    pub fn any<I: IntoIterator<Item = FilterCondition>>(filters: I) -> FilterCondition {
+        FilterCondition::Or {
+            or: filters.into_iter().collect(),
+        }
+    }
+
+    /// Returns pages whose creation or last edit time matches `condition`.
+    pub fn timestamp(
+        timestamp: DatabaseSortTimestamp,
+        condition: DateCondition,
+    ) -> FilterCondition {
+        FilterCondition::Timestamp {
+            timestamp,
+            date_operator: condition,
+        }
+    }
+
+    /// Returns pages created after `date`.
+    pub fn created_after(date: OffsetDateTime) -> FilterCondition {
+        Self::timestamp(
+            DatabaseSortTimestamp::CreatedTime,
+            DateCondition::After(date),
+        )
+    }
+
+    /// Returns pages last edited after `date`, e.g. to fetch the changes since the last sync.
+    pub fn last_edited_after(date: OffsetDateTime) -> FilterCondition {
+        Self::timestamp(
+            DatabaseSortTimestamp::LastEditedTime,
+            DateCondition::After(date),
+        )
+    }
+
+    fn condition(
+        self,
+        condition: PropertyCondition,
+    ) -> FilterCondition {
+        FilterCondition::Property {
+            property: self.property,
+            condition,
+        }
+    }
+
+    fn text(
+        self,
+        condition: TextCondition,
+    ) -> FilterCondition {
+        self.condition(PropertyCondition::RichText(condition))
+    }
+
+    pub fn text_equals<S: Into<String>>(
+        self,
+        value: S,
+    ) -> FilterCondition {
+        self.text(TextCondition::Equals(value.into()))
+    }
+
+    pub fn text_does_not_equal<S: Into<String>>(
+        self,
+        value: S,
+    ) -> FilterCondition {
+        self.text(TextCondition::DoesNotEqual(value.into()))
+    }
+
+    pub fn text_contains<S: Into<String>>(
+        self,
+        value: S,
+    ) -> FilterCondition {
+        self.text(TextCondition::Contains(value.into()))
+    }
+
+    pub fn text_does_not_contain<S: Into<String>>(
+        self,
+        value: S,
+    ) -> FilterCondition {
+        self.text(TextCondition::DoesNotContain(value.into()))
+    }
+
+    pub fn text_starts_with<S: Into<String>>(
+        self,
+        value: S,
+    ) -> FilterCondition {
+        self.text(TextCondition::StartsWith(value.into()))
+    }
+
+    pub fn text_ends_with<S: Into<String>>(
+        self,
+        value: S,
+    ) -> FilterCondition {
+        self.text(TextCondition::EndsWith(value.into()))
+    }
+
+    pub fn text_is_empty(self) -> FilterCondition {
+        self.text(TextCondition::IsEmpty)
+    }
+
+    pub fn text_is_not_empty(self) -> FilterCondition {
+        self.text(TextCondition::IsNotEmpty)
+    }
+
+    fn number(
+        self,
+        condition: NumberCondition,
+    ) -> FilterCondition {
+        self.condition(PropertyCondition::Number(condition))
+    }
+
+    pub fn number_equals<N: Into<Number>>(
+        self,
+        value: N,
+    ) -> FilterCondition {
+        self.number(NumberCondition::Equals(value.into()))
+    }
+
+    pub fn number_does_not_equal<N: Into<Number>>(
+        self,
+        value: N,
+    ) -> FilterCondition {
+        self.number(NumberCondition::DoesNotEqual(value.into()))
+    }
+
+    pub fn number_greater_than<N: Into<Number>>(
+        self,
+        value: N,
+    ) -> FilterCondition {
+        self.number(NumberCondition::GreaterThan(value.into()))
+    }
+
+    pub fn number_less_than<N: Into<Number>>(
+        self,
+        value: N,
+    ) -> FilterCondition {
+        self.number(NumberCondition::LessThan(value.into()))
+    }
+
+    pub fn number_greater_than_or_equal_to<N: Into<Number>>(
+        self,
+        value: N,
+    ) -> FilterCondition {
+        self.number(NumberCondition::GreaterThanOrEqualTo(value.into()))
+    }
+
+    pub fn number_less_than_or_equal_to<N: Into<Number>>(
+        self,
+        value: N,
+    ) -> FilterCondition {
+        self.number(NumberCondition::LessThanOrEqualTo(value.into()))
+    }
+
+    pub fn number_is_empty(self) -> FilterCondition {
+        self.number(NumberCondition::IsEmpty)
+    }
+
+    pub fn number_is_not_empty(self) -> FilterCondition {
+        self.number(NumberCondition::IsNotEmpty)
+    }
+
+    pub fn checkbox_equals(
+        self,
+        value: bool,
+    ) -> FilterCondition {
+        self.condition(PropertyCondition::Checkbox(CheckboxCondition::Equals(
+            value,
+        )))
+    }
+
+    pub fn checkbox_does_not_equal(
+        self,
+        value: bool,
+    ) -> FilterCondition {
+        self.condition(PropertyCondition::Checkbox(
+            CheckboxCondition::DoesNotEqual(value),
+        ))
+    }
+
+    fn select(
+        self,
+        condition: SelectCondition,
+    ) -> FilterCondition {
+        self.condition(PropertyCondition::Select(condition))
+    }
+
+    pub fn select_equals<S: Into<String>>(
+        self,
+        value: S,
+    ) -> FilterCondition {
+        self.select(SelectCondition::Equals(value.into()))
+    }
+
+    pub fn select_does_not_equal<S: Into<String>>(
+        self,
+        value: S,
+    ) -> FilterCondition {
+        self.select(SelectCondition::DoesNotEqual(value.into()))
+    }
+
+    pub fn select_is_empty(self) -> FilterCondition {
+        self.select(SelectCondition::IsEmpty)
+    }
+
+    pub fn select_is_not_empty(self) -> FilterCondition {
+        self.select(SelectCondition::IsNotEmpty)
+    }
+
+    fn multi_select(
+        self,
+        condition: MultiSelectCondition,
+    ) -> FilterCondition {
+        self.condition(PropertyCondition::MultiSelect(condition))
+    }
+
+    pub fn multi_select_contains<S: Into<String>>(
+        self,
+        value: S,
+    ) -> FilterCondition {
+        self.multi_select(MultiSelectCondition::Contains(value.into()))
+    }
+
+    pub fn multi_select_does_not_contain<S: Into<String>>(
+        self,
+        value: S,
+    ) -> FilterCondition {
+        self.multi_select(MultiSelectCondition::DoesNotContain(value.into()))
+    }
+
+    pub fn multi_select_is_empty(self) -> FilterCondition {
+        self.multi_select(MultiSelectCondition::IsEmpty)
+    }
+
+    pub fn multi_select_is_not_empty(self) -> FilterCondition {
+        self.multi_select(MultiSelectCondition::IsNotEmpty)
+    }
+
+    fn date(
+        self,
+        condition: DateCondition,
+    ) -> FilterCondition {
+        self.condition(PropertyCondition::Date(condition))
+    }
+
+    pub fn date_equals(
+        self,
+        value: OffsetDateTime,
+    ) -> FilterCondition {
+        self.date(DateCondition::Equals(value))
+    }
+
+    pub fn date_before(
+        self,
+        value: OffsetDateTime,
+    ) -> FilterCondition {
+        self.date(DateCondition::Before(value))
+    }
+
+    pub fn date_after(
+        self,
+        value: OffsetDateTime,
+    ) -> FilterCondition {
+        self.date(DateCondition::After(value))
+    }
+
+    pub fn date_on_or_before(
+        self,
+        value: OffsetDateTime,
+    ) -> FilterCondition {
+        self.date(DateCondition::OnOrBefore(value))
+    }
+
+    pub fn date_on_or_after(
+        self,
+        value: OffsetDateTime,
+    ) -> FilterCondition {
+        self.date(DateCondition::OnOrAfter(value))
+    }
+
+    pub fn date_is_empty(self) -> FilterCondition {
+        self.date(DateCondition::IsEmpty)
+    }
+
+    pub fn date_is_not_empty(self) -> FilterCondition {
+        self.date(DateCondition::IsNotEmpty)
+    }
+
+    pub fn date_past_week(self) -> FilterCondition {
+        self.date(DateCondition::PastWeek)
+    }
+
+    pub fn date_past_month(self) -> FilterCondition {
+        self.date(DateCondition::PastMonth)
+    }
+
+    pub fn date_past_year(self) -> FilterCondition {
+        self.date(DateCondition::PastYear)
+    }
+
+    pub fn date_next_week(self) -> FilterCondition {
+        self.date(DateCondition::NextWeek)
+    }
+
+    pub fn date_next_month(self) -> FilterCondition {
+        self.date(DateCondition::NextMonth)
+    }
+
+    pub fn date_next_year(self) -> FilterCondition {
+        self.date(DateCondition::NextYear)
+    }
+
+    fn people(
+        self,
+        condition: PeopleCondition,
+    ) -> FilterCondition {
+        self.condition(PropertyCondition::People(condition))
+    }
+
+    pub fn people_contains(
+        self,
+        value: UserId,
+    ) -> FilterCondition {
+        self.people(PeopleCondition::Contains(value))
+    }
+
+    pub fn people_does_not_contain(
+        self,
+        value: UserId,
+    ) -> FilterCondition {
+        self.people(PeopleCondition::DoesNotContain(value))
+    }
+
+    pub fn people_is_empty(self) -> FilterCondition {
+        self.people(PeopleCondition::IsEmpty)
+    }
+
+    pub fn people_is_not_empty(self) -> FilterCondition {
+        self.people(PeopleCondition::IsNotEmpty)
+    }
+
+    /// Filters a created by property, which supports the same operators as people properties.
+    pub fn created_by(
+        self,
+        condition: PeopleCondition,
+    ) -> FilterCondition {
+        self.condition(PropertyCondition::CreatedBy(condition))
+    }
+
+    /// Filters a last edited by property, which supports the same operators as people properties.
+    pub fn last_edited_by(
+        self,
+        condition: PeopleCondition,
+    ) -> FilterCondition {
+        self.condition(PropertyCondition::LastEditedBy(condition))
+    }
+
+    pub fn files_is_empty(self) -> FilterCondition {
+        self.condition(PropertyCondition::Files(FilesCondition::IsEmpty))
+    }
+
+    pub fn files_is_not_empty(self) -> FilterCondition {
+        self.condition(PropertyCondition::Files(FilesCondition::IsNotEmpty))
+    }
+
+    pub fn relation_contains(
+        self,
+        value: PageId,
+    ) -> FilterCondition {
+        self.condition(PropertyCondition::Relation(RelationCondition::Contains(
+            value,
+        )))
+    }
+
+    pub fn relation_does_not_contain(
+        self,
+        value: PageId,
+    ) -> FilterCondition {
+        self.condition(PropertyCondition::Relation(
+            RelationCondition::DoesNotContain(value),
+        ))
+    }
+
+    pub fn relation_is_empty(self) -> FilterCondition {
+        self.condition(PropertyCondition::Relation(RelationCondition::IsEmpty))
+    }
+
+    pub fn relation_is_not_empty(self) -> FilterCondition {
+        self.condition(PropertyCondition::Relation(RelationCondition::IsNotEmpty))
+    }
+}
+
+impl FilterCondition {
+    /// Combines two filters so that both must match.
+    ///
+    /// If `self` is already an `and` group the other filter is appended to it
+    /// rather than nesting a new group.
+    pub fn and(
+        self,
+        other: FilterCondition,
+    ) -> FilterCondition {
+        match self {
+            FilterCondition::And { mut and } => {
+                and.push(other);
+                FilterCondition::And { and }
+            }
+            condition => FilterCondition::And {
+                and: vec![condition, other],
+            },
+        }
+    }
+
+    /// Combines two filters so that either may match.
+    ///
+    /// If `self` is already an `or` group the other filter is appended to it
+    /// rather than nesting a new group.
+    pub fn or(
+        self,
+        other: FilterCondition,
+    ) -> FilterCondition {
+        match self {
+            FilterCondition::Or { mut or } => {
+                or.push(other);
+                FilterCondition::Or { or }
+            }
+            condition => FilterCondition::Or {
+                or: vec![condition, other],
+            },
+        }
+    }
+}
+
+/// Builds the sorts of a database query, the first criterion takes precedence and the
+/// following ones break ties.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct SortBuilder {
+    sorts: Vec<DatabaseSort>,
+}
+
+impl SortBuilder {
+    /// Sorts by the value of the property `name`.
+    pub fn property<S: Into<String>>(
+        name: S,
+        direction: SortDirection,
+    ) -> Self {
+        SortBuilder { sorts: vec![] }.then_property(name, direction)
+    }
+
+    /// Sorts by when pages were created or last edited.
+    pub fn timestamp(
+        timestamp: DatabaseSortTimestamp,
+        direction: SortDirection,
+    ) -> Self {
+        SortBuilder { sorts: vec![] }.then_timestamp(timestamp, direction)
+    }
+
+    pub fn then_property<S: Into<String>>(
+        mut self,
+        name: S,
+        direction: SortDirection,
+    ) -> Self {
+        self.sorts.push(DatabaseSort {
+            property: Some(name.into()),
+            timestamp: None,
+            direction,
+        });
+        self
+    }
+
+    pub fn then_timestamp(
+        mut self,
+        timestamp: DatabaseSortTimestamp,
+        direction: SortDirection,
+    ) -> Self {
+        self.sorts.push(DatabaseSort {
+            property: None,
+            timestamp: Some(timestamp),
+            direction,
+        });
+        self
+    }
+
+    pub fn build(self) -> Vec<DatabaseSort> {
+        self.sorts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FilterBuilder, SortBuilder};
+    use crate::ids::{PageId, UserId};
+    use crate::models::search::{
+        DatabaseQuery, DatabaseSortTimestamp, PeopleCondition, SortDirection,
+    };
+    use serde_json::json;
+    use std::str::FromStr;
+    use time::format_description::well_known::Rfc3339;
+    use time::OffsetDateTime;
+
+    #[test]
+    fn select_equals() -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::to_value(FilterBuilder::property("Status").select_equals("Done"))?;
+        assert_eq!(
+            json,
+            json!({"property":"Status","select":{"equals":"Done"}})
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn operators_without_values() -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::to_value(FilterBuilder::all([
+            FilterBuilder::property("Name").text_is_empty(),
+            FilterBuilder::property("Tags").multi_select_is_not_empty(),
+            FilterBuilder::property("Due").date_past_week(),
+            FilterBuilder::property("Owner").people_is_empty(),
+        ]))?;
+        assert_eq!(
+            json,
+            json!({"and":[
+                {"property":"Name","rich_text":{"is_empty":true}},
+                {"property":"Tags","multi_select":{"is_not_empty":true}},
+                {"property":"Due","date":{"past_week":{}}},
+                {"property":"Owner","people":{"is_empty":true}}
+            ]})
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn date_and_people_values() -> Result<(), Box<dyn std::error::Error>> {
+        let user = UserId::from_str("6a3a8b4c-72d0-4fd8-b4d9-8d1e7ab3f5c1")?;
+        let due = OffsetDateTime::parse("2021-05-10T12:00:00Z", &Rfc3339)?;
+        let json = serde_json::to_value(
+            FilterBuilder::property("Due")
+                .date_on_or_after(due)
+                .and(FilterBuilder::property("Owner").people_contains(user)),
+        )?;
+        assert_eq!(
+            json,
+            json!({"and":[
+                {"property":"Due","date":{"on_or_after":"2021-05-10T12:00:00Z"}},
+                {"property":"Owner","people":{"contains":"6a3a8b4c-72d0-4fd8-b4d9-8d1e7ab3f5c1"}}
+            ]})
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn people_operators() -> Result<(), Box<dyn std::error::Error>> {
+        let reviewer = UserId::from_str("6a3a8b4c-72d0-4fd8-b4d9-8d1e7ab3f5c1")?;
+        let json = serde_json::to_value(FilterBuilder::all([
+            FilterBuilder::property("Assignee").people_contains(reviewer.clone()),
+            FilterBuilder::property("Reviewers").people_does_not_contain(reviewer.clone()),
+            FilterBuilder::property("Author").created_by(PeopleCondition::Contains(reviewer)),
+            FilterBuilder::property("Editor").last_edited_by(PeopleCondition::IsNotEmpty),
+        ]))?;
+        assert_eq!(
+            json,
+            json!({"and":[
+                {"property":"Assignee","people":{"contains":"6a3a8b4c-72d0-4fd8-b4d9-8d1e7ab3f5c1"}},
+                {"property":"Reviewers","people":{"does_not_contain":"6a3a8b4c-72d0-4fd8-b4d9-8d1e7ab3f5c1"}},
+                {"property":"Author","created_by":{"contains":"6a3a8b4c-72d0-4fd8-b4d9-8d1e7ab3f5c1"}},
+                {"property":"Editor","last_edited_by":{"is_not_empty":true}}
+            ]})
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn relation_operators() -> Result<(), Box<dyn std::error::Error>> {
+        let project = PageId::from_str("bb85a889-3eb3-4146-9325-80508fb5e23d")?;
+        let json = serde_json::to_value(FilterBuilder::all([
+            FilterBuilder::property("Project").relation_contains(project.clone()),
+            FilterBuilder::property("Blocked by").relation_does_not_contain(project),
+            FilterBuilder::property("Parent").relation_is_empty(),
+            FilterBuilder::property("Sub-items").relation_is_not_empty(),
+        ]))?;
+        assert_eq!(
+            json,
+            json!({"and":[
+                {"property":"Project","relation":{"contains":"bb85a889-3eb3-4146-9325-80508fb5e23d"}},
+                {"property":"Blocked by","relation":{"does_not_contain":"bb85a889-3eb3-4146-9325-80508fb5e23d"}},
+                {"property":"Parent","relation":{"is_empty":true}},
+                {"property":"Sub-items","relation":{"is_not_empty":true}}
+            ]})
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn chained_and_flattens() -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::to_value(
+            FilterBuilder::property("Seen")
+                .checkbox_equals(false)
+                .and(FilterBuilder::property("Visitors").number_greater_than(1000000))
+                .and(FilterBuilder::property("Name").text_starts_with("The")),
+        )?;
+        assert_eq!(
+            json,
+            json!({"and":[
+                {"property":"Seen","checkbox":{"equals":false}},
+                {"property":"Visitors","number":{"greater_than":1000000}},
+                {"property":"Name","rich_text":{"starts_with":"The"}}
+            ]})
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn nested_groups() -> Result<(), Box<dyn std::error::Error>> {
+        let filter = FilterBuilder::property("Description")
+            .text_contains("fish")
+            .or(FilterBuilder::property("Food group")
+                .select_equals("🥦Vegetable")
+                .and(FilterBuilder::property("Is protein rich?").checkbox_equals(true))
+                .and(
+                    FilterBuilder::property("Tags")
+                        .multi_select_contains("Green")
+                        .or(FilterBuilder::property("Tags").multi_select_does_not_contain("Red")),
+                ));
+        let json = serde_json::to_value(DatabaseQuery::from(filter))?;
+        assert_eq!(
+            json,
+            json!({"filter":{"or":[
+                {"property":"Description","rich_text":{"contains":"fish"}},
+                {"and":[
+                    {"property":"Food group","select":{"equals":"🥦Vegetable"}},
+                    {"property":"Is protein rich?","checkbox":{"equals":true}},
+                    {"or":[
+                        {"property":"Tags","multi_select":{"contains":"Green"}},
+                        {"property":"Tags","multi_select":{"does_not_contain":"Red"}}
+                    ]}
+                ]}
+            ]}})
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn timestamp_filters() -> Result<(), Box<dyn std::error::Error>> {
+        let since = OffsetDateTime::parse("2022-05-13T20:08:00Z", &Rfc3339)?;
+        let json = serde_json::to_value(FilterBuilder::last_edited_after(since))?;
+        assert_eq!(
+            json,
+            json!({"timestamp":"last_edited_time","last_edited_time":{"after":"2022-05-13T20:08:00Z"}})
+        );
+
+        let json = serde_json::to_value(
+            FilterBuilder::created_after(since)
+                .and(FilterBuilder::property("Status").select_equals("Done")),
+        )?;
+        assert_eq!(
+            json,
+            json!({"and":[
+                {"timestamp":"created_time","created_time":{"after":"2022-05-13T20:08:00Z"}},
+                {"property":"Status","select":{"equals":"Done"}}
+            ]})
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn sort_by_property_then_timestamp() -> Result<(), Box<dyn std::error::Error>> {
+        let query = DatabaseQuery {
+            sorts: Some(
+                SortBuilder::property("Due", SortDirection::Ascending)
+                    .then_timestamp(
+                        DatabaseSortTimestamp::LastEditedTime,
+                        SortDirection::Descending,
+                    )
+                    .build(),
+            ),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(query)?,
+            json!({"sorts":[
+                {"property":"Due","direction":"ascending"},
+                {"timestamp":"last_edited_time","direction":"descending"}
+            ]})
+        );
+
+        Ok(())
+    }
+}
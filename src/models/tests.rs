@@ -1,10 +1,21 @@
-use crate::ids::{DatabaseId, PageId, UserId};
-use crate::models::properties::{DateOrDateTime, DateValue};
+use crate::ids::{BlockId, DatabaseId, PageId, UserId};
+use crate::models::block::{Block, FileObject};
+use crate::models::paging::{Paging, PagingCursor};
+use crate::models::properties::{
+    Color, DateOrDateTime, DateValue, PropertySchema, PropertyValue, SelectOptionSchema,
+    SelectSchema,
+};
+use crate::models::search::{DatabaseQuery, SearchRequest};
 use crate::models::text::{
-    Annotations, Link, MentionId, MentionObject, RichText, RichTextCommon, Text, TextColor,
+    Annotations, Link, LinkMention, LinkPreviewMention, MentionId, MentionObject, RichText,
+    RichTextBuilder, RichTextCommon, TemplateMention, TemplateMentionDate, TemplateMentionUser,
+    Text, TextColor, MAX_TEXT_CONTENT_LENGTH,
+};
+use crate::models::users::{Bot, BotOwner, Person, User, UserCommon};
+use crate::models::{
+    DatabaseUpdateRequest, ListResponse, Object, Page, PageUpdateRequest, Parent, Properties,
 };
-use crate::models::users::{Person, User, UserCommon};
-use crate::models::{ListResponse, Object, Page};
+use serde_json::json;
 use std::str::FromStr;
 use time::format_description::well_known::Iso8601;
 use time::{Date, Month, OffsetDateTime};
@@ -55,6 +66,142 @@ fn rich_text() {
     )
 }
 
+#[test]
+fn rich_text_links() {
+    let unlinked: RichText =
+        serde_json::from_str(include_str!("tests/rich_text_unlinked.json")).unwrap();
+    match &unlinked {
+        RichText::Text { rich_text, text } => {
+            assert_eq!(text.link, None);
+            assert_eq!(rich_text.href, None);
+        }
+        other => panic!("Expected text, got {:?}", other),
+    }
+    // links are left out instead of being sent as null
+    assert_eq!(
+        serde_json::to_value(&unlinked).unwrap()["text"],
+        json!({"content": "Plain"})
+    );
+
+    let linked: RichText = serde_json::from_str(include_str!("tests/rich_text_text.json")).unwrap();
+    let linked_json = serde_json::to_value(&linked).unwrap();
+    assert_eq!(
+        linked_json["text"]["link"],
+        json!({"url": "https://github.com/jakeswenson/notion"})
+    );
+    assert_eq!(
+        linked_json["href"],
+        json!("https://github.com/jakeswenson/notion")
+    );
+}
+
+#[test]
+fn rich_text_display() {
+    let rich_text: RichText =
+        serde_json::from_str(include_str!("tests/rich_text_text.json")).unwrap();
+    assert_eq!(rich_text.to_string(), "Rich");
+    assert_eq!(
+        rich_text.to_markdown(),
+        "[***~~`Rich`~~***](https://github.com/jakeswenson/notion)"
+    );
+}
+
+#[test]
+fn rich_text_builder_segments() {
+    let text = RichTextBuilder::new()
+        .text("Read the ")
+        .text("docs")
+        .bold()
+        .italic()
+        .link("https://developers.notion.com")
+        .text(" now")
+        .color(TextColor::Red)
+        .build();
+
+    assert_eq!(
+        text,
+        vec![
+            RichText::Text {
+                rich_text: RichTextCommon {
+                    plain_text: "Read the ".to_string(),
+                    href: None,
+                    annotations: None,
+                },
+                text: Text {
+                    content: "Read the ".to_string(),
+                    link: None,
+                },
+            },
+            RichText::Text {
+                rich_text: RichTextCommon {
+                    plain_text: "docs".to_string(),
+                    href: Some("https://developers.notion.com".to_string()),
+                    annotations: Some(Annotations {
+                        bold: Some(true),
+                        italic: Some(true),
+                        ..Default::default()
+                    }),
+                },
+                text: Text {
+                    content: "docs".to_string(),
+                    link: Some(Link {
+                        url: "https://developers.notion.com".to_string()
+                    }),
+                },
+            },
+            RichText::Text {
+                rich_text: RichTextCommon {
+                    plain_text: " now".to_string(),
+                    href: None,
+                    annotations: Some(Annotations {
+                        color: Some(TextColor::Red),
+                        ..Default::default()
+                    }),
+                },
+                text: Text {
+                    content: " now".to_string(),
+                    link: None,
+                },
+            },
+        ]
+    );
+
+    // annotations without a segment are ignored
+    assert!(RichTextBuilder::new().bold().build().is_empty());
+}
+
+#[test]
+fn annotations_from_default() {
+    let annotations = Annotations::default().bold().code();
+
+    assert_eq!(
+        annotations,
+        Annotations {
+            bold: Some(true),
+            code: Some(true),
+            color: Some(TextColor::Default),
+            italic: Some(false),
+            strikethrough: Some(false),
+            underline: Some(false),
+        }
+    );
+    assert_eq!(
+        serde_json::to_value(&annotations).unwrap(),
+        json!({
+            "bold": true,
+            "code": true,
+            "color": "default",
+            "italic": false,
+            "strikethrough": false,
+            "underline": false
+        })
+    );
+    assert_eq!(
+        Annotations::default().color(TextColor::Red).color,
+        Some(TextColor::Red)
+    );
+}
+
 #[test]
 fn rich_text_mention_user_person() {
     let rich_text_mention_user_person: RichText =
@@ -86,7 +233,7 @@ fn rich_text_mention_user_person() {
           },
           tag: "person".to_string(),
           person: Person {
-            email: "john.doe@gmail.com".to_string()
+            email: Some("john.doe@gmail.com".to_string())
           },
         }
       }, href: None,
@@ -293,3 +440,649 @@ fn rich_text_mention_database() {
         }
     );
 }
+
+#[test]
+fn rich_text_mention_link_preview() {
+    let rich_text: RichText =
+        serde_json::from_str(include_str!("tests/rich_text_mention_link_preview.json")).unwrap();
+    let RichText::Mention { mention, .. } = &rich_text else {
+        panic!("Expected a mention, got {:?}", rich_text);
+    };
+    assert_eq!(
+        mention,
+        &MentionObject::LinkPreview {
+            link_preview: LinkPreviewMention {
+                url: "https://github.com/nivereno/notion/pull/1".to_string()
+            }
+        }
+    );
+    assert_eq!(
+        rich_text.plain_text(),
+        "https://github.com/nivereno/notion/pull/1"
+    );
+}
+
+#[test]
+fn rich_text_mention_link() {
+    let rich_text: RichText =
+        serde_json::from_str(include_str!("tests/rich_text_mention_link.json")).unwrap();
+    let RichText::Mention { mention, .. } = &rich_text else {
+        panic!("Expected a mention, got {:?}", rich_text);
+    };
+    assert_eq!(
+        mention,
+        &MentionObject::LinkMention {
+            link_mention: LinkMention {
+                href: "https://github.com/nivereno/notion".to_string(),
+                title: Some("nivereno/notion".to_string()),
+                description: Some("A Notion API client for Rust".to_string()),
+                link_author: None,
+                link_provider: Some("GitHub".to_string()),
+                icon_url: Some("https://github.com/favicon.ico".to_string()),
+                thumbnail_url: None,
+            }
+        }
+    );
+    assert_eq!(rich_text.plain_text(), "nivereno/notion");
+}
+
+#[test]
+fn rich_text_mention_template_date() {
+    let rich_text: RichText =
+        serde_json::from_str(include_str!("tests/rich_text_mention_template_date.json")).unwrap();
+    let RichText::Mention { mention, .. } = &rich_text else {
+        panic!("Expected a mention, got {:?}", rich_text);
+    };
+    assert_eq!(
+        mention,
+        &MentionObject::TemplateMention {
+            template_mention: TemplateMention::TemplateMentionDate {
+                template_mention_date: TemplateMentionDate::Today
+            }
+        }
+    );
+    assert_eq!(rich_text.plain_text(), "@Today");
+
+    let mut json: serde_json::Value =
+        serde_json::from_str(include_str!("tests/rich_text_mention_template_date.json")).unwrap();
+    json["plain_text"] = json!("");
+    let without_text: RichText = serde_json::from_value(json).unwrap();
+    assert_eq!(without_text.plain_text(), "@Today");
+
+    let user: TemplateMention = serde_json::from_value(json!({
+        "type": "template_mention_user",
+        "template_mention_user": "me"
+    }))
+    .unwrap();
+    assert_eq!(
+        user,
+        TemplateMention::TemplateMentionUser {
+            template_mention_user: TemplateMentionUser::Me
+        }
+    );
+}
+
+#[test]
+fn rich_text_chunked_at_word_boundaries() {
+    let word = "abcd ";
+    let content = word.repeat(1000);
+    assert_eq!(content.len(), 5000);
+
+    let chunks = RichText::from_plain_chunked(&content);
+    assert_eq!(chunks.len(), 3);
+    assert!(chunks
+        .iter()
+        .all(|chunk| chunk.plain_text().len() <= MAX_TEXT_CONTENT_LENGTH
+            && chunk.plain_text().ends_with(' ')));
+    assert_eq!(
+        chunks.iter().map(RichText::plain_text).collect::<String>(),
+        content
+    );
+}
+
+#[test]
+fn rich_text_chunked_keeps_annotations() {
+    let content = "x".repeat(4500);
+    let annotations = Annotations {
+        bold: Some(true),
+        ..Default::default()
+    };
+    let rich_text = RichText::Text {
+        rich_text: RichTextCommon {
+            plain_text: content.clone(),
+            href: None,
+            annotations: Some(annotations.clone()),
+        },
+        text: Text {
+            content: content.clone(),
+            link: Some(Link {
+                url: "https://example.com".to_string(),
+            }),
+        },
+    };
+
+    let chunks = rich_text.chunked();
+    let lengths: Vec<_> = chunks
+        .iter()
+        .map(|chunk| chunk.plain_text().len())
+        .collect();
+    assert_eq!(lengths, vec![2000, 2000, 500]);
+    for chunk in chunks {
+        match chunk {
+            RichText::Text { rich_text, text } => {
+                assert_eq!(rich_text.annotations.as_ref(), Some(&annotations));
+                assert_eq!(text.content, rich_text.plain_text);
+                assert!(text.link.is_some());
+            }
+            other => panic!("Expected text, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn inline_database() {
+    let object: Object = serde_json::from_str(include_str!("tests/inline_database.json")).unwrap();
+    let Object::Database { database } = object else {
+        panic!("Expected a database, got {:?}", object);
+    };
+
+    assert_eq!(database.is_inline, Some(true));
+    assert_eq!(database.archived, Some(false));
+    assert_eq!(database.in_trash, Some(false));
+    assert_eq!(
+        database
+            .description
+            .iter()
+            .map(RichText::plain_text)
+            .collect::<String>(),
+        "Everything in the shed"
+    );
+    assert_eq!(
+        database.url.as_deref(),
+        Some("https://www.notion.so/5d794de0222449d386f93540db13d884")
+    );
+    assert_eq!(database.public_url, None);
+}
+
+#[test]
+fn serialize_database_update_request() {
+    let request = DatabaseUpdateRequest::default()
+        .rename_property("Assignee", "Owner")
+        .set_property(
+            "Priority",
+            PropertySchema::Select(SelectSchema {
+                options: vec![
+                    SelectOptionSchema {
+                        id: None,
+                        name: "High".to_string(),
+                        color: Some(Color::Red),
+                    },
+                    SelectOptionSchema {
+                        id: None,
+                        name: "Low".to_string(),
+                        color: None,
+                    },
+                ],
+            }),
+        )
+        .remove_property("Obsolete");
+
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        json!({
+            "properties": {
+                "Assignee": {"name": "Owner"},
+                "Obsolete": null,
+                "Priority": {"select": {"options": [
+                    {"name": "High", "color": "red"},
+                    {"name": "Low"}
+                ]}}
+            }
+        })
+    );
+}
+
+#[test]
+fn paging_clamps_page_size() {
+    assert_eq!(Paging::new(50).page_size, Some(50));
+    assert_eq!(Paging::new(250).page_size, Some(100));
+    assert_eq!(Paging::new(0).page_size, Some(1));
+
+    let request = SearchRequest::default().page_size(200);
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        json!({"page_size": 100})
+    );
+}
+
+#[test]
+fn paging_with_cursor() {
+    let cursor = PagingCursor("fe2cc560-036c-44cd-90e8-294d5a74cebc".to_string());
+    let query = DatabaseQuery {
+        paging: Some(Paging::new(25).with_cursor(cursor.clone())),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        serde_json::to_value(&query).unwrap(),
+        json!({
+            "start_cursor": "fe2cc560-036c-44cd-90e8-294d5a74cebc",
+            "page_size": 25
+        })
+    );
+    assert_eq!(
+        Paging::default().with_cursor(cursor.clone()),
+        Paging {
+            start_cursor: Some(cursor),
+            page_size: None,
+        }
+    );
+}
+
+#[test]
+fn list_with_unknown_object_type() {
+    let page: serde_json::Value = serde_json::from_str(include_str!("tests/page.json")).unwrap();
+    let list: ListResponse<Object> = serde_json::from_value(json!({
+        "object": "list",
+        "results": [
+            page,
+            {"object": "workspace_view", "id": "0a0b0c0d-0000-4000-8000-000000000000", "name": "Board"}
+        ],
+        "next_cursor": null,
+        "has_more": false
+    }))
+    .unwrap();
+
+    assert_eq!(list.results().len(), 2);
+    assert!(list.results()[1].is_unknown());
+    // The raw object is kept and written back as it was
+    assert_eq!(
+        serde_json::to_value(&list.results()[1]).unwrap(),
+        json!({"object": "workspace_view", "id": "0a0b0c0d-0000-4000-8000-000000000000", "name": "Board"})
+    );
+
+    let pages = list.expect_pages().unwrap();
+    assert_eq!(pages.results().len(), 1);
+}
+
+#[test]
+fn malformed_known_object_is_an_error() {
+    let page = json!({"object": "page", "id": "not a page"});
+    assert!(serde_json::from_value::<Object>(page).is_err());
+}
+
+#[test]
+fn list_response_next_paging() {
+    let with_cursor: ListResponse<Object> = serde_json::from_value(json!({
+        "object": "list",
+        "results": [],
+        "next_cursor": "fe2cc560-036c-44cd-90e8-294d5a74cebc",
+        "has_more": true
+    }))
+    .unwrap();
+    assert_eq!(
+        with_cursor.next_paging(),
+        Some(Paging {
+            start_cursor: Some(PagingCursor(
+                "fe2cc560-036c-44cd-90e8-294d5a74cebc".to_string()
+            )),
+            page_size: None,
+        })
+    );
+
+    let last_page: ListResponse<Object> = serde_json::from_value(json!({
+        "object": "list",
+        "results": [],
+        "next_cursor": null,
+        "has_more": false
+    }))
+    .unwrap();
+    assert_eq!(last_page.next_paging(), None);
+}
+
+#[test]
+fn serialize_archive_page_request() {
+    assert_eq!(
+        serde_json::to_value(PageUpdateRequest::archived(true)).unwrap(),
+        json!({"archived": true, "properties": {}})
+    );
+}
+
+#[test]
+fn serialize_move_page_request() {
+    let parent = Parent::Page {
+        page_id: PageId::from_str("bb85a889-3eb3-4146-9325-80508fb5e23d").unwrap(),
+    };
+    let request = PageUpdateRequest::moved_to(parent);
+
+    assert!(request.validate().is_ok());
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        json!({
+            "properties": {},
+            "parent": {"type": "page_id", "page_id": "bb85a889-3eb3-4146-9325-80508fb5e23d"}
+        })
+    );
+    assert!(PageUpdateRequest::moved_to(Parent::Workspace)
+        .validate()
+        .is_err());
+}
+
+#[test]
+fn bot_user_with_workspace_owner() {
+    let object: Object = serde_json::from_str(include_str!("tests/bot_user.json")).unwrap();
+    assert_eq!(
+        object,
+        Object::User {
+            user: User::Bot {
+                common: UserCommon {
+                    id: UserId::from_str("16d84278-ab0e-484c-9bdd-b35da3bd8905").unwrap(),
+                    name: Some("pied piper".to_string()),
+                    avatar_url: None,
+                },
+                tag: "bot".to_string(),
+                bot: Bot {
+                    email: None,
+                    owner: Some(BotOwner::Workspace { workspace: true }),
+                    workspace_name: Some("Pied Piper".to_string()),
+                },
+            }
+        }
+    );
+}
+
+#[test]
+fn text_color_round_trips() {
+    let color: TextColor = serde_json::from_value(json!("red_background")).unwrap();
+    assert_eq!(color, TextColor::RedBackground);
+
+    for (color, name) in [
+        (TextColor::Default, "default"),
+        (TextColor::Gray, "gray"),
+        (TextColor::BlueBackground, "blue_background"),
+        (TextColor::PinkBackground, "pink_background"),
+    ] {
+        assert_eq!(serde_json::to_value(color).unwrap(), json!(name));
+        assert_eq!(
+            serde_json::from_value::<TextColor>(json!(name)).unwrap(),
+            color
+        );
+    }
+    assert_eq!(TextColor::default(), TextColor::Default);
+}
+
+#[test]
+fn page_cover_round_trips() {
+    let mut json: serde_json::Value =
+        serde_json::from_str(include_str!("tests/page.json")).unwrap();
+    json["cover"] = json!({
+        "type": "external",
+        "external": {"url": "https://upload.wikimedia.org/wikipedia/commons/6/62/Tuscankale.jpg"}
+    });
+
+    let page: Page = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(
+        page.cover,
+        Some(FileObject::external(
+            "https://upload.wikimedia.org/wikipedia/commons/6/62/Tuscankale.jpg"
+        ))
+    );
+    let serialized = serde_json::to_value(&page).unwrap();
+    assert_eq!(serialized["cover"], json["cover"]);
+
+    let without_cover = Page {
+        cover: None,
+        ..page
+    };
+    let serialized = serde_json::to_value(&without_cover).unwrap();
+    assert!(serialized.get("cover").is_none());
+
+    let update = PageUpdateRequest {
+        cover: Some(FileObject::external("https://example.com/cover.png")),
+        ..PageUpdateRequest::archived(false)
+    };
+    assert_eq!(
+        serde_json::to_value(update).unwrap(),
+        json!({
+            "properties": {},
+            "archived": false,
+            "cover": {"type": "external", "external": {"url": "https://example.com/cover.png"}}
+        })
+    );
+}
+
+#[test]
+fn typed_property_accessors() {
+    let properties: Properties = serde_json::from_value(json!({
+        "Price": {"id": "a", "type": "number", "number": 12.5},
+        "Empty price": {"id": "b", "type": "number", "number": null},
+        "Status": {"id": "c", "type": "select", "select": {"id": "1", "name": "Done", "color": "green"}},
+        "Tags": {"id": "d", "type": "multi_select", "multi_select": [
+            {"id": "2", "name": "urgent", "color": "red"},
+            {"id": "3", "name": "home", "color": "blue"}
+        ]},
+        "Bought": {"id": "e", "type": "checkbox", "checkbox": true},
+        "Due": {"id": "f", "type": "date", "date": {"start": "2021-05-19", "end": null, "time_zone": null}}
+    }))
+    .unwrap();
+
+    assert_eq!(properties.get_number("Price"), Some(12.5));
+    assert_eq!(properties.get_number("Empty price"), None);
+    assert_eq!(properties.get_select("Status"), Some("Done"));
+    assert_eq!(properties.get_multi_select("Tags"), vec!["urgent", "home"]);
+    assert_eq!(properties.get_checkbox("Bought"), Some(true));
+    assert_eq!(
+        properties.get_date("Due"),
+        Some(&DateValue {
+            start: DateOrDateTime::Date(Date::from_calendar_date(2021, Month::May, 19).unwrap()),
+            end: None,
+            time_zone: None,
+        })
+    );
+
+    // Missing properties and mismatched types
+    assert_eq!(properties.get_number("Missing"), None);
+    assert_eq!(properties.get_number("Status"), None);
+    assert_eq!(properties.get_select("Price"), None);
+    assert_eq!(properties.get_checkbox("Tags"), None);
+    assert_eq!(properties.get_date("Bought"), None);
+    assert!(properties.get_multi_select("Due").is_empty());
+}
+
+#[test]
+fn string_property_accessors() {
+    let properties: Properties = serde_json::from_value(json!({
+        "Website": {"id": "a", "type": "url", "url": "https://developers.notion.com"},
+        "Email": {"id": "b", "type": "email", "email": "ada@example.com"},
+        "Phone": {"id": "c", "type": "phone_number", "phone_number": "+1 555 0100"},
+        "No website": {"id": "d", "type": "url", "url": null},
+        "No email": {"id": "e", "type": "email", "email": null},
+        "No phone": {"id": "f", "type": "phone_number", "phone_number": null}
+    }))
+    .unwrap();
+
+    assert_eq!(
+        properties.get_url("Website"),
+        Some("https://developers.notion.com")
+    );
+    assert_eq!(properties.get_email("Email"), Some("ada@example.com"));
+    assert_eq!(properties.get_phone("Phone"), Some("+1 555 0100"));
+    assert_eq!(properties.get_url("No website"), None);
+    assert_eq!(properties.get_email("No email"), None);
+    assert_eq!(properties.get_phone("No phone"), None);
+    assert_eq!(properties.get_url("Email"), None);
+    assert_eq!(properties.get_phone("Missing"), None);
+
+    let round_tripped: Properties =
+        serde_json::from_value(serde_json::to_value(&properties).unwrap()).unwrap();
+    assert_eq!(round_tripped, properties);
+}
+
+#[test]
+fn in_trash_is_optional() {
+    let mut page_json: serde_json::Value =
+        serde_json::from_str(include_str!("tests/page.json")).unwrap();
+    let mut block_json: serde_json::Value =
+        serde_json::from_str(include_str!("block/tests/callout.json")).unwrap();
+
+    let page: Page = serde_json::from_value(page_json.clone()).unwrap();
+    let block: Block = serde_json::from_value(block_json.clone()).unwrap();
+    assert_eq!(page.in_trash, None);
+    assert!(matches!(block, Block::Callout { common, .. } if common.in_trash.is_none()));
+
+    page_json["in_trash"] = json!(true);
+    block_json["in_trash"] = json!(false);
+    let page: Page = serde_json::from_value(page_json).unwrap();
+    let block: Block = serde_json::from_value(block_json).unwrap();
+    assert_eq!(page.in_trash, Some(true));
+    assert!(!page.archived);
+    assert!(matches!(block, Block::Callout { common, .. } if common.in_trash == Some(false)));
+    assert_eq!(
+        serde_json::to_value(&page).unwrap()["in_trash"],
+        json!(true)
+    );
+}
+
+fn text_block_json(
+    id: &str,
+    block_type: &str,
+    content: &str,
+) -> serde_json::Value {
+    json!({
+        "object": "block",
+        "id": id,
+        "created_time": "2022-05-13T20:08:00.000Z",
+        "last_edited_time": "2022-05-13T20:08:00.000Z",
+        "created_by": {"object": "user", "id": "e2507360-468c-4e0f-a928-7bbcbbb45353"},
+        "last_edited_by": {"object": "user", "id": "e2507360-468c-4e0f-a928-7bbcbbb45353"},
+        "has_children": false,
+        "archived": false,
+        "type": block_type,
+        block_type: {
+            "rich_text": [{"type": "text", "text": {"content": content}, "plain_text": content}],
+            "color": "default"
+        }
+    })
+}
+
+#[test]
+fn page_plain_text() {
+    let mut page: Page = serde_json::from_str(include_str!("tests/page.json")).unwrap();
+    assert_eq!(page.plain_text(), "");
+
+    let blocks: Vec<Block> = serde_json::from_value(json!([
+        text_block_json("b1f3c8a2-0000-4000-8000-000000000001", "heading_1", "Groceries"),
+        text_block_json("b1f3c8a2-0000-4000-8000-000000000002", "paragraph", "Milk and eggs."),
+        {
+            "object": "block",
+            "id": "b1f3c8a2-0000-4000-8000-000000000003",
+            "created_time": "2022-05-13T20:08:00.000Z",
+            "last_edited_time": "2022-05-13T20:08:00.000Z",
+            "created_by": {"object": "user", "id": "e2507360-468c-4e0f-a928-7bbcbbb45353"},
+            "last_edited_by": {"object": "user", "id": "e2507360-468c-4e0f-a928-7bbcbbb45353"},
+            "has_children": false,
+            "archived": false,
+            "type": "divider",
+            "divider": {}
+        },
+        text_block_json("b1f3c8a2-0000-4000-8000-000000000004", "paragraph", "Bread, too."),
+    ]))
+    .unwrap();
+    page.blocks = Some(blocks);
+
+    assert_eq!(page.plain_text(), "Groceries\nMilk and eggs.\nBread, too.");
+}
+
+#[test]
+fn parent_accessors() {
+    let page_id = PageId::from_str("5c6a2821-6bb1-4a7e-b6e1-c50111515c3d").unwrap();
+    let database_id = DatabaseId::from_str("5c6a2821-6bb1-4a7e-b6e1-c50111515c3d").unwrap();
+    let block_id = BlockId::from_str("5c6a2821-6bb1-4a7e-b6e1-c50111515c3d").unwrap();
+
+    let page = Parent::Page {
+        page_id: page_id.clone(),
+    };
+    assert_eq!(page.as_page_id(), Some(&page_id));
+    assert_eq!(page.as_database_id(), None);
+    assert_eq!(page.as_block_id(), None);
+    assert!(!page.is_workspace());
+
+    let database = Parent::Database {
+        database_id: database_id.clone(),
+    };
+    assert_eq!(database.as_database_id(), Some(&database_id));
+    assert_eq!(database.as_page_id(), None);
+
+    let block = Parent::Block {
+        block_id: block_id.clone(),
+    };
+    assert_eq!(block.as_block_id(), Some(&block_id));
+    assert_eq!(block.as_page_id(), None);
+
+    assert!(Parent::Workspace.is_workspace());
+    assert_eq!(Parent::Workspace.as_page_id(), None);
+    assert_eq!(Parent::Workspace.as_database_id(), None);
+    assert_eq!(Parent::Workspace.as_block_id(), None);
+}
+
+#[test]
+fn text_property_round_trip() {
+    let json = json!({
+        "Notes": {
+            "id": "a",
+            "type": "rich_text",
+            "rich_text": [
+                {
+                    "type": "text",
+                    "text": {"content": "Buy ", "link": null},
+                    "annotations": {
+                        "bold": false,
+                        "italic": false,
+                        "strikethrough": false,
+                        "underline": false,
+                        "code": false,
+                        "color": "default"
+                    },
+                    "plain_text": "Buy ",
+                    "href": null
+                },
+                {
+                    "type": "text",
+                    "text": {"content": "kale", "link": null},
+                    "annotations": {
+                        "bold": true,
+                        "italic": false,
+                        "strikethrough": false,
+                        "underline": false,
+                        "code": false,
+                        "color": "green"
+                    },
+                    "plain_text": "kale",
+                    "href": null
+                }
+            ]
+        },
+        "Name": {"id": "title", "type": "title", "title": []}
+    });
+    let properties: Properties = serde_json::from_value(json.clone()).unwrap();
+
+    assert!(matches!(
+        properties.properties.get("Notes"),
+        Some(PropertyValue::Text { .. })
+    ));
+    assert_eq!(properties.get_rich_text("Notes").map(Vec::len), Some(2));
+    assert_eq!(properties.get_text("Notes"), Some("Buy kale".to_string()));
+    assert_eq!(properties.get_text("Name"), None);
+    assert_eq!(properties.get_rich_text("Missing"), None);
+
+    let serialized = serde_json::to_value(&properties).unwrap();
+    assert_eq!(
+        serde_json::from_value::<Properties>(serialized.clone()).unwrap(),
+        properties
+    );
+    assert_eq!(serialized["Notes"]["type"], "rich_text");
+    assert_eq!(
+        serialized["Notes"]["rich_text"][1]["annotations"],
+        json["Notes"]["rich_text"][1]["annotations"]
+    );
+}
@@ -1,11 +1,16 @@
 use crate::ids::{DatabaseId, PageId};
+use crate::models::block::Equation;
 use crate::models::properties::DateValue;
 use crate::models::users::User;
 use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
 
-#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Copy, Clone)]
+/// The color of rich text and text blocks, as foreground or background color.
+/// See <https://developers.notion.com/reference/rich-text#the-annotation-object>
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Copy, Clone, Hash, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum TextColor {
+    #[default]
     Default,
     Gray,
     Brown,
@@ -29,7 +34,18 @@ pub enum TextColor {
 
 /// Rich text annotations
 /// See <https://developers.notion.com/reference/rich-text#annotations>
-#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+///
+/// The default is plain text like Notion's: every annotation off and the default color.
+/// Set annotations with the builder methods:
+///
+/// ```
+/// use notion::models::text::Annotations;
+///
+/// let annotations = Annotations::default().bold().code();
+/// assert_eq!(annotations.bold, Some(true));
+/// assert_eq!(annotations.italic, Some(false));
+/// ```
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
 pub struct Annotations {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bold: Option<bool>,
@@ -45,6 +61,66 @@ pub struct Annotations {
     pub underline: Option<bool>,
 }
 
+impl Default for Annotations {
+    fn default() -> Self {
+        Annotations {
+            bold: Some(false),
+            code: Some(false),
+            color: Some(TextColor::Default),
+            italic: Some(false),
+            strikethrough: Some(false),
+            underline: Some(false),
+        }
+    }
+}
+
+impl Annotations {
+    pub fn bold(self) -> Self {
+        Annotations {
+            bold: Some(true),
+            ..self
+        }
+    }
+
+    pub fn italic(self) -> Self {
+        Annotations {
+            italic: Some(true),
+            ..self
+        }
+    }
+
+    pub fn strikethrough(self) -> Self {
+        Annotations {
+            strikethrough: Some(true),
+            ..self
+        }
+    }
+
+    pub fn underline(self) -> Self {
+        Annotations {
+            underline: Some(true),
+            ..self
+        }
+    }
+
+    pub fn code(self) -> Self {
+        Annotations {
+            code: Some(true),
+            ..self
+        }
+    }
+
+    pub fn color(
+        self,
+        color: TextColor,
+    ) -> Self {
+        Annotations {
+            color: Some(color),
+            ..self
+        }
+    }
+}
+
 /// Properties common on all rich text objects
 /// See <https://developers.notion.com/reference/rich-text#all-rich-text>
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
@@ -90,14 +166,92 @@ pub enum MentionObject {
     Date {
         date: DateValue,
     },
-    // TODO: need to add LinkPreview
-    // LinkPreview {
-    //
-    // },
+    LinkPreview {
+        link_preview: LinkPreviewMention,
+    },
+    /// A link pasted as a mention, with the metadata Notion fetched from the linked page.
+    LinkMention {
+        link_mention: LinkMention,
+    },
+    /// Only found in template pages, replaced with the actual date or user once the template is used.
+    TemplateMention {
+        template_mention: TemplateMention,
+    },
     #[serde(other)]
     Unknown,
 }
 
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct LinkPreviewMention {
+    pub url: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct LinkMention {
+    pub href: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link_author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link_provider: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thumbnail_url: Option<String>,
+}
+
+impl MentionObject {
+    /// What Notion shows for the mention when no plain text was sent along,
+    /// e.g. for mentions created through the API.
+    fn placeholder(&self) -> &str {
+        match self {
+            MentionObject::LinkPreview { link_preview } => &link_preview.url,
+            MentionObject::LinkMention { link_mention } => {
+                link_mention.title.as_deref().unwrap_or(&link_mention.href)
+            }
+            MentionObject::TemplateMention { template_mention } => match template_mention {
+                TemplateMention::TemplateMentionDate {
+                    template_mention_date: TemplateMentionDate::Today,
+                } => "@Today",
+                TemplateMention::TemplateMentionDate {
+                    template_mention_date: TemplateMentionDate::Now,
+                } => "@Now",
+                TemplateMention::TemplateMentionUser { .. } => "@Me",
+            },
+            _ => "",
+        }
+    }
+}
+
+/// See <https://developers.notion.com/reference/rich-text#template-mention-type-object>
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+#[serde(tag = "type")]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateMention {
+    TemplateMentionDate {
+        template_mention_date: TemplateMentionDate,
+    },
+    TemplateMentionUser {
+        template_mention_user: TemplateMentionUser,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Copy, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateMentionDate {
+    Today,
+    Now,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Copy, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateMentionUser {
+    Me,
+}
+
 /// Rich text objects contain data for displaying formatted text, mentions, and equations.
 /// A rich text object also contains annotations for style information.
 /// Arrays of rich text objects are used within property objects and property
@@ -123,16 +277,266 @@ pub enum RichText {
     Equation {
         #[serde(flatten)]
         rich_text: RichTextCommon,
+        equation: Equation,
     },
 }
 
+/// The maximum length of the content of a single text object accepted by the API.
+/// See <https://developers.notion.com/reference/request-limits#limits-for-property-values>
+pub const MAX_TEXT_CONTENT_LENGTH: usize = 2000;
+
 impl RichText {
+    /// The text as displayed in Notion, without annotations.
+    /// Notion fills this in for mentions too, e.g. with the title of a mentioned page
+    /// or the formatted dates of a date mention.
+    /// Mentions of links and template values without plain text fall back to
+    /// the link or a placeholder like `@Today`.
     pub fn plain_text(&self) -> &str {
         use RichText::*;
         match self {
+            Mention {
+                rich_text, mention, ..
+            } if rich_text.plain_text.is_empty() => mention.placeholder(),
             Text { rich_text, .. } | Mention { rich_text, .. } | Equation { rich_text, .. } => {
                 &rich_text.plain_text
             }
         }
     }
+
+    /// Creates unannotated text objects, split so none of them exceed [MAX_TEXT_CONTENT_LENGTH].
+    pub fn from_plain_chunked(content: &str) -> Vec<RichText> {
+        RichText::Text {
+            rich_text: RichTextCommon {
+                plain_text: content.to_string(),
+                href: None,
+                annotations: None,
+            },
+            text: Text {
+                content: content.to_string(),
+                link: None,
+            },
+        }
+        .chunked()
+    }
+
+    /// Splits a text object whose content exceeds [MAX_TEXT_CONTENT_LENGTH] into several,
+    /// preferably at word boundaries. Annotations and links are kept on every segment.
+    /// Mentions and equations are returned unchanged.
+    pub fn chunked(self) -> Vec<RichText> {
+        match self {
+            RichText::Text { rich_text, text } => {
+                split_text(&text.content, MAX_TEXT_CONTENT_LENGTH)
+                    .into_iter()
+                    .map(|content| RichText::Text {
+                        rich_text: RichTextCommon {
+                            plain_text: content.to_string(),
+                            ..rich_text.clone()
+                        },
+                        text: Text {
+                            content: content.to_string(),
+                            link: text.link.clone(),
+                        },
+                    })
+                    .collect()
+            }
+            other => vec![other],
+        }
+    }
+}
+
+impl Display for RichText {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.write_str(self.plain_text())
+    }
+}
+
+/// Conversion into the rich text of a block or property.
+///
+/// Plain strings become unannotated text, split with [RichText::from_plain_chunked]
+/// so they don't exceed the API's length limit.
+pub trait IntoRichText {
+    fn into_rich_text(self) -> Vec<RichText>;
+}
+
+impl IntoRichText for &str {
+    fn into_rich_text(self) -> Vec<RichText> {
+        RichText::from_plain_chunked(self)
+    }
+}
+
+impl IntoRichText for String {
+    fn into_rich_text(self) -> Vec<RichText> {
+        RichText::from_plain_chunked(&self)
+    }
+}
+
+impl IntoRichText for &String {
+    fn into_rich_text(self) -> Vec<RichText> {
+        RichText::from_plain_chunked(self)
+    }
+}
+
+impl IntoRichText for RichText {
+    fn into_rich_text(self) -> Vec<RichText> {
+        vec![self]
+    }
+}
+
+impl IntoRichText for Vec<RichText> {
+    fn into_rich_text(self) -> Vec<RichText> {
+        self
+    }
+}
+
+impl IntoRichText for RichTextBuilder {
+    fn into_rich_text(self) -> Vec<RichText> {
+        self.build()
+    }
+}
+
+/// Composes text of several differently annotated segments.
+///
+/// Every [text](Self::text) call starts a new segment, the annotation methods apply to the
+/// last one:
+///
+/// ```
+/// use notion::models::text::RichTextBuilder;
+///
+/// let text = RichTextBuilder::new()
+///     .text("Read the ")
+///     .text("docs")
+///     .bold()
+///     .link("https://developers.notion.com")
+///     .text(" first.")
+///     .build();
+/// assert_eq!(text.len(), 3);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RichTextBuilder {
+    segments: Vec<RichText>,
+}
+
+impl RichTextBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a new unannotated segment.
+    pub fn text<S: Into<String>>(
+        mut self,
+        content: S,
+    ) -> Self {
+        let content = content.into();
+        self.segments.push(RichText::Text {
+            rich_text: RichTextCommon {
+                plain_text: content.clone(),
+                href: None,
+                annotations: None,
+            },
+            text: Text {
+                content,
+                link: None,
+            },
+        });
+        self
+    }
+
+    pub fn bold(self) -> Self {
+        self.annotate(Annotations::bold)
+    }
+
+    pub fn italic(self) -> Self {
+        self.annotate(Annotations::italic)
+    }
+
+    pub fn strikethrough(self) -> Self {
+        self.annotate(Annotations::strikethrough)
+    }
+
+    pub fn underline(self) -> Self {
+        self.annotate(Annotations::underline)
+    }
+
+    pub fn code(self) -> Self {
+        self.annotate(Annotations::code)
+    }
+
+    pub fn color(
+        self,
+        color: TextColor,
+    ) -> Self {
+        self.annotate(|annotations| annotations.color(color))
+    }
+
+    /// Links the current segment to `url`.
+    pub fn link<S: Into<String>>(
+        mut self,
+        url: S,
+    ) -> Self {
+        if let Some(RichText::Text { rich_text, text }) = self.segments.last_mut() {
+            let url = url.into();
+            rich_text.href = Some(url.clone());
+            text.link = Some(Link { url });
+        }
+        self
+    }
+
+    /// The segments, split where they exceed [MAX_TEXT_CONTENT_LENGTH].
+    pub fn build(self) -> Vec<RichText> {
+        self.segments
+            .into_iter()
+            .flat_map(RichText::chunked)
+            .collect()
+    }
+
+    /// Annotation methods called before the first segment do nothing.
+    fn annotate(
+        mut self,
+        update: impl FnOnce(Annotations) -> Annotations,
+    ) -> Self {
+        if let Some(RichText::Text { rich_text, .. }) = self.segments.last_mut() {
+            rich_text.annotations = Some(update(rich_text.annotations.take().unwrap_or_default()));
+        }
+        self
+    }
+}
+
+/// Splits `content` into pieces of at most `limit` UTF-16 code units (the unit Notion counts in),
+/// breaking after the last whitespace of a piece when there is one.
+fn split_text(
+    content: &str,
+    limit: usize,
+) -> Vec<&str> {
+    let mut chunks = vec![];
+    let mut rest = content;
+
+    loop {
+        let mut length = 0;
+        let end = rest
+            .char_indices()
+            .find(|(_, c)| {
+                length += c.len_utf16();
+                length > limit
+            })
+            .map(|(index, _)| index);
+
+        let end = match end {
+            Some(end) => end,
+            None => {
+                chunks.push(rest);
+                return chunks;
+            }
+        };
+
+        let split = rest[..end]
+            .rfind(char::is_whitespace)
+            .map(|index| index + rest[index..].chars().next().map_or(1, char::len_utf8))
+            .unwrap_or(end);
+        let (chunk, remainder) = rest.split_at(split);
+        chunks.push(chunk);
+        rest = remainder;
+    }
 }
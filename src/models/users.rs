@@ -13,12 +13,20 @@ pub struct UserCommon {
 
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
 pub struct Person {
-    pub email: String,
+    /// Only present if the integration has the user information with email capability.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
 pub struct Bot {
-    pub email: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// Only present when retrieving the bot of the current token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<BotOwner>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_name: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
@@ -38,9 +46,17 @@ pub enum User {
         tag: String,
         bot: Bot,
     },
-    Stub {
-        id: UserId,
-    },
+    /// A partial user, returned when the integration can't read user information.
+    Stub { id: UserId },
+}
+
+impl User {
+    pub fn id(&self) -> &UserId {
+        match self {
+            User::Person { common, .. } | User::Bot { common, .. } => &common.id,
+            User::Stub { id } => id,
+        }
+    }
 }
 
 impl Hash for UserCommon {
@@ -57,12 +73,7 @@ impl Hash for User {
         &self,
         state: &mut H,
     ) {
-        use User::*;
-        match self {
-            Person { common, .. } | Bot { common, .. } => &common.id,
-            Stub { id } => id,
-        }
-        .hash(state);
+        self.id().hash(state);
     }
 }
 
@@ -74,3 +85,17 @@ pub enum PeopleObject {
         common: UserCommon,
     },
 }
+
+/// Who a bot, or the access token of a public integration, belongs to.
+/// See <https://developers.notion.com/reference/user#bots>
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BotOwner {
+    /// Internal integrations are owned by the workspace, this is always `true`.
+    Workspace {
+        workspace: bool,
+    },
+    User {
+        user: Box<User>,
+    },
+}
@@ -0,0 +1,380 @@
+//! Checks of the request limits of the API, so oversized content can be caught before sending.
+//! See <https://developers.notion.com/reference/request-limits>
+
+use crate::models::block::CreateBlock;
+use crate::models::text::{RichText, MAX_TEXT_CONTENT_LENGTH};
+use std::fmt;
+
+/// The maximum number of elements of an array in a request, e.g. rich text or children.
+pub const MAX_ARRAY_LENGTH: usize = 100;
+/// The maximum length of a url, both of links in rich text and of embedded content.
+pub const MAX_URL_LENGTH: usize = 2000;
+/// The maximum length of the expression of an equation.
+pub const MAX_EQUATION_LENGTH: usize = 1000;
+
+/// A single exceeded limit. `path` locates the offending value, e.g. `children[2].rich_text[0]`.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Violation {
+    /// A string longer than `limit`, measured in UTF-16 code units like the API does.
+    TooLong {
+        path: String,
+        length: usize,
+        limit: usize,
+    },
+    /// An array with more than `limit` elements.
+    TooManyElements {
+        path: String,
+        count: usize,
+        limit: usize,
+    },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Violation::TooLong {
+                path,
+                length,
+                limit,
+            } => write!(
+                f,
+                "{path} is {length} characters long, at most {limit} are allowed"
+            ),
+            Violation::TooManyElements { path, count, limit } => write!(
+                f,
+                "{path} has {count} elements, at most {limit} are allowed"
+            ),
+        }
+    }
+}
+
+/// All limits exceeded by a value, in the order they were found.
+#[derive(Debug, Eq, PartialEq, Clone, thiserror::Error)]
+pub struct ValidationError {
+    pub violations: Vec<Violation>,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(f, "Request limits exceeded: ")?;
+        for (index, violation) in self.violations.iter().enumerate() {
+            if index > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{violation}")?;
+        }
+        Ok(())
+    }
+}
+
+impl RichText {
+    /// Checks the content and link of a single rich text object.
+    /// Use [validate_rich_text] for a whole array.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut validator = Validator::default();
+        validator.rich_text("rich_text", self);
+        validator.finish()
+    }
+}
+
+/// Checks every object of a rich text array as well as the length of the array.
+pub fn validate_rich_text(rich_text: &[RichText]) -> Result<(), ValidationError> {
+    let mut validator = Validator::default();
+    validator.rich_text_array("rich_text", rich_text);
+    validator.finish()
+}
+
+impl CreateBlock {
+    /// Checks the rich text, urls and children of this block and of all its descendants.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut validator = Validator::default();
+        validator.block("", self);
+        validator.finish()
+    }
+}
+
+#[derive(Default)]
+struct Validator {
+    violations: Vec<Violation>,
+}
+
+impl Validator {
+    fn finish(self) -> Result<(), ValidationError> {
+        if self.violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError {
+                violations: self.violations,
+            })
+        }
+    }
+
+    fn length(
+        &mut self,
+        path: String,
+        value: &str,
+        limit: usize,
+    ) {
+        let length = value.encode_utf16().count();
+        if length > limit {
+            self.violations.push(Violation::TooLong {
+                path,
+                length,
+                limit,
+            });
+        }
+    }
+
+    fn count(
+        &mut self,
+        path: &str,
+        count: usize,
+    ) {
+        if count > MAX_ARRAY_LENGTH {
+            self.violations.push(Violation::TooManyElements {
+                path: path.to_string(),
+                count,
+                limit: MAX_ARRAY_LENGTH,
+            });
+        }
+    }
+
+    fn rich_text(
+        &mut self,
+        path: &str,
+        rich_text: &RichText,
+    ) {
+        match rich_text {
+            RichText::Text { text, .. } => {
+                self.length(
+                    format!("{path}.text.content"),
+                    &text.content,
+                    MAX_TEXT_CONTENT_LENGTH,
+                );
+                if let Some(link) = &text.link {
+                    self.length(format!("{path}.text.link.url"), &link.url, MAX_URL_LENGTH);
+                }
+            }
+            RichText::Equation { equation, .. } => self.length(
+                format!("{path}.equation.expression"),
+                &equation.expression,
+                MAX_EQUATION_LENGTH,
+            ),
+            RichText::Mention { .. } => {}
+        }
+    }
+
+    fn rich_text_array(
+        &mut self,
+        path: &str,
+        rich_text: &[RichText],
+    ) {
+        self.count(path, rich_text.len());
+        for (index, item) in rich_text.iter().enumerate() {
+            self.rich_text(&format!("{path}[{index}]"), item);
+        }
+    }
+
+    fn block(
+        &mut self,
+        path: &str,
+        block: &CreateBlock,
+    ) {
+        use CreateBlock::*;
+        let field = |name: &str| {
+            if path.is_empty() {
+                name.to_string()
+            } else {
+                format!("{path}.{name}")
+            }
+        };
+
+        let (rich_text, children) = match block {
+            Paragraph { paragraph: fields }
+            | Quote { quote: fields }
+            | BulletedListItem {
+                bulleted_list_item: fields,
+            }
+            | NumberedListItem {
+                numbered_list_item: fields,
+            }
+            | Toggle { toggle: fields } => (Some(&fields.rich_text), fields.children.as_ref()),
+            Heading1 { heading_1: heading }
+            | Heading2 { heading_2: heading }
+            | Heading3 { heading_3: heading } => {
+                (Some(&heading.rich_text), heading.children.as_ref())
+            }
+            Callout { callout } => (Some(&callout.rich_text), callout.children.as_ref()),
+            ToDo { to_do } => (Some(&to_do.rich_text), to_do.children.as_ref()),
+            Code { code } => {
+                self.rich_text_array(&field("caption"), &code.caption);
+                (Some(&code.rich_text), None)
+            }
+            Template { template } => (Some(&template.rich_text), None),
+            Image { image: media }
+            | Video { video: media }
+            | File { file: media }
+            | Pdf { pdf: media } => {
+                self.rich_text_array(&field("caption"), &media.caption);
+                (None, None)
+            }
+            Bookmark { bookmark } => {
+                self.length(field("url"), &bookmark.url, MAX_URL_LENGTH);
+                self.rich_text_array(&field("caption"), &bookmark.caption);
+                (None, None)
+            }
+            Embed { embed } => {
+                self.length(field("url"), &embed.url, MAX_URL_LENGTH);
+                (None, None)
+            }
+            Equation { equation } => {
+                self.length(
+                    field("expression"),
+                    &equation.expression,
+                    MAX_EQUATION_LENGTH,
+                );
+                (None, None)
+            }
+            TableRow { table_row } => {
+                for (index, cell) in table_row.cells.iter().enumerate() {
+                    self.rich_text_array(&field(&format!("cells[{index}]")), cell);
+                }
+                (None, None)
+            }
+            ColumnList { column_list } => (None, column_list.children.as_ref()),
+            Column { column } => (None, column.children.as_ref()),
+            Table { table } => (None, table.children.as_ref()),
+            SyncedBlock { synced_block } => (None, synced_block.children.as_ref()),
+            _ => (None, None),
+        };
+
+        if let Some(rich_text) = rich_text {
+            self.rich_text_array(&field("rich_text"), rich_text);
+        }
+        if let Some(children) = children {
+            let path = field("children");
+            self.count(&path, children.len());
+            for (index, child) in children.iter().enumerate() {
+                self.block(&format!("{path}[{index}]"), child);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::text::{Link, RichTextCommon, Text};
+
+    fn text(content: &str) -> RichText {
+        RichText::Text {
+            rich_text: RichTextCommon {
+                plain_text: content.to_string(),
+                href: None,
+                annotations: None,
+            },
+            text: Text {
+                content: content.to_string(),
+                link: None,
+            },
+        }
+    }
+
+    #[test]
+    fn over_length_segment() {
+        let segment = text(&"a".repeat(MAX_TEXT_CONTENT_LENGTH + 1));
+        assert_eq!(
+            segment.validate(),
+            Err(ValidationError {
+                violations: vec![Violation::TooLong {
+                    path: "rich_text.text.content".to_string(),
+                    length: 2001,
+                    limit: 2000,
+                }]
+            })
+        );
+
+        // Characters outside the basic plane count twice, like they do for the API.
+        assert!(text(&"🦀".repeat(1000)).validate().is_ok());
+        assert!(text(&"🦀".repeat(1001)).validate().is_err());
+        assert!(text(&"a".repeat(MAX_TEXT_CONTENT_LENGTH))
+            .validate()
+            .is_ok());
+    }
+
+    #[test]
+    fn over_long_link() {
+        let mut segment = text("link");
+        if let RichText::Text { text, .. } = &mut segment {
+            text.link = Some(Link {
+                url: format!("https://example.com/{}", "a".repeat(MAX_URL_LENGTH)),
+            });
+        }
+        let error = segment.validate().unwrap_err();
+        assert!(matches!(
+            &error.violations[..],
+            [Violation::TooLong { path, limit: MAX_URL_LENGTH, .. }] if path == "rich_text.text.link.url"
+        ));
+    }
+
+    #[test]
+    fn over_count_array() {
+        let rich_text = vec![text("a"); MAX_ARRAY_LENGTH + 1];
+        assert_eq!(
+            validate_rich_text(&rich_text),
+            Err(ValidationError {
+                violations: vec![Violation::TooManyElements {
+                    path: "rich_text".to_string(),
+                    count: 101,
+                    limit: 100,
+                }]
+            })
+        );
+        assert!(validate_rich_text(&rich_text[..MAX_ARRAY_LENGTH]).is_ok());
+    }
+
+    #[test]
+    fn block_violations_are_collected() {
+        let block =
+            CreateBlock::paragraph(vec![text("a"); MAX_ARRAY_LENGTH + 1]).with_children(vec![
+                CreateBlock::paragraph("fine"),
+                CreateBlock::bulleted_list_item(text(&"a".repeat(2001))),
+            ]);
+        let error = block.validate().unwrap_err();
+        assert_eq!(
+            error.violations,
+            vec![
+                Violation::TooManyElements {
+                    path: "rich_text".to_string(),
+                    count: 101,
+                    limit: 100,
+                },
+                Violation::TooLong {
+                    path: "children[1].rich_text[0].text.content".to_string(),
+                    length: 2001,
+                    limit: 2000,
+                },
+            ]
+        );
+        assert_eq!(
+            error.to_string(),
+            "Request limits exceeded: rich_text has 101 elements, at most 100 are allowed; \
+             children[1].rich_text[0].text.content is 2001 characters long, at most 2000 are allowed"
+        );
+    }
+
+    #[test]
+    fn valid_block() {
+        let block = CreateBlock::toggle("Details").with_children(vec![
+            CreateBlock::paragraph(RichText::from_plain_chunked(&"a".repeat(5000))),
+            CreateBlock::divider(),
+        ]);
+        assert_eq!(block.validate(), Ok(()));
+    }
+}
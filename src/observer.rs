@@ -0,0 +1,23 @@
+use reqwest::{Method, StatusCode, Url};
+
+/// Gets notified about every request the [NotionApi](crate::NotionApi) client sends,
+/// e.g. to log them or to record fixtures.
+///
+/// Register one with [NotionApi::with_observer](crate::NotionApi::with_observer).
+/// Headers are not passed on, so the api token is never exposed to the observer.
+pub trait RequestObserver: Send + Sync {
+    /// Called before a request is sent. Retries of the request are not reported again.
+    fn on_request(
+        &self,
+        method: &Method,
+        url: &Url,
+        body: Option<&str>,
+    );
+
+    /// Called with the final response to a request, before it is parsed.
+    fn on_response(
+        &self,
+        status: StatusCode,
+        body: &str,
+    );
+}
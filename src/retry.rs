@@ -0,0 +1,221 @@
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// Controls how [`NotionApi`](crate::NotionApi) replays requests that were
+/// rate limited (HTTP 429) or, unless disabled, failed with a server error (HTTP 5xx).
+///
+/// Enable it with [`NotionApi::with_retry`](crate::NotionApi::with_retry).
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct RetryConfig {
+    /// How many times a request is replayed before giving up.
+    pub max_retries: u32,
+    /// Sleep for the duration given by the `Retry-After` header of a 429 response
+    /// instead of the computed backoff.
+    pub respect_retry_after: bool,
+    /// How long to wait between attempts.
+    pub backoff: BackoffStrategy,
+    /// Replay requests that failed with a server error as well, not only rate limited ones.
+    /// A server error doesn't always mean the request had no effect, so creating something
+    /// twice is possible when this is enabled.
+    pub retry_server_errors: bool,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        RetryConfig {
+            max_retries: 3,
+            respect_retry_after: true,
+            backoff: BackoffStrategy::default(),
+            retry_server_errors: true,
+        }
+    }
+}
+
+/// The delays between the attempts of a request.
+///
+/// A short fixed delay suits interactive use, while batch jobs are better off backing off
+/// exponentially to get out of the way of the rate limit.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum BackoffStrategy {
+    /// Always wait the same time.
+    Fixed(Duration),
+    /// Wait `base` before the first retry and double the delay on every subsequent one,
+    /// up to `max`. The actual delay is picked randomly between half and all of it,
+    /// so that concurrent requests don't retry in lockstep.
+    Exponential { base: Duration, max: Duration },
+}
+
+impl Default for BackoffStrategy {
+    fn default() -> Self {
+        BackoffStrategy::Exponential {
+            base: Duration::from_millis(500),
+            max: Duration::from_secs(30),
+        }
+    }
+}
+
+impl BackoffStrategy {
+    /// The delay before retrying `attempt` (starting at 0), before any jitter is applied.
+    pub fn delay(
+        &self,
+        attempt: u32,
+    ) -> Duration {
+        match *self {
+            BackoffStrategy::Fixed(delay) => delay,
+            BackoffStrategy::Exponential { base, max } => {
+                base.saturating_mul(2u32.saturating_pow(attempt)).min(max)
+            }
+        }
+    }
+}
+
+impl RetryConfig {
+    pub(crate) fn should_retry(
+        &self,
+        status: StatusCode,
+    ) -> bool {
+        status == StatusCode::TOO_MANY_REQUESTS
+            || (self.retry_server_errors && status.is_server_error())
+    }
+
+    /// The delay before retrying `attempt` (starting at 0).
+    pub(crate) fn delay(
+        &self,
+        attempt: u32,
+        status: StatusCode,
+        retry_after: Option<Duration>,
+    ) -> Duration {
+        if status == StatusCode::TOO_MANY_REQUESTS && self.respect_retry_after {
+            if let Some(retry_after) = retry_after {
+                return retry_after;
+            }
+        }
+
+        let backoff = self.backoff.delay(attempt);
+        match self.backoff {
+            BackoffStrategy::Fixed(_) => backoff,
+            // Pick a random delay between half the backoff and the full backoff.
+            BackoffStrategy::Exponential { .. } => {
+                let half = backoff / 2;
+                half + half.mul_f64(jitter())
+            }
+        }
+    }
+}
+
+/// Parses a `Retry-After` header given in seconds.
+/// Notion doesn't send the HTTP-date form, so it is ignored.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// A random value in `[0, 1)`, seeded from the std hasher so we don't need a `rand` dependency.
+fn jitter() -> f64 {
+    let random = RandomState::new().build_hasher().finish();
+    (random >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{retry_after, BackoffStrategy, RetryConfig};
+    use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+    use reqwest::StatusCode;
+    use std::time::Duration;
+
+    #[test]
+    fn parses_retry_after_seconds() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("7"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(7)));
+
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers), None);
+    }
+
+    #[test]
+    fn backoff_is_bounded() {
+        let config = RetryConfig {
+            backoff: BackoffStrategy::Exponential {
+                base: Duration::from_secs(1),
+                max: Duration::from_secs(4),
+            },
+            ..Default::default()
+        };
+
+        for attempt in 0..10 {
+            let delay = config.delay(attempt, StatusCode::BAD_GATEWAY, None);
+            let backoff = Duration::from_secs(2u64.pow(attempt).min(4));
+            assert!(delay >= backoff / 2 && delay <= backoff, "{:?}", delay);
+        }
+    }
+
+    #[test]
+    fn exponential_backoff_doubles_up_to_max() {
+        let backoff = BackoffStrategy::Exponential {
+            base: Duration::from_millis(250),
+            max: Duration::from_secs(3),
+        };
+
+        let delays: Vec<_> = (0..7).map(|attempt| backoff.delay(attempt)).collect();
+        assert_eq!(
+            delays,
+            [250, 500, 1000, 2000, 3000, 3000, 3000].map(Duration::from_millis)
+        );
+        assert_eq!(backoff.delay(u32::MAX), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn fixed_backoff_has_no_jitter() {
+        let config = RetryConfig {
+            backoff: BackoffStrategy::Fixed(Duration::from_millis(300)),
+            ..Default::default()
+        };
+
+        for attempt in 0..5 {
+            assert_eq!(
+                config.delay(attempt, StatusCode::BAD_GATEWAY, None),
+                Duration::from_millis(300)
+            );
+        }
+    }
+
+    #[test]
+    fn server_errors_can_be_left_alone() {
+        let rate_limits_only = RetryConfig {
+            retry_server_errors: false,
+            ..Default::default()
+        };
+
+        assert!(RetryConfig::default().should_retry(StatusCode::BAD_GATEWAY));
+        assert!(!rate_limits_only.should_retry(StatusCode::BAD_GATEWAY));
+        assert!(rate_limits_only.should_retry(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!rate_limits_only.should_retry(StatusCode::BAD_REQUEST));
+    }
+
+    #[test]
+    fn retry_after_overrides_backoff() {
+        let config = RetryConfig::default();
+        let retry_after = Duration::from_secs(12);
+
+        assert_eq!(
+            config.delay(0, StatusCode::TOO_MANY_REQUESTS, Some(retry_after)),
+            retry_after
+        );
+        assert!(
+            config.delay(0, StatusCode::INTERNAL_SERVER_ERROR, Some(retry_after)) < retry_after
+        );
+    }
+}
@@ -0,0 +1,296 @@
+//! A fake Notion server for testing code that uses this crate, enabled by the `testing` feature.
+//!
+//! [MockNotion] serves canned responses for the common endpoints and hands out a [NotionApi]
+//! pointed at itself. Anything it doesn't cover can be mounted on [MockNotion::server] directly.
+//!
+//! ```
+//! use notion::ids::PageId;
+//! use notion::testing::{self, MockNotion};
+//! use std::str::FromStr;
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let notion = MockNotion::start().await;
+//! let id = PageId::from_str("bb85a889-3eb3-4146-9325-80508fb5e23d").unwrap();
+//! notion.page(&testing::page(id.clone())).await;
+//!
+//! let page = notion.client().get_page(id.clone()).await.unwrap();
+//! assert_eq!(page.id, id);
+//! # }
+//! ```
+
+use crate::ids::{AsIdentifier, BlockId, DatabaseId, PageId};
+use crate::models::block::Block;
+use crate::models::error::ErrorCode;
+use crate::models::users::User;
+use crate::models::{Database, ListResponse, Object, Page, Parent, Properties};
+use crate::NotionApi;
+use serde_json::json;
+use std::collections::HashMap;
+use time::OffsetDateTime;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// A fake Notion server running on a local port for as long as it is kept around.
+///
+/// Responses mounted first take precedence, so mount specific responses before general ones.
+pub struct MockNotion {
+    server: MockServer,
+}
+
+impl MockNotion {
+    /// Starts a server without any responses, every request is answered with a 404.
+    pub async fn start() -> Self {
+        MockNotion {
+            server: MockServer::start().await,
+        }
+    }
+
+    /// The underlying server, for mounting responses and inspecting the received requests.
+    pub fn server(&self) -> &MockServer {
+        &self.server
+    }
+
+    /// The address of the server, to be passed to [NotionApiBuilder::base_url](crate::NotionApiBuilder::base_url).
+    pub fn uri(&self) -> String {
+        self.server.uri()
+    }
+
+    /// A client sending its requests to this server.
+    pub fn client(&self) -> NotionApi {
+        NotionApi::builder("test-token".to_string())
+            .base_url(self.server.uri())
+            .build()
+            .expect("the test token is a valid header value")
+    }
+
+    /// Responds to [NotionApi::get_page] for `page`.
+    pub async fn page(
+        &self,
+        page: &Page,
+    ) {
+        self.respond(
+            "GET",
+            format!("/v1/pages/{}", page.id),
+            Object::Page { page: page.clone() },
+        )
+        .await;
+    }
+
+    /// Responds to [NotionApi::get_database] for `database`.
+    pub async fn database(
+        &self,
+        database: &Database,
+    ) {
+        self.respond(
+            "GET",
+            format!("/v1/databases/{}", database.id),
+            Object::Database {
+                database: database.clone(),
+            },
+        )
+        .await;
+    }
+
+    /// Responds to [NotionApi::query_database] of `database` with a single page of `pages`,
+    /// whatever the query.
+    pub async fn query_database<T: AsIdentifier<DatabaseId>>(
+        &self,
+        database: T,
+        pages: Vec<Page>,
+    ) {
+        self.respond(
+            "POST",
+            format!("/v1/databases/{}/query", database.as_id()),
+            list(pages.into_iter().map(|page| Object::Page { page })),
+        )
+        .await;
+    }
+
+    /// Responds to [NotionApi::search] with a single page of `results`, whatever the query.
+    pub async fn search(
+        &self,
+        results: Vec<Object>,
+    ) {
+        self.respond("POST", "/v1/search".to_string(), list(results))
+            .await;
+    }
+
+    /// Responds to [NotionApi::get_block_children] of `block` with a single page of `children`.
+    pub async fn block_children<T: AsIdentifier<BlockId>>(
+        &self,
+        block: T,
+        children: Vec<Block>,
+    ) {
+        self.respond(
+            "GET",
+            format!("/v1/blocks/{}/children", block.as_id()),
+            list(children.into_iter().map(|block| Object::Block { block })),
+        )
+        .await;
+    }
+
+    /// Responds to [NotionApi::list_users] with a single page of `users`.
+    pub async fn users(
+        &self,
+        users: Vec<User>,
+    ) {
+        self.respond(
+            "GET",
+            "/v1/users".to_string(),
+            list(users.into_iter().map(|user| Object::User { user })),
+        )
+        .await;
+    }
+
+    /// Answers requests for `request_path`, e.g. `/v1/pages/<id>`, with an error like Notion's.
+    /// The client passes it on as [Error::ApiError](crate::Error::ApiError).
+    pub async fn error(
+        &self,
+        http_method: &str,
+        request_path: &str,
+        status: u16,
+        code: ErrorCode,
+        message: &str,
+    ) {
+        let error = json!({
+            "object": "error",
+            "status": status,
+            "code": code,
+            "message": message,
+        });
+        Mock::given(method(http_method))
+            .and(path(request_path))
+            .respond_with(ResponseTemplate::new(status).set_body_json(error))
+            .mount(&self.server)
+            .await;
+    }
+
+    async fn respond(
+        &self,
+        http_method: &str,
+        request_path: String,
+        object: Object,
+    ) {
+        Mock::given(method(http_method))
+            .and(path(request_path))
+            .respond_with(ResponseTemplate::new(200).set_body_json(object))
+            .mount(&self.server)
+            .await;
+    }
+}
+
+fn list(results: impl IntoIterator<Item = Object>) -> Object {
+    Object::List {
+        list: ListResponse {
+            results: results.into_iter().collect(),
+            next_cursor: None,
+            has_more: false,
+        },
+    }
+}
+
+/// A page in the workspace without properties, to be filled in as a test needs.
+pub fn page(id: PageId) -> Page {
+    Page {
+        id,
+        created_time: OffsetDateTime::UNIX_EPOCH,
+        last_edited_time: OffsetDateTime::UNIX_EPOCH,
+        archived: false,
+        in_trash: None,
+        properties: Properties {
+            properties: HashMap::new(),
+        },
+        parent: Parent::Workspace,
+        icon: None,
+        cover: None,
+        url: None,
+        blocks: None,
+    }
+}
+
+/// A database in the workspace without a title or properties.
+pub fn database(id: DatabaseId) -> Database {
+    Database {
+        id,
+        created_time: OffsetDateTime::UNIX_EPOCH,
+        last_edited_time: OffsetDateTime::UNIX_EPOCH,
+        title: vec![],
+        properties: HashMap::new(),
+        cover: None,
+        description: vec![],
+        is_inline: None,
+        archived: None,
+        in_trash: None,
+        url: None,
+        public_url: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::search::DatabaseQuery;
+    use crate::Error;
+    use std::str::FromStr;
+
+    const PAGE_ID: &str = "bb85a889-3eb3-4146-9325-80508fb5e23d";
+    const DATABASE_ID: &str = "5d794de0-2224-49d3-86f9-3540db13d884";
+
+    #[tokio::test]
+    async fn canned_responses() {
+        let notion = MockNotion::start().await;
+        let page_id = PageId::from_str(PAGE_ID).unwrap();
+        let database_id = DatabaseId::from_str(DATABASE_ID).unwrap();
+        let mut page = page(page_id.clone());
+        page.parent = Parent::Database {
+            database_id: database_id.clone(),
+        };
+        notion.page(&page).await;
+        notion.database(&database(database_id.clone())).await;
+        notion
+            .query_database(&database_id, vec![page.clone()])
+            .await;
+
+        let api = notion.client();
+        assert_eq!(api.get_page(&page_id).await.unwrap(), page);
+        assert_eq!(
+            api.get_database(&database_id).await.unwrap().id,
+            database_id
+        );
+        let rows = api
+            .query_database(&database_id, DatabaseQuery::default())
+            .await
+            .unwrap();
+        assert_eq!(rows.results, vec![page]);
+        assert!(!rows.has_more);
+    }
+
+    #[tokio::test]
+    async fn errors_and_unmocked_requests() {
+        let notion = MockNotion::start().await;
+        let page_id = PageId::from_str(PAGE_ID).unwrap();
+        notion
+            .error(
+                "GET",
+                &format!("/v1/pages/{page_id}"),
+                404,
+                ErrorCode::ObjectNotFound,
+                "Could not find page",
+            )
+            .await;
+
+        let api = notion.client();
+        match api.get_page(&page_id).await {
+            Err(Error::ApiError { error, .. }) => {
+                assert_eq!(error.code, ErrorCode::ObjectNotFound);
+                assert_eq!(error.message, "Could not find page");
+            }
+            other => panic!("expected an api error, got {other:?}"),
+        }
+
+        let missing = DatabaseId::from_str(DATABASE_ID).unwrap();
+        assert!(api.get_database(missing).await.is_err());
+        assert_eq!(notion.server().received_requests().await.unwrap().len(), 2);
+    }
+}